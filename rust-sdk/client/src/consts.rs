@@ -13,10 +13,10 @@ pub const NO_UPPER_LIMIT_ORDER: i32 = 2147483647;
 
 pub const UNLIMITED_SUPPLY_LIMIT: u64 = u64::MAX;
 
-pub const TUNA_POSITION_FLAGS_LOWER_LIMIT_ORDER_SWAP_TO_TOKEN_A: u32 = 1;
-pub const TUNA_POSITION_FLAGS_LOWER_LIMIT_ORDER_SWAP_TO_TOKEN_B: u32 = 1 << 1;
-pub const TUNA_POSITION_FLAGS_UPPER_LIMIT_ORDER_SWAP_TO_TOKEN_A: u32 = 1 << 2;
-pub const TUNA_POSITION_FLAGS_UPPER_LIMIT_ORDER_SWAP_TO_TOKEN_B: u32 = 1 << 3;
+pub use defituna_core::{
+    TUNA_POSITION_FLAGS_LOWER_LIMIT_ORDER_SWAP_TO_TOKEN_A, TUNA_POSITION_FLAGS_LOWER_LIMIT_ORDER_SWAP_TO_TOKEN_B,
+    TUNA_POSITION_FLAGS_UPPER_LIMIT_ORDER_SWAP_TO_TOKEN_A, TUNA_POSITION_FLAGS_UPPER_LIMIT_ORDER_SWAP_TO_TOKEN_B,
+};
 pub const TUNA_POSITION_FLAGS_AUTO_COMPOUND_YIELD: u32 = 1 << 4;
 pub const TUNA_POSITION_FLAGS_AUTO_COMPOUND_YIELD_WITH_LEVERAGE: u32 = 1 << 5;
 pub const TUNA_POSITION_FLAGS_ALLOW_REBALANCING: u32 = 1 << 6;
@@ -1,7 +1,6 @@
 use crate::accounts::TunaSpotPosition;
 use defituna_core::TunaSpotPositionFacade;
 
-
 impl From<TunaSpotPosition> for TunaSpotPositionFacade {
     fn from(val: TunaSpotPosition) -> Self {
         TunaSpotPositionFacade {
@@ -0,0 +1,248 @@
+use crate::accounts::{LendingPosition, Market, TunaConfig, TunaLpPosition, TunaSpotPosition, Vault};
+use crate::{DecodedAccount, MaybeAccount};
+use anyhow::{anyhow, Result};
+use fusionamm_client::FusionPool;
+use orca_whirlpools_client::{Position, Whirlpool};
+use solana_account::Account;
+use solana_client::nonblocking::rpc_client::RpcClient as AsyncRpcClient;
+use solana_client::rpc_client::RpcClient;
+use solana_pubkey::Pubkey;
+use solana_sysvar::clock::Clock;
+use solana_sysvar::rent::Rent;
+use solana_sysvar::slot_hashes::SysvarId;
+use solana_sysvar::Sysvar;
+use std::collections::HashMap;
+use std::future::Future;
+use std::pin::pin;
+use std::sync::Arc;
+use std::task::{Context, Poll, Wake, Waker};
+use std::thread::Thread;
+
+/// The source of the on-chain state read by the transaction builders.
+///
+/// Implemented for the blocking and the nonblocking RPC clients and for [`AccountSnapshot`].
+/// Implement it to build transactions from any other account cache, e.g. one maintained by websocket subscriptions.
+///
+/// The blocking builders drive the returned futures on the current thread, so they only accept a [`SyncAccountFetcher`].
+/// Use the `_async` builders with fetchers that depend on an async runtime, like the nonblocking RPC client.
+pub trait AccountFetcher: Sync {
+    /// Returns the accounts in the order of the addresses, `None` for the accounts that don't exist.
+    fn get_multiple_accounts(&self, addresses: &[Pubkey]) -> impl Future<Output = Result<Vec<Option<Account>>>> + Send;
+
+    fn get_account(&self, address: &Pubkey) -> impl Future<Output = Result<Account>> + Send {
+        async move {
+            let mut accounts = self.get_multiple_accounts(&[*address]).await?;
+            accounts.pop().flatten().ok_or(anyhow!("Account not found: {}", address))
+        }
+    }
+
+    fn get_rent(&self) -> impl Future<Output = Result<Rent>> + Send {
+        async move {
+            let rent = self.get_account(&Rent::id()).await?;
+            Ok(bincode::deserialize(&rent.data)?)
+        }
+    }
+
+    fn get_clock(&self) -> impl Future<Output = Result<Clock>> + Send {
+        async move {
+            let clock = self.get_account(&Clock::id()).await?;
+            Ok(bincode::deserialize(&clock.data)?)
+        }
+    }
+}
+
+/// An [`AccountFetcher`] whose futures complete without an async runtime. Only these fetchers can be used with the blocking builders.
+///
+/// Implement it for a custom fetcher only if its futures never wait on a runtime, e.g. when it serves the accounts from memory.
+pub trait SyncAccountFetcher: AccountFetcher {}
+
+impl AccountFetcher for RpcClient {
+    async fn get_multiple_accounts(&self, addresses: &[Pubkey]) -> Result<Vec<Option<Account>>> {
+        Ok(RpcClient::get_multiple_accounts(self, addresses)?)
+    }
+}
+
+impl SyncAccountFetcher for RpcClient {}
+
+impl AccountFetcher for AsyncRpcClient {
+    async fn get_multiple_accounts(&self, addresses: &[Pubkey]) -> Result<Vec<Option<Account>>> {
+        Ok(AsyncRpcClient::get_multiple_accounts(self, addresses).await?)
+    }
+}
+
+/// An in-memory set of accounts.
+///
+/// Builders called with a snapshot don't make any network requests. Accounts missing in the snapshot are treated as non-existent.
+#[derive(Debug, Default, Clone)]
+pub struct AccountSnapshot {
+    accounts: HashMap<Pubkey, Account>,
+}
+
+impl AccountSnapshot {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a snapshot of the accounts loaded from another fetcher. Accounts that don't exist are skipped.
+    pub async fn fetch(fetcher: &impl AccountFetcher, addresses: &[Pubkey]) -> Result<Self> {
+        let accounts = fetcher.get_multiple_accounts(addresses).await?;
+        Ok(addresses
+            .iter()
+            .zip(accounts)
+            .filter_map(|(address, account)| Some((*address, account?)))
+            .collect())
+    }
+
+    pub fn get(&self, address: &Pubkey) -> Option<&Account> {
+        self.accounts.get(address)
+    }
+
+    pub fn insert(&mut self, address: Pubkey, account: Account) -> Option<Account> {
+        self.accounts.insert(address, account)
+    }
+
+    pub fn remove(&mut self, address: &Pubkey) -> Option<Account> {
+        self.accounts.remove(address)
+    }
+
+    /// Inserts a sysvar account, e.g. [`Rent`] or [`Clock`].
+    pub fn insert_sysvar<S: Sysvar>(&mut self, sysvar: &S) -> Result<Option<Account>> {
+        let data = bincode::serialize(sysvar)?;
+        let account = Account {
+            lamports: 1,
+            data,
+            owner: solana_sdk_ids::sysvar::ID,
+            executable: false,
+            rent_epoch: 0,
+        };
+        Ok(self.insert(S::id(), account))
+    }
+}
+
+impl FromIterator<(Pubkey, Account)> for AccountSnapshot {
+    fn from_iter<I: IntoIterator<Item = (Pubkey, Account)>>(iter: I) -> Self {
+        Self {
+            accounts: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl Extend<(Pubkey, Account)> for AccountSnapshot {
+    fn extend<I: IntoIterator<Item = (Pubkey, Account)>>(&mut self, iter: I) {
+        self.accounts.extend(iter)
+    }
+}
+
+impl AccountFetcher for AccountSnapshot {
+    async fn get_multiple_accounts(&self, addresses: &[Pubkey]) -> Result<Vec<Option<Account>>> {
+        Ok(addresses.iter().map(|address| self.accounts.get(address).cloned()).collect())
+    }
+}
+
+impl SyncAccountFetcher for AccountSnapshot {}
+
+struct ThreadWaker(Thread);
+
+impl Wake for ThreadWaker {
+    fn wake(self: Arc<Self>) {
+        self.0.unpark();
+    }
+}
+
+/// Runs a builder future to completion on the current thread.
+pub(crate) fn block_on<F: Future>(future: F) -> F::Output {
+    let mut future = pin!(future);
+    let waker = Waker::from(Arc::new(ThreadWaker(std::thread::current())));
+    let mut context = Context::from_waker(&waker);
+    loop {
+        match future.as_mut().poll(&mut context) {
+            Poll::Ready(output) => return output,
+            Poll::Pending => std::thread::park(),
+        }
+    }
+}
+
+async fn fetch_all_maybe_decoded<T>(
+    rpc: &impl AccountFetcher,
+    addresses: &[Pubkey],
+    from_bytes: fn(&[u8]) -> std::io::Result<T>,
+) -> Result<Vec<MaybeAccount<T>>> {
+    let accounts = rpc.get_multiple_accounts(addresses).await?;
+    let mut decoded_accounts = Vec::with_capacity(addresses.len());
+    for (address, account) in addresses.iter().zip(accounts) {
+        decoded_accounts.push(match account {
+            Some(account) => MaybeAccount::Exists(DecodedAccount {
+                address: *address,
+                data: from_bytes(&account.data)?,
+                account,
+            }),
+            None => MaybeAccount::NotFound(*address),
+        });
+    }
+    Ok(decoded_accounts)
+}
+
+async fn fetch_all_decoded<T>(
+    rpc: &impl AccountFetcher,
+    addresses: &[Pubkey],
+    from_bytes: fn(&[u8]) -> std::io::Result<T>,
+) -> Result<Vec<DecodedAccount<T>>> {
+    fetch_all_maybe_decoded(rpc, addresses, from_bytes)
+        .await?
+        .into_iter()
+        .map(|account| match account {
+            MaybeAccount::Exists(account) => Ok(account),
+            MaybeAccount::NotFound(address) => Err(anyhow!("Account not found: {}", address)),
+        })
+        .collect()
+}
+
+async fn fetch_decoded<T>(rpc: &impl AccountFetcher, address: &Pubkey, from_bytes: fn(&[u8]) -> std::io::Result<T>) -> Result<DecodedAccount<T>> {
+    let mut accounts = fetch_all_decoded(rpc, &[*address], from_bytes).await?;
+    Ok(accounts.remove(0))
+}
+
+async fn fetch_maybe_decoded<T>(rpc: &impl AccountFetcher, address: &Pubkey, from_bytes: fn(&[u8]) -> std::io::Result<T>) -> Result<MaybeAccount<T>> {
+    let mut accounts = fetch_all_maybe_decoded(rpc, &[*address], from_bytes).await?;
+    Ok(accounts.remove(0))
+}
+
+pub(crate) async fn fetch_tuna_config(rpc: &impl AccountFetcher, address: &Pubkey) -> Result<DecodedAccount<TunaConfig>> {
+    fetch_decoded(rpc, address, TunaConfig::from_bytes).await
+}
+
+pub(crate) async fn fetch_market(rpc: &impl AccountFetcher, address: &Pubkey) -> Result<DecodedAccount<Market>> {
+    fetch_decoded(rpc, address, Market::from_bytes).await
+}
+
+pub(crate) async fn fetch_all_vault(rpc: &impl AccountFetcher, addresses: &[Pubkey]) -> Result<Vec<DecodedAccount<Vault>>> {
+    fetch_all_decoded(rpc, addresses, Vault::from_bytes).await
+}
+
+pub(crate) async fn fetch_tuna_lp_position(rpc: &impl AccountFetcher, address: &Pubkey) -> Result<DecodedAccount<TunaLpPosition>> {
+    fetch_decoded(rpc, address, TunaLpPosition::from_bytes).await
+}
+
+pub(crate) async fn fetch_tuna_spot_position(rpc: &impl AccountFetcher, address: &Pubkey) -> Result<DecodedAccount<TunaSpotPosition>> {
+    fetch_decoded(rpc, address, TunaSpotPosition::from_bytes).await
+}
+
+pub(crate) async fn fetch_maybe_tuna_spot_position(rpc: &impl AccountFetcher, address: &Pubkey) -> Result<MaybeAccount<TunaSpotPosition>> {
+    fetch_maybe_decoded(rpc, address, TunaSpotPosition::from_bytes).await
+}
+
+pub(crate) async fn fetch_maybe_lending_position(rpc: &impl AccountFetcher, address: &Pubkey) -> Result<MaybeAccount<LendingPosition>> {
+    fetch_maybe_decoded(rpc, address, LendingPosition::from_bytes).await
+}
+
+pub(crate) async fn fetch_whirlpool(rpc: &impl AccountFetcher, address: &Pubkey) -> Result<DecodedAccount<Whirlpool>> {
+    fetch_decoded(rpc, address, Whirlpool::from_bytes).await
+}
+
+pub(crate) async fn fetch_position(rpc: &impl AccountFetcher, address: &Pubkey) -> Result<DecodedAccount<Position>> {
+    fetch_decoded(rpc, address, Position::from_bytes).await
+}
+
+pub(crate) async fn fetch_fusion_pool(rpc: &impl AccountFetcher, address: &Pubkey) -> Result<DecodedAccount<FusionPool>> {
+    fetch_decoded(rpc, address, FusionPool::from_bytes).await
+}
@@ -1,11 +1,21 @@
+use crate::accounts::Vault;
 use crate::consts::LEVERAGE_ONE;
 use crate::generated::accounts::Market;
+use crate::TunaError as ErrorCode;
+use defituna_core::fixed::Rounding;
 use std::fmt;
 
 impl Market {
     pub fn get_max_leverage(&self) -> f64 {
         self.max_leverage as f64 / LEVERAGE_ONE as f64
     }
+
+    /// Returns the amounts of tokens A and B that can still be borrowed before the market borrow limits are reached.
+    pub fn get_available_borrow_limit(&self, vault_a: &Vault, vault_b: &Vault) -> Result<(u64, u64), ErrorCode> {
+        let borrowed_a = vault_a.calculate_borrowed_funds(self.borrowed_shares_a, Rounding::Up)?;
+        let borrowed_b = vault_b.calculate_borrowed_funds(self.borrowed_shares_b, Rounding::Up)?;
+        Ok((self.borrow_limit_a.saturating_sub(borrowed_a), self.borrow_limit_b.saturating_sub(borrowed_b)))
+    }
 }
 
 impl fmt::Display for Market {
@@ -1,6 +1,7 @@
 pub mod market;
 pub mod tuna_lp_position;
 pub mod tuna_position;
+pub mod tuna_position_flags;
 pub mod tuna_spot_position;
 pub mod vault;
 
@@ -11,6 +12,8 @@ pub use tuna_lp_position::*;
 #[allow(unused_imports)]
 pub use tuna_position::*;
 #[allow(unused_imports)]
+pub use tuna_position_flags::*;
+#[allow(unused_imports)]
 pub use tuna_spot_position::*;
 #[allow(unused_imports)]
 pub use vault::*;
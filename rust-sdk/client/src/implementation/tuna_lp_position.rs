@@ -1,9 +1,15 @@
 use crate::accounts::*;
 use crate::consts::HUNDRED_PERCENT;
+use crate::implementation::tuna_position::core_error_to_tuna_error;
 use crate::types::*;
 use crate::{impl_tuna_position, TunaError as ErrorCode, TunaLimitOrderType, TunaPosition, TunaPositionKind};
 use defituna_core::fixed::Rounding;
 use defituna_core::price::sqrt_price_x64_to_price_x64;
+use defituna_core::{
+    get_liquidate_lp_position_quote, get_lp_limit_order_execution_quote, get_lp_position_future_liquidation_prices, get_lp_position_pnl,
+    get_lp_position_time_to_liquidation, LiquidateLpPositionQuoteArgs, LiquidateLpPositionQuoteResult, LiquidationPrices,
+    LpLimitOrderExecutionQuoteArgs, LpLimitOrderExecutionQuoteResult, LpPositionInterestAccrualArgs, LpPositionPnl, LpPositionPnlArgs,
+};
 use fixed::types::U64F64;
 use fusionamm_core::{
     get_amounts_from_liquidity, sqrt_price_to_tick_index, tick_index_to_sqrt_price, MAX_SQRT_PRICE, MAX_TICK_INDEX, MIN_SQRT_PRICE, MIN_TICK_INDEX,
@@ -13,6 +19,138 @@ use std::fmt;
 
 impl_tuna_position!(TunaLpPosition);
 
+impl TunaLpPosition {
+    /// Returns the unrealized PnL of the position. Vaults must be passed with accrued interest.
+    pub fn compute_pnl(&self, sqrt_price: u128, vault_a: &Vault, vault_b: &Vault) -> Result<LpPositionPnl, ErrorCode> {
+        // Old positions only store the entry tick index.
+        let entry_sqrt_price = if self.entry_sqrt_price > 0 {
+            self.entry_sqrt_price
+        } else {
+            tick_index_to_sqrt_price(self.tick_entry_index)
+        };
+
+        get_lp_position_pnl(LpPositionPnlArgs {
+            liquidity: self.liquidity,
+            tick_lower_index: self.tick_lower_index,
+            tick_upper_index: self.tick_upper_index,
+            leftovers_a: self.leftovers_a,
+            leftovers_b: self.leftovers_b,
+            loan_funds_a: self.loan_funds_a,
+            loan_funds_b: self.loan_funds_b,
+            debt_a: vault_a.calculate_borrowed_funds(self.loan_shares_a, Rounding::Up)?,
+            debt_b: vault_b.calculate_borrowed_funds(self.loan_shares_b, Rounding::Up)?,
+            compounded_yield_a: self.compounded_yield_a,
+            compounded_yield_b: self.compounded_yield_b,
+            entry_sqrt_price,
+            sqrt_price,
+        })
+        .map_err(core_error_to_tuna_error)
+    }
+
+    /// Returns the number of seconds until the position becomes unhealthy at the provided price due to accrued interest.
+    /// Vaults must be passed with accrued interest.
+    pub fn compute_time_to_liquidation(&self, sqrt_price: u128, market: &Market, vault_a: &Vault, vault_b: &Vault) -> Result<u64, ErrorCode> {
+        get_lp_position_time_to_liquidation(self.get_interest_accrual_args(market, vault_a, vault_b)?, sqrt_price).map_err(core_error_to_tuna_error)
+    }
+
+    /// Returns the liquidation prices after `duration` seconds, taking into account the interest accrued on the debt.
+    /// Vaults must be passed with accrued interest.
+    pub fn compute_liquidation_prices_at(
+        &self,
+        duration: u64,
+        market: &Market,
+        vault_a: &Vault,
+        vault_b: &Vault,
+    ) -> Result<LiquidationPrices, ErrorCode> {
+        get_lp_position_future_liquidation_prices(self.get_interest_accrual_args(market, vault_a, vault_b)?, duration)
+            .map_err(core_error_to_tuna_error)
+    }
+
+    /// Returns the outcome of the position liquidation at the provided pool price and swap fee rate.
+    /// Vaults must be passed with accrued interest.
+    pub fn compute_liquidation_outcome(
+        &self,
+        sqrt_price: u128,
+        swap_fee_rate: u16,
+        decrease_percent: u32,
+        market: &Market,
+        vault_a: &Vault,
+        vault_b: &Vault,
+    ) -> Result<LiquidateLpPositionQuoteResult, ErrorCode> {
+        get_liquidate_lp_position_quote(LiquidateLpPositionQuoteArgs {
+            liquidity: self.liquidity,
+            tick_lower_index: self.tick_lower_index,
+            tick_upper_index: self.tick_upper_index,
+            leftovers_a: self.leftovers_a,
+            leftovers_b: self.leftovers_b,
+            debt_a: vault_a.calculate_borrowed_funds(self.loan_shares_a, Rounding::Up)?,
+            debt_b: vault_b.calculate_borrowed_funds(self.loan_shares_b, Rounding::Up)?,
+            decrease_percent,
+            liquidation_fee: market.liquidation_fee,
+            swap_fee_rate,
+            sqrt_price,
+        })
+        .map_err(core_error_to_tuna_error)
+    }
+
+    /// Returns the outcome of the limit order execution at its trigger price.
+    /// Vaults must be passed with accrued interest.
+    pub fn compute_limit_order_execution(
+        &self,
+        limit_order_type: TunaLimitOrderType,
+        swap_fee_rate: u16,
+        vault_a: &Vault,
+        vault_b: &Vault,
+    ) -> Result<LpLimitOrderExecutionQuoteResult, ErrorCode> {
+        let upper_limit_order = limit_order_type == TunaLimitOrderType::TakeProfit;
+
+        // Old positions only store the limit order tick indexes.
+        let trigger_sqrt_price = if self.version >= 7 {
+            if upper_limit_order {
+                self.upper_limit_order_sqrt_price
+            } else {
+                self.lower_limit_order_sqrt_price
+            }
+        } else if upper_limit_order {
+            tick_index_to_sqrt_price(self.tick_take_profit_index.min(MAX_TICK_INDEX))
+        } else {
+            tick_index_to_sqrt_price(self.tick_stop_loss_index.max(MIN_TICK_INDEX))
+        };
+
+        get_lp_limit_order_execution_quote(LpLimitOrderExecutionQuoteArgs {
+            liquidity: self.liquidity,
+            tick_lower_index: self.tick_lower_index,
+            tick_upper_index: self.tick_upper_index,
+            leftovers_a: self.leftovers_a,
+            leftovers_b: self.leftovers_b,
+            debt_a: vault_a.calculate_borrowed_funds(self.loan_shares_a, Rounding::Up)?,
+            debt_b: vault_b.calculate_borrowed_funds(self.loan_shares_b, Rounding::Up)?,
+            flags: self.flags,
+            upper_limit_order,
+            trigger_sqrt_price,
+            swap_fee_rate,
+        })
+        .map_err(core_error_to_tuna_error)
+    }
+
+    fn get_interest_accrual_args(&self, market: &Market, vault_a: &Vault, vault_b: &Vault) -> Result<LpPositionInterestAccrualArgs, ErrorCode> {
+        Ok(LpPositionInterestAccrualArgs {
+            liquidity: self.liquidity,
+            tick_lower_index: self.tick_lower_index,
+            tick_upper_index: self.tick_upper_index,
+            leftovers_a: self.leftovers_a,
+            leftovers_b: self.leftovers_b,
+            debt_a: vault_a.calculate_borrowed_funds(self.loan_shares_a, Rounding::Up)?,
+            debt_b: vault_b.calculate_borrowed_funds(self.loan_shares_b, Rounding::Up)?,
+            liquidation_threshold: market.liquidation_threshold,
+            interest_rate_a: vault_a.interest_rate,
+            utilization_a: vault_a.get_utilization(),
+            interest_rate_b: vault_b.interest_rate,
+            utilization_b: vault_b.get_utilization(),
+        })
+    }
+}
+
 impl TunaPosition for TunaLpPosition {
     fn kind(&self) -> TunaPositionKind {
         TunaPositionKind::Liquidity
@@ -47,7 +185,7 @@ impl TunaPosition for TunaLpPosition {
         let upper_sqrt_price = tick_index_to_sqrt_price(self.tick_upper_index);
 
         get_amounts_from_liquidity(self.liquidity, sqrt_price, lower_sqrt_price, upper_sqrt_price, false)
-            .map_err(|_| ErrorCode::MathOverflow)
+            .map_err(core_error_to_tuna_error)
             .map(|amounts| (amounts.a, amounts.b))
     }
 
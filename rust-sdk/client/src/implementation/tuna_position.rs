@@ -1,5 +1,6 @@
 use crate::accounts::{Market, Vault};
 use crate::TunaError as ErrorCode;
+use fusionamm_core::{CoreError, AMOUNT_EXCEEDS_MAX_U64, ARITHMETIC_OVERFLOW};
 use solana_pubkey::Pubkey;
 use std::any::Any;
 
@@ -33,6 +34,15 @@ pub trait TunaPosition: Any {
     fn is_healthy(&self, sqrt_price: u128, market: &Market, vault_a: &Vault, vault_b: &Vault) -> Result<(bool, u32), ErrorCode>;
 }
 
+/// Converts an error of a core quote to the program error. Only arithmetic errors are reported as a math overflow,
+/// the argument and validation errors, e.g. conflicting position flags or invalid tick indexes, are reported as invalid arguments.
+pub(crate) fn core_error_to_tuna_error(error: CoreError) -> ErrorCode {
+    match error {
+        ARITHMETIC_OVERFLOW | AMOUNT_EXCEEDS_MAX_U64 => ErrorCode::MathOverflow,
+        _ => ErrorCode::InvalidInstructionArguments,
+    }
+}
+
 #[macro_export]
 macro_rules! impl_tuna_position {
     ($t:ty) => {
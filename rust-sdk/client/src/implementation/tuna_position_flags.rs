@@ -0,0 +1,157 @@
+use crate::consts::{
+    TUNA_POSITION_FLAGS_ALLOW_REBALANCING, TUNA_POSITION_FLAGS_AUTO_COMPOUND_YIELD, TUNA_POSITION_FLAGS_AUTO_COMPOUND_YIELD_WITH_LEVERAGE,
+    TUNA_POSITION_FLAGS_LOCKED, TUNA_POSITION_FLAGS_LOWER_LIMIT_ORDER_SWAP_TO_TOKEN_A, TUNA_POSITION_FLAGS_LOWER_LIMIT_ORDER_SWAP_TO_TOKEN_B,
+    TUNA_POSITION_FLAGS_MASK, TUNA_POSITION_FLAGS_UPPER_LIMIT_ORDER_SWAP_TO_TOKEN_A, TUNA_POSITION_FLAGS_UPPER_LIMIT_ORDER_SWAP_TO_TOKEN_B,
+};
+use crate::TunaError as ErrorCode;
+use defituna_core::{TOKEN_A, TOKEN_B};
+use std::fmt;
+
+/// Tuna position flags. Wraps the raw `u32` value stored in the position account and passed to the program instructions.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "u32", into = "u32"))]
+pub struct TunaPositionFlags(u32);
+
+impl TunaPositionFlags {
+    /// Creates flags from the raw value. Fails if unknown bits are set.
+    pub fn from_bits(bits: u32) -> Result<Self, ErrorCode> {
+        if bits & !TUNA_POSITION_FLAGS_MASK != 0 {
+            return Err(ErrorCode::InvalidInstructionArguments);
+        }
+        Ok(Self(bits))
+    }
+
+    /// Creates flags from the raw value stored in a position account. Bits unknown to this version of the client,
+    /// e.g. flags added to the program later, are kept as is.
+    pub fn from_bits_retain(bits: u32) -> Self {
+        Self(bits)
+    }
+
+    pub fn bits(&self) -> u32 {
+        self.0
+    }
+
+    fn contains(&self, flag: u32) -> bool {
+        self.0 & flag != 0
+    }
+
+    fn with(self, flag: u32, enabled: bool) -> Self {
+        if enabled {
+            Self(self.0 | flag)
+        } else {
+            Self(self.0 & !flag)
+        }
+    }
+
+    fn swap_to_token(&self, swap_to_token_a: u32, swap_to_token_b: u32) -> Option<u8> {
+        if self.contains(swap_to_token_a) {
+            Some(TOKEN_A)
+        } else if self.contains(swap_to_token_b) {
+            Some(TOKEN_B)
+        } else {
+            None
+        }
+    }
+
+    fn with_swap_to_token(self, swap_to_token_a: u32, swap_to_token_b: u32, swap_to_token: Option<u8>) -> Self {
+        self.with(swap_to_token_a, swap_to_token == Some(TOKEN_A))
+            .with(swap_to_token_b, swap_to_token == Some(TOKEN_B))
+    }
+
+    /// Returns the token the position is swapped to when the lower limit order (stop loss) is executed.
+    pub fn lower_limit_order_swap_to_token(&self) -> Option<u8> {
+        self.swap_to_token(TUNA_POSITION_FLAGS_LOWER_LIMIT_ORDER_SWAP_TO_TOKEN_A, TUNA_POSITION_FLAGS_LOWER_LIMIT_ORDER_SWAP_TO_TOKEN_B)
+    }
+
+    /// Returns the token the position is swapped to when the upper limit order (take profit) is executed.
+    pub fn upper_limit_order_swap_to_token(&self) -> Option<u8> {
+        self.swap_to_token(TUNA_POSITION_FLAGS_UPPER_LIMIT_ORDER_SWAP_TO_TOKEN_A, TUNA_POSITION_FLAGS_UPPER_LIMIT_ORDER_SWAP_TO_TOKEN_B)
+    }
+
+    pub fn auto_compound_yield(&self) -> bool {
+        self.contains(TUNA_POSITION_FLAGS_AUTO_COMPOUND_YIELD)
+    }
+
+    pub fn auto_compound_yield_with_leverage(&self) -> bool {
+        self.contains(TUNA_POSITION_FLAGS_AUTO_COMPOUND_YIELD_WITH_LEVERAGE)
+    }
+
+    pub fn allow_rebalancing(&self) -> bool {
+        self.contains(TUNA_POSITION_FLAGS_ALLOW_REBALANCING)
+    }
+
+    pub fn locked(&self) -> bool {
+        self.contains(TUNA_POSITION_FLAGS_LOCKED)
+    }
+
+    pub fn with_lower_limit_order_swap_to_token(self, swap_to_token: Option<u8>) -> Self {
+        self.with_swap_to_token(
+            TUNA_POSITION_FLAGS_LOWER_LIMIT_ORDER_SWAP_TO_TOKEN_A,
+            TUNA_POSITION_FLAGS_LOWER_LIMIT_ORDER_SWAP_TO_TOKEN_B,
+            swap_to_token,
+        )
+    }
+
+    pub fn with_upper_limit_order_swap_to_token(self, swap_to_token: Option<u8>) -> Self {
+        self.with_swap_to_token(
+            TUNA_POSITION_FLAGS_UPPER_LIMIT_ORDER_SWAP_TO_TOKEN_A,
+            TUNA_POSITION_FLAGS_UPPER_LIMIT_ORDER_SWAP_TO_TOKEN_B,
+            swap_to_token,
+        )
+    }
+
+    pub fn with_auto_compound_yield(self, enabled: bool) -> Self {
+        self.with(TUNA_POSITION_FLAGS_AUTO_COMPOUND_YIELD, enabled)
+    }
+
+    pub fn with_auto_compound_yield_with_leverage(self, enabled: bool) -> Self {
+        self.with(TUNA_POSITION_FLAGS_AUTO_COMPOUND_YIELD_WITH_LEVERAGE, enabled)
+    }
+
+    pub fn with_allow_rebalancing(self, enabled: bool) -> Self {
+        self.with(TUNA_POSITION_FLAGS_ALLOW_REBALANCING, enabled)
+    }
+
+    pub fn with_locked(self, enabled: bool) -> Self {
+        self.with(TUNA_POSITION_FLAGS_LOCKED, enabled)
+    }
+
+    /// Checks the flags for combinations rejected by the program.
+    /// `leveraged` must be true if the position has or is going to have debt.
+    pub fn validate(&self, leveraged: bool) -> Result<(), ErrorCode> {
+        let lower_limit_order_swap = TUNA_POSITION_FLAGS_LOWER_LIMIT_ORDER_SWAP_TO_TOKEN_A | TUNA_POSITION_FLAGS_LOWER_LIMIT_ORDER_SWAP_TO_TOKEN_B;
+        let upper_limit_order_swap = TUNA_POSITION_FLAGS_UPPER_LIMIT_ORDER_SWAP_TO_TOKEN_A | TUNA_POSITION_FLAGS_UPPER_LIMIT_ORDER_SWAP_TO_TOKEN_B;
+
+        // A limit order can't swap to both tokens.
+        if self.0 & lower_limit_order_swap == lower_limit_order_swap || self.0 & upper_limit_order_swap == upper_limit_order_swap {
+            return Err(ErrorCode::InvalidInstructionArguments);
+        }
+
+        if self.locked() && leveraged {
+            return Err(ErrorCode::LeveragedLockedPositionNotAllowed);
+        }
+
+        Ok(())
+    }
+}
+
+impl TryFrom<u32> for TunaPositionFlags {
+    type Error = ErrorCode;
+
+    fn try_from(bits: u32) -> Result<Self, Self::Error> {
+        Self::from_bits(bits)
+    }
+}
+
+impl From<TunaPositionFlags> for u32 {
+    fn from(flags: TunaPositionFlags) -> Self {
+        flags.0
+    }
+}
+
+impl fmt::Display for TunaPositionFlags {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:#010b}", self.0)
+    }
+}
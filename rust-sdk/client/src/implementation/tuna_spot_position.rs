@@ -1,9 +1,13 @@
 use crate::accounts::*;
 use crate::consts::HUNDRED_PERCENT;
+use crate::implementation::tuna_position::core_error_to_tuna_error;
 use crate::types::*;
 use crate::{impl_tuna_position, TunaError as ErrorCode, TunaLimitOrderType, TunaPosition, TunaPositionKind};
 use defituna_core::fixed::Rounding;
-use defituna_core::sqrt_price_x64_to_price_x64;
+use defituna_core::{
+    get_liquidate_spot_position_quote, get_spot_position_future_liquidation_price, get_spot_position_pnl, get_spot_position_time_to_liquidation,
+    sqrt_price_x64_to_price_x64, LiquidateSpotPositionQuoteResult, SpotPositionPnl,
+};
 use fixed::types::U64F64;
 use fusionamm_core::{sqrt_price_to_tick_index, MAX_SQRT_PRICE, MIN_SQRT_PRICE};
 use solana_pubkey::Pubkey;
@@ -11,6 +15,90 @@ use std::fmt;
 
 impl_tuna_position!(TunaSpotPosition);
 
+impl TunaSpotPosition {
+    /// Returns the unrealized PnL of the position. Vaults must be passed with accrued interest.
+    pub fn compute_pnl(&self, sqrt_price: u128, vault_a: &Vault, vault_b: &Vault) -> Result<SpotPositionPnl, ErrorCode> {
+        let debt = self
+            .get_borrowed_vault(vault_a, vault_b)
+            .calculate_borrowed_funds(self.loan_shares, Rounding::Up)?;
+
+        get_spot_position_pnl(
+            self.position_token as u8,
+            self.collateral_token as u8,
+            self.amount,
+            self.loan_funds,
+            debt,
+            self.entry_sqrt_price,
+            sqrt_price,
+        )
+        .map_err(core_error_to_tuna_error)
+    }
+
+    /// Returns the number of seconds until the position becomes unhealthy at the provided price due to accrued interest.
+    /// Vaults must be passed with accrued interest.
+    pub fn compute_time_to_liquidation(&self, sqrt_price: u128, market: &Market, vault_a: &Vault, vault_b: &Vault) -> Result<u64, ErrorCode> {
+        let borrowed_vault = self.get_borrowed_vault(vault_a, vault_b);
+        get_spot_position_time_to_liquidation(
+            self.position_token as u8,
+            self.amount,
+            borrowed_vault.calculate_borrowed_funds(self.loan_shares, Rounding::Up)?,
+            market.liquidation_threshold,
+            borrowed_vault.interest_rate,
+            borrowed_vault.get_utilization(),
+            sqrt_price,
+        )
+        .map_err(core_error_to_tuna_error)
+    }
+
+    /// Returns the liquidation price after `duration` seconds, taking into account the interest accrued on the debt.
+    /// Vaults must be passed with accrued interest.
+    pub fn compute_liquidation_price_at(&self, duration: u64, market: &Market, vault_a: &Vault, vault_b: &Vault) -> Result<f64, ErrorCode> {
+        let borrowed_vault = self.get_borrowed_vault(vault_a, vault_b);
+        get_spot_position_future_liquidation_price(
+            self.position_token as u8,
+            self.amount,
+            borrowed_vault.calculate_borrowed_funds(self.loan_shares, Rounding::Up)?,
+            market.liquidation_threshold,
+            borrowed_vault.interest_rate,
+            borrowed_vault.get_utilization(),
+            duration,
+        )
+        .map_err(core_error_to_tuna_error)
+    }
+
+    /// Returns the outcome of the position liquidation at the provided pool price and swap fee rate.
+    /// Vaults must be passed with accrued interest.
+    pub fn compute_liquidation_outcome(
+        &self,
+        sqrt_price: u128,
+        swap_fee_rate: u16,
+        decrease_percent: u32,
+        market: &Market,
+        vault_a: &Vault,
+        vault_b: &Vault,
+    ) -> Result<LiquidateSpotPositionQuoteResult, ErrorCode> {
+        get_liquidate_spot_position_quote(
+            self.position_token as u8,
+            self.collateral_token as u8,
+            self.amount,
+            self.get_borrowed_vault(vault_a, vault_b)
+                .calculate_borrowed_funds(self.loan_shares, Rounding::Up)?,
+            decrease_percent,
+            market.liquidation_fee,
+            swap_fee_rate,
+            sqrt_price,
+        )
+        .map_err(core_error_to_tuna_error)
+    }
+
+    fn get_borrowed_vault<'a>(&self, vault_a: &'a Vault, vault_b: &'a Vault) -> &'a Vault {
+        match self.position_token {
+            PoolToken::A => vault_b,
+            PoolToken::B => vault_a,
+        }
+    }
+}
+
 impl TunaPosition for TunaSpotPosition {
     fn kind(&self) -> TunaPositionKind {
         TunaPositionKind::Spot
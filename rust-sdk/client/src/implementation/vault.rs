@@ -1,8 +1,11 @@
 use crate::accounts::Vault;
 use crate::TunaError as ErrorCode;
-use defituna_core::borrow_curve::sample;
+use defituna_core::borrow_curve::{BorrowCurve, KinkedBorrowCurve};
 use defituna_core::fixed::{mul_div_64, Rounding};
-use defituna_core::Fixed128;
+use defituna_core::{
+    get_vault_borrow_rate_curve_with_curve, get_vault_borrow_rate_with_curve, get_vault_projected_debt_with_curve, get_vault_supply_rate_with_curve,
+    rate_to_apy, Fixed128,
+};
 use std::fmt;
 
 pub const INTEREST_ACCRUE_MIN_INTERVAL: u64 = 60;
@@ -16,6 +19,69 @@ impl Vault {
         }
     }
 
+    /// Returns the amount of funds that can still be borrowed from the vault.
+    pub fn get_available_liquidity(&self) -> u64 {
+        self.deposited_funds.saturating_sub(self.borrowed_funds)
+    }
+
+    /// Returns the annual borrow rate at the current utilization.
+    pub fn get_borrow_rate(&self) -> f64 {
+        self.get_borrow_rate_with_curve(&KinkedBorrowCurve::default())
+    }
+
+    /// Returns the annual borrow rate at the current utilization using the provided borrow curve.
+    pub fn get_borrow_rate_with_curve(&self, curve: &dyn BorrowCurve) -> f64 {
+        get_vault_borrow_rate_with_curve(curve, self.interest_rate, self.get_utilization())
+    }
+
+    /// Returns the annual borrow rate at a hypothetical utilization (1.0 is equal to 100%).
+    pub fn get_borrow_rate_at_utilization(&self, utilization: f64) -> f64 {
+        get_vault_borrow_rate_with_curve(&KinkedBorrowCurve::default(), self.interest_rate, utilization)
+    }
+
+    /// Returns the annual borrow yield at the current utilization, taking into account interest compounding.
+    pub fn get_borrow_apy(&self) -> f64 {
+        rate_to_apy(self.get_borrow_rate())
+    }
+
+    /// Returns the annual supply rate at the current utilization.
+    pub fn get_supply_rate(&self) -> f64 {
+        self.get_supply_rate_with_curve(&KinkedBorrowCurve::default())
+    }
+
+    /// Returns the annual supply rate at the current utilization using the provided borrow curve.
+    pub fn get_supply_rate_with_curve(&self, curve: &dyn BorrowCurve) -> f64 {
+        get_vault_supply_rate_with_curve(curve, self.interest_rate, self.get_utilization())
+    }
+
+    /// Returns the annual supply yield at the current utilization, taking into account interest compounding.
+    pub fn get_supply_apy(&self) -> f64 {
+        rate_to_apy(self.get_supply_rate())
+    }
+
+    /// Returns the projected debt after `duration` seconds, assuming the utilization doesn't change.
+    /// The vault must be passed with accrued interest.
+    pub fn get_projected_debt(&self, debt: u64, duration: u64) -> Result<u64, ErrorCode> {
+        self.get_projected_debt_with_curve(&KinkedBorrowCurve::default(), debt, duration)
+    }
+
+    /// Returns the projected debt after `duration` seconds using the provided borrow curve.
+    pub fn get_projected_debt_with_curve(&self, curve: &dyn BorrowCurve, debt: u64, duration: u64) -> Result<u64, ErrorCode> {
+        get_vault_projected_debt_with_curve(curve, debt, self.interest_rate, self.get_utilization(), duration).map_err(|_| ErrorCode::MathOverflow)
+    }
+
+    /// Returns the borrow rate curve as (utilization, annual borrow rate) pairs evenly spaced from 0% to 100%.
+    pub fn get_borrow_rate_curve(&self, points: u32) -> Vec<(f64, f64)> {
+        self.get_borrow_rate_curve_with_curve(&KinkedBorrowCurve::default(), points)
+    }
+
+    /// Returns the borrow rate curve for the provided borrow curve.
+    pub fn get_borrow_rate_curve_with_curve(&self, curve: &dyn BorrowCurve, points: u32) -> Vec<(f64, f64)> {
+        let rates = get_vault_borrow_rate_curve_with_curve(curve, self.interest_rate, points);
+        let last = (rates.len() - 1) as f64;
+        rates.into_iter().enumerate().map(|(i, rate)| (i as f64 / last, rate)).collect()
+    }
+
     /// Returns the sum of the first three terms of a Taylor expansion of e^r - 1, to approximate a
     /// continuous compound interest rate.
     pub fn compounded_interest_rate(r: f64) -> f64 {
@@ -26,6 +92,11 @@ impl Vault {
     }
 
     pub fn accrue_interest(&mut self, timestamp: u64) -> Result<(), ErrorCode> {
+        self.accrue_interest_with_curve(timestamp, &KinkedBorrowCurve::default())
+    }
+
+    /// Accrues interest using the provided borrow curve. Can be used to simulate alternative rate models.
+    pub fn accrue_interest_with_curve(&mut self, timestamp: u64, curve: &dyn BorrowCurve) -> Result<(), ErrorCode> {
         let elapsed_time_seconds = timestamp.checked_sub(self.last_update_timestamp).ok_or(ErrorCode::MathUnderflow)?;
 
         // Nothing to accrue.
@@ -40,7 +111,7 @@ impl Vault {
         }
         // Compute interest based on utilization.
         let utilization = self.get_utilization();
-        let interest_rate_multiplier = sample(utilization);
+        let interest_rate_multiplier = curve.sample(utilization);
         let interest_rate = Fixed128::from_bits(self.interest_rate as u128).to_num::<f64>() * interest_rate_multiplier;
 
         let interest = Self::compounded_interest_rate(interest_rate * elapsed_time_seconds as f64);
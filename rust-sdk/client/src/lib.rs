@@ -1,6 +1,7 @@
 #![allow(dead_code)]
 
 mod core_types;
+mod fetcher;
 mod pda;
 
 #[rustfmt::skip]
@@ -33,6 +34,7 @@ pub(crate) use generated::*;
 pub use gpa::*;
 
 pub use consts::*;
+pub use fetcher::{AccountFetcher, AccountSnapshot, SyncAccountFetcher};
 pub use implementation::*;
 pub use pda::*;
 pub use txbuilder::*;
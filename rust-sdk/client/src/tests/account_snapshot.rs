@@ -0,0 +1,214 @@
+#[cfg(test)]
+mod tests {
+    use crate::accounts::LendingPosition;
+    use crate::instructions::SetTunaSpotPositionLimitOrdersInstructionArgs;
+    use crate::open_lending_position_v2::open_lending_position_v2_instruction;
+    use crate::tests::*;
+    use crate::types::PoolToken;
+    use crate::{
+        close_tuna_spot_position_instruction, close_tuna_spot_position_instructions, collect_fees_orca_instruction, collect_fees_orca_instructions,
+        deposit_instruction, deposit_instructions, get_tuna_liquidity_position_address, get_tuna_spot_position_address,
+        open_lending_position_and_deposit_instructions, open_lending_position_instruction, set_tuna_spot_position_limit_orders_instruction,
+        set_tuna_spot_position_limit_orders_instructions, withdraw_instruction, withdraw_instructions, AccountFetcher, AccountSnapshot,
+        SetTunaSpotPositionLimitOrdersArgs, TUNA_ID,
+    };
+    use fusionamm_core::price_to_sqrt_price;
+    use solana_pubkey::Pubkey;
+    use solana_sysvar::rent::Rent;
+    use spl_associated_token_account::instruction::create_associated_token_account_idempotent;
+
+    const DECIMALS_A: u8 = 9;
+    const DECIMALS_B: u8 = 6;
+
+    fn lending_snapshot(mint: &Pubkey) -> AccountSnapshot {
+        let mut snapshot = AccountSnapshot::from_iter([(*mint, mint_account(DECIMALS_A))]);
+        snapshot.insert_sysvar(&Rent::default()).unwrap();
+        snapshot
+    }
+
+    #[test]
+    fn test_snapshot_fetch() {
+        let mint = Pubkey::new_unique();
+        let missing = Pubkey::new_unique();
+        let snapshot = lending_snapshot(&mint);
+
+        let copy = crate::fetcher::block_on(AccountSnapshot::fetch(&snapshot, &[mint, missing])).unwrap();
+        assert_eq!(copy.get(&mint), snapshot.get(&mint));
+        assert!(copy.get(&missing).is_none());
+
+        let accounts = crate::fetcher::block_on(copy.get_multiple_accounts(&[missing, mint])).unwrap();
+        assert!(accounts[0].is_none());
+        assert_eq!(accounts[1].as_ref(), snapshot.get(&mint));
+    }
+
+    #[test]
+    fn test_deposit_instructions() {
+        let authority = Pubkey::new_unique();
+        let mint = Pubkey::new_unique();
+        let snapshot = lending_snapshot(&mint);
+
+        let instructions = deposit_instructions(&snapshot, &authority, &mint, None, 1_000_000).unwrap().instructions;
+        assert_eq!(
+            instructions,
+            vec![
+                create_associated_token_account_idempotent(&authority, &authority, &mint, &spl_token::ID),
+                deposit_instruction(&authority, &mint, &spl_token::ID, None, 1_000_000),
+            ]
+        );
+
+        // The mint must exist.
+        assert!(deposit_instructions(&snapshot, &authority, &Pubkey::new_unique(), None, 1_000_000).is_err());
+    }
+
+    #[test]
+    fn test_deposit_native_mint_instructions() {
+        let authority = Pubkey::new_unique();
+        let mint = spl_token::native_mint::ID;
+        let snapshot = lending_snapshot(&mint);
+
+        // SOL is wrapped before the deposit and the WSOL account is closed afterward.
+        let instructions = deposit_instructions(&snapshot, &authority, &mint, None, 1_000_000).unwrap().instructions;
+        assert_eq!(instructions.len(), 5);
+        assert_eq!(instructions[0], create_associated_token_account_idempotent(&authority, &authority, &mint, &spl_token::ID));
+        assert_eq!(instructions[3], deposit_instruction(&authority, &mint, &spl_token::ID, None, 1_000_000));
+        assert_eq!(instructions[4].program_id, spl_token::ID);
+    }
+
+    #[test]
+    fn test_withdraw_instructions() {
+        let authority = Pubkey::new_unique();
+        let mint = Pubkey::new_unique();
+        let mut snapshot = lending_snapshot(&mint);
+        let mut mint_2022 = mint_account(DECIMALS_A);
+        mint_2022.owner = spl_token_2022::ID;
+        snapshot.insert(mint, mint_2022);
+
+        let instructions = withdraw_instructions(&snapshot, &authority, &mint, None, 0, 1_000).unwrap().instructions;
+        assert_eq!(
+            instructions,
+            vec![
+                create_associated_token_account_idempotent(&authority, &authority, &mint, &spl_token_2022::ID),
+                withdraw_instruction(&authority, &mint, &spl_token_2022::ID, None, 0, 1_000),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_open_lending_position_and_deposit_instructions() {
+        let authority = Pubkey::new_unique();
+        let mint = Pubkey::new_unique();
+        let vault = Pubkey::new_unique();
+        let mut snapshot = lending_snapshot(&mint);
+
+        let deposit = deposit_instructions(&snapshot, &authority, &mint, None, 1_000).unwrap().instructions;
+
+        let tx_plan = open_lending_position_and_deposit_instructions(&snapshot, &authority, &mint, None, 1_000).unwrap();
+        assert_eq!(tx_plan.instructions[0], open_lending_position_instruction(&authority, &mint));
+        assert_eq!(tx_plan.instructions[1..], deposit);
+        assert_eq!(tx_plan.refundable_rent, Rent::default().minimum_balance(LendingPosition::LEN));
+
+        let tx_plan = open_lending_position_and_deposit_instructions(&snapshot, &authority, &mint, Some(&vault), 1_000).unwrap();
+        assert_eq!(tx_plan.instructions[0], open_lending_position_v2_instruction(&authority, &mint, &vault));
+
+        // The position already exists.
+        snapshot.extend([lending_position_account(&test_lending_position(&authority, &mint, 1_000))]);
+        let tx_plan = open_lending_position_and_deposit_instructions(&snapshot, &authority, &mint, None, 1_000).unwrap();
+        assert_eq!(tx_plan.instructions, deposit);
+        assert_eq!(tx_plan.refundable_rent, 0);
+    }
+
+    #[test]
+    fn test_spot_position_instructions() {
+        let authority = Pubkey::new_unique();
+        let mint_a = Pubkey::new_unique();
+        let mint_b = Pubkey::new_unique();
+        let pool = Pubkey::new_unique();
+
+        let whirlpool = test_whirlpool(&mint_a, &mint_b, 64, price_to_sqrt_price(200.0, DECIMALS_A, DECIMALS_B));
+        let tuna_position = test_tuna_spot_position(&authority, &pool, &mint_a, &mint_b, PoolToken::A, PoolToken::B);
+        let snapshot = AccountSnapshot::from_iter([
+            tuna_spot_position_account(&tuna_position),
+            (pool, whirlpool_account(&whirlpool)),
+            (mint_a, mint_account(DECIMALS_A)),
+            (mint_b, mint_account(DECIMALS_B)),
+        ]);
+
+        let instructions = set_tuna_spot_position_limit_orders_instructions(
+            &snapshot,
+            &authority,
+            &pool,
+            SetTunaSpotPositionLimitOrdersArgs {
+                lower_limit_order_price: Some(150.0),
+                upper_limit_order_price: None,
+            },
+        )
+        .unwrap()
+        .instructions;
+        assert_eq!(
+            instructions,
+            vec![set_tuna_spot_position_limit_orders_instruction(
+                &authority,
+                &pool,
+                SetTunaSpotPositionLimitOrdersInstructionArgs {
+                    lower_limit_order_sqrt_price: price_to_sqrt_price(150.0, DECIMALS_A, DECIMALS_B),
+                    upper_limit_order_sqrt_price: fusionamm_core::MAX_SQRT_PRICE,
+                },
+            )]
+        );
+
+        let tuna_position_address = get_tuna_spot_position_address(&authority, &pool).0;
+        let instructions = close_tuna_spot_position_instructions(&snapshot, &authority, &pool).unwrap().instructions;
+        assert_eq!(
+            instructions,
+            vec![close_tuna_spot_position_instruction(
+                &authority,
+                &tuna_position_address,
+                &mint_a,
+                &mint_b,
+                &spl_token::ID,
+                &spl_token::ID
+            )]
+        );
+
+        // Another authority has no position in the pool.
+        assert!(close_tuna_spot_position_instructions(&snapshot, &Pubkey::new_unique(), &pool).is_err());
+    }
+
+    #[test]
+    fn test_collect_fees_orca_instructions() {
+        let authority = Pubkey::new_unique();
+        let position_mint = Pubkey::new_unique();
+        let mint_a = Pubkey::new_unique();
+        let mint_b = Pubkey::new_unique();
+        let pool = Pubkey::new_unique();
+
+        let whirlpool = test_whirlpool(&mint_a, &mint_b, 64, price_to_sqrt_price(200.0, DECIMALS_A, DECIMALS_B));
+        let tuna_position = test_tuna_lp_position(&authority, &pool, &mint_a, &mint_b, &position_mint);
+        let mut mint_b_account = mint_account(DECIMALS_B);
+        mint_b_account.owner = spl_token_2022::ID;
+        let snapshot = AccountSnapshot::from_iter([
+            tuna_lp_position_account(&tuna_position),
+            (pool, whirlpool_account(&whirlpool)),
+            (mint_a, mint_account(DECIMALS_A)),
+            (mint_b, mint_b_account),
+        ]);
+
+        let instructions = collect_fees_orca_instructions(&snapshot, &authority, &position_mint)
+            .unwrap()
+            .instructions;
+        assert_eq!(
+            instructions,
+            vec![
+                create_associated_token_account_idempotent(&authority, &authority, &mint_a, &spl_token::ID),
+                create_associated_token_account_idempotent(&authority, &authority, &mint_b, &spl_token_2022::ID),
+                collect_fees_orca_instruction(&authority, &tuna_position, &whirlpool, &spl_token::ID, &spl_token_2022::ID),
+            ]
+        );
+        let collect_fees_instruction = &instructions[2];
+        assert_eq!(collect_fees_instruction.program_id, TUNA_ID);
+        assert!(collect_fees_instruction
+            .accounts
+            .iter()
+            .any(|account| account.pubkey == get_tuna_liquidity_position_address(&position_mint).0));
+    }
+}
@@ -0,0 +1,169 @@
+#[cfg(test)]
+mod tests {
+    use crate::instructions::OpenTunaSpotPositionInstructionArgs;
+    use crate::tests::*;
+    use crate::types::{MarketMaker, PoolToken};
+    use crate::{
+        get_vault_address, open_tuna_spot_position_instructions, open_tuna_spot_position_instructions_async,
+        repay_tuna_lp_position_debt_instructions, repay_tuna_lp_position_debt_instructions_async, set_tuna_lp_position_limit_orders_instructions,
+        set_tuna_lp_position_limit_orders_instructions_async, AccountFetcher, AccountSnapshot, RepayTunaLpPositionDebtArgs,
+        SetTunaLpPositionLimitOrdersArgs, TxPlan,
+    };
+    use anyhow::Result;
+    use fusionamm_core::price_to_sqrt_price;
+    use solana_account::Account;
+    use solana_pubkey::Pubkey;
+    use solana_sysvar::clock::Clock;
+    use solana_sysvar::rent::Rent;
+    use std::sync::Arc;
+
+    const DECIMALS_A: u8 = 9;
+    const DECIMALS_B: u8 = 6;
+
+    /// An asynchronous fetcher: every request yields to the runtime before it's served from the snapshot.
+    struct YieldingFetcher(AccountSnapshot);
+
+    impl AccountFetcher for YieldingFetcher {
+        async fn get_multiple_accounts(&self, addresses: &[Pubkey]) -> Result<Vec<Option<Account>>> {
+            tokio::task::yield_now().await;
+            self.0.get_multiple_accounts(addresses).await
+        }
+    }
+
+    struct TestPosition {
+        authority: Pubkey,
+        position_mint: Pubkey,
+        pool: Pubkey,
+        snapshot: AccountSnapshot,
+    }
+
+    fn setup() -> TestPosition {
+        let authority = Pubkey::new_unique();
+        let position_mint = Pubkey::new_unique();
+        let mint_a = Pubkey::new_unique();
+        let mint_b = Pubkey::new_unique();
+        let pool = Pubkey::new_unique();
+
+        let whirlpool = test_whirlpool(&mint_a, &mint_b, 64, price_to_sqrt_price(200.0, DECIMALS_A, DECIMALS_B));
+        let market = test_market(&pool, MarketMaker::Orca, &get_vault_address(&mint_a, None).0, &get_vault_address(&mint_b, None).0);
+        let mut tuna_position = test_tuna_lp_position(&authority, &pool, &mint_a, &mint_b, &position_mint);
+        tuna_position.loan_shares_a = 1_000_000;
+
+        let mut snapshot = AccountSnapshot::from_iter([
+            tuna_lp_position_account(&tuna_position),
+            (pool, whirlpool_account(&whirlpool)),
+            market_account(&market),
+            vault_account(&test_vault(&mint_a, 100_000_000, 10_000_000, 0)),
+            vault_account(&test_vault(&mint_b, 100_000_000, 10_000_000, 0)),
+            (mint_a, mint_account(DECIMALS_A)),
+            (mint_b, mint_account(DECIMALS_B)),
+        ]);
+        snapshot.insert_sysvar(&Clock::default()).unwrap();
+        snapshot.insert_sysvar(&Rent::default()).unwrap();
+
+        TestPosition {
+            authority,
+            position_mint,
+            pool,
+            snapshot,
+        }
+    }
+
+    fn assert_same_plan(actual: &TxPlan, expected: &TxPlan) {
+        assert_eq!(actual.instructions, expected.instructions);
+        assert_eq!(actual.address_lookup_table_addresses, expected.address_lookup_table_addresses);
+        assert_eq!(actual.non_refundable_rent, expected.non_refundable_rent);
+        assert_eq!(actual.refundable_rent, expected.refundable_rent);
+        assert_eq!(actual.created_accounts, expected.created_accounts);
+        assert_eq!(actual.closed_accounts, expected.closed_accounts);
+    }
+
+    fn repay_args() -> RepayTunaLpPositionDebtArgs {
+        RepayTunaLpPositionDebtArgs {
+            collateral_funds_a: 500_000,
+            collateral_funds_b: 0,
+        }
+    }
+
+    fn limit_orders_args() -> SetTunaLpPositionLimitOrdersArgs {
+        SetTunaLpPositionLimitOrdersArgs {
+            lower_limit_order_price: Some(150.0),
+            upper_limit_order_price: Some(250.0),
+            ..Default::default()
+        }
+    }
+
+    fn open_spot_args() -> OpenTunaSpotPositionInstructionArgs {
+        OpenTunaSpotPositionInstructionArgs {
+            position_token: PoolToken::B,
+            collateral_token: PoolToken::A,
+        }
+    }
+
+    #[test]
+    fn test_async_builders_match_blocking_builders() {
+        let test = setup();
+        let fetcher = YieldingFetcher(test.snapshot.clone());
+
+        let rt = tokio::runtime::Runtime::new().unwrap();
+        rt.block_on(async {
+            let plan = repay_tuna_lp_position_debt_instructions_async(&fetcher, &test.authority, &test.position_mint, repay_args())
+                .await
+                .unwrap();
+            let expected = repay_tuna_lp_position_debt_instructions(&test.snapshot, &test.authority, &test.position_mint, repay_args()).unwrap();
+            assert_same_plan(&plan, &expected);
+
+            let plan = set_tuna_lp_position_limit_orders_instructions_async(&fetcher, &test.authority, &test.position_mint, limit_orders_args())
+                .await
+                .unwrap();
+            let expected =
+                set_tuna_lp_position_limit_orders_instructions(&test.snapshot, &test.authority, &test.position_mint, limit_orders_args()).unwrap();
+            assert_same_plan(&plan, &expected);
+
+            let plan = open_tuna_spot_position_instructions_async(&fetcher, &test.authority, &test.pool, open_spot_args())
+                .await
+                .unwrap();
+            let expected = open_tuna_spot_position_instructions(&test.snapshot, &test.authority, &test.pool, open_spot_args()).unwrap();
+            assert_same_plan(&plan, &expected);
+            assert!(plan.refundable_rent > 0);
+        });
+    }
+
+    #[test]
+    fn test_async_builders_can_be_spawned() {
+        let test = setup();
+        let fetcher = Arc::new(YieldingFetcher(test.snapshot.clone()));
+
+        let rt = tokio::runtime::Runtime::new().unwrap();
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                let fetcher = fetcher.clone();
+                rt.spawn(async move {
+                    repay_tuna_lp_position_debt_instructions_async(fetcher.as_ref(), &test.authority, &test.position_mint, repay_args()).await
+                })
+            })
+            .collect();
+
+        let expected = repay_tuna_lp_position_debt_instructions(&test.snapshot, &test.authority, &test.position_mint, repay_args()).unwrap();
+        for handle in handles {
+            let plan = rt.block_on(handle).unwrap().unwrap();
+            assert_same_plan(&plan, &expected);
+        }
+    }
+
+    #[test]
+    fn test_async_builders_report_missing_accounts() {
+        let test = setup();
+        let fetcher = YieldingFetcher(AccountSnapshot::new());
+
+        let rt = tokio::runtime::Runtime::new().unwrap();
+        rt.block_on(async {
+            assert!(repay_tuna_lp_position_debt_instructions_async(&fetcher, &test.authority, &test.position_mint, repay_args())
+                .await
+                .is_err());
+            assert!(open_tuna_spot_position_instructions_async(&fetcher, &test.authority, &test.pool, open_spot_args())
+                .await
+                .is_err());
+        });
+    }
+}
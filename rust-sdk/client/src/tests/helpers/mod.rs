@@ -2,10 +2,12 @@ pub mod fusion;
 pub mod orca;
 pub mod rpc;
 pub mod setup;
+pub mod snapshot;
 pub mod token;
 pub mod token_extensions;
 
 pub use rpc::*;
 pub use setup::*;
+pub use snapshot::*;
 pub use token::*;
 pub use token_extensions::*;
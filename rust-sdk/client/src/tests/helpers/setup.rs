@@ -144,7 +144,8 @@ pub async fn setup_test_market(
                     market: market_address,
                     interest_rate: 3655890108,
                 },
-            );
+            )
+            .instructions;
             instructions.push(open_lending_position_v2_instruction(&ctx.signer.pubkey(), &mint_address, &vault_address));
             instructions.push(deposit_instruction(&ctx.signer.pubkey(), &mint_address, &token_program, Some(&vault_address), *deposit_amount));
             instructions
@@ -160,7 +161,8 @@ pub async fn setup_test_market(
                     pyth_oracle_feed_id: Default::default(),
                     allow_unsafe_token_extensions: true,
                 },
-            );
+            )
+            .instructions;
             instructions.push(open_lending_position_instruction(&ctx.signer.pubkey(), &mint_address));
             instructions.push(deposit_instruction(&ctx.signer.pubkey(), &mint_address, &token_program, None, *deposit_amount));
             instructions
@@ -0,0 +1,345 @@
+use crate::accounts::{
+    LendingPosition, Market, TunaConfig, TunaLpPosition, TunaSpotPosition, Vault, LENDING_POSITION_DISCRIMINATOR, MARKET_DISCRIMINATOR,
+    TUNA_CONFIG_DISCRIMINATOR, TUNA_LP_POSITION_DISCRIMINATOR, TUNA_SPOT_POSITION_DISCRIMINATOR, VAULT_DISCRIMINATOR,
+};
+use crate::types::{MarketMaker, PoolToken, TunaPositionState};
+use crate::{
+    get_lending_position_address, get_market_address, get_tuna_config_address, get_tuna_liquidity_position_address, get_tuna_spot_position_address,
+    get_vault_address, LEVERAGE_ONE, TUNA_ID,
+};
+use borsh::BorshSerialize;
+use orca_whirlpools_client::{
+    get_position_address, get_tick_array_address, FixedTickArray, Position, PositionRewardInfo, Tick, Whirlpool, WhirlpoolRewardInfo,
+    FIXED_TICK_ARRAY_DISCRIMINATOR, POSITION_DISCRIMINATOR, WHIRLPOOL_DISCRIMINATOR, WHIRLPOOL_ID,
+};
+use orca_whirlpools_core::{get_tick_array_start_tick_index, sqrt_price_to_tick_index};
+use solana_account::Account;
+use solana_address_lookup_table_interface::state::{AddressLookupTable, LookupTableMeta};
+use solana_pubkey::Pubkey;
+use spl_token::solana_program::program_pack::Pack;
+use spl_token::state::Mint;
+use std::borrow::Cow;
+
+/// Creates an account holding the borsh encoded data.
+pub fn borsh_account<T: BorshSerialize>(owner: &Pubkey, data: &T) -> Account {
+    Account {
+        lamports: 1_000_000_000,
+        data: data.try_to_vec().unwrap(),
+        owner: *owner,
+        executable: false,
+        rent_epoch: 0,
+    }
+}
+
+/// Creates a tuna program account holding the borsh encoded data.
+pub fn tuna_account<T: BorshSerialize>(data: &T) -> Account {
+    borsh_account(&TUNA_ID, data)
+}
+
+pub fn test_tuna_config(fee_recipient: &Pubkey) -> TunaConfig {
+    TunaConfig {
+        discriminator: TUNA_CONFIG_DISCRIMINATOR,
+        version: 1,
+        bump: get_tuna_config_address().1,
+        admin_authority: Pubkey::new_unique(),
+        fee_recipient: *fee_recipient,
+        owner_authority: Pubkey::new_unique(),
+        max_swap_slippage: 0,
+        max_percentage_of_leftovers: 0,
+        suspend_lending_deposits: false,
+        suspend_lending_withdrawals: false,
+        suspend_add_liquidity: false,
+        suspend_remove_liquidity: false,
+        liquidator_authority: Pubkey::new_unique(),
+        oracle_price_deviation_threshold: 0,
+        default_protocol_fee_rate: 0,
+        default_liquidation_fee_rate: 0,
+        default_rebalance_fee_rate: 0,
+        oracle_price_update_authority: Pubkey::new_unique(),
+        reserved: [0; 134],
+    }
+}
+
+pub fn tuna_config_account(tuna_config: &TunaConfig) -> (Pubkey, Account) {
+    (get_tuna_config_address().0, tuna_account(tuna_config))
+}
+
+/// Returns a vault with the borrowed funds equal to the borrowed shares and the interest accrued at `timestamp`.
+pub fn test_vault(mint: &Pubkey, deposited_funds: u64, borrowed_funds: u64, timestamp: u64) -> Vault {
+    Vault {
+        discriminator: VAULT_DISCRIMINATOR,
+        version: 1,
+        bump: [get_vault_address(mint, None).1],
+        mint: *mint,
+        deposited_funds,
+        deposited_shares: deposited_funds,
+        borrowed_funds,
+        borrowed_shares: borrowed_funds,
+        unpaid_debt_shares: 0,
+        interest_rate: 0,
+        last_update_timestamp: timestamp,
+        supply_limit: u64::MAX,
+        oracle_price_update: Pubkey::new_unique(),
+        pyth_oracle_feed_id: Pubkey::new_unique(),
+        authority: Pubkey::default(),
+        market: Pubkey::default(),
+        reserved: [0; 120],
+    }
+}
+
+pub fn vault_account(vault: &Vault) -> (Pubkey, Account) {
+    (get_vault_address(&vault.mint, None).0, tuna_account(vault))
+}
+
+pub fn test_market(pool: &Pubkey, market_maker: MarketMaker, vault_a: &Pubkey, vault_b: &Pubkey) -> Market {
+    Market {
+        discriminator: MARKET_DISCRIMINATOR,
+        version: 1,
+        bump: [get_market_address(pool).1],
+        market_maker,
+        pool: *pool,
+        address_lookup_table: Pubkey::default(),
+        max_leverage: LEVERAGE_ONE * 5,
+        protocol_fee: 1000,
+        protocol_fee_on_collateral: 1000,
+        liquidation_fee: 10000,
+        liquidation_threshold: 830000,
+        unused1: 0,
+        oracle_price_deviation_threshold: 0,
+        disabled: false,
+        borrowed_shares_a: 0,
+        borrowed_shares_b: 0,
+        borrow_limit_a: 0,
+        borrow_limit_b: 0,
+        max_swap_slippage: 0,
+        rebalance_protocol_fee: 0,
+        spot_position_size_limit_a: 0,
+        spot_position_size_limit_b: 0,
+        vault_a: *vault_a,
+        vault_b: *vault_b,
+        bad_debt_a: 0,
+        bad_debt_b: 0,
+        authority: Pubkey::default(),
+        reserved: [0; 79],
+    }
+}
+
+pub fn market_account(market: &Market) -> (Pubkey, Account) {
+    (get_market_address(&market.pool).0, tuna_account(market))
+}
+
+pub fn mint_account(decimals: u8) -> Account {
+    let mut data = vec![0; Mint::LEN];
+    Mint::pack(
+        Mint {
+            mint_authority: None.into(),
+            supply: 0,
+            decimals,
+            is_initialized: true,
+            freeze_authority: None.into(),
+        },
+        &mut data,
+    )
+    .unwrap();
+
+    Account {
+        lamports: 1_000_000_000,
+        data,
+        owner: spl_token::ID,
+        executable: false,
+        rent_epoch: 0,
+    }
+}
+
+pub fn test_whirlpool(mint_a: &Pubkey, mint_b: &Pubkey, tick_spacing: u16, sqrt_price: u128) -> Whirlpool {
+    let reward_info = WhirlpoolRewardInfo {
+        mint: Pubkey::default(),
+        vault: Pubkey::default(),
+        extension: [0; 32],
+        emissions_per_second_x64: 0,
+        growth_global_x64: 0,
+    };
+
+    Whirlpool {
+        discriminator: WHIRLPOOL_DISCRIMINATOR.try_into().unwrap(),
+        whirlpools_config: Pubkey::new_unique(),
+        whirlpool_bump: [0],
+        tick_spacing,
+        fee_tier_index_seed: tick_spacing.to_le_bytes(),
+        fee_rate: 3000,
+        protocol_fee_rate: 0,
+        liquidity: 1_000_000_000_000,
+        sqrt_price,
+        tick_current_index: sqrt_price_to_tick_index(sqrt_price),
+        protocol_fee_owed_a: 0,
+        protocol_fee_owed_b: 0,
+        token_mint_a: *mint_a,
+        token_vault_a: Pubkey::new_unique(),
+        fee_growth_global_a: 0,
+        token_mint_b: *mint_b,
+        token_vault_b: Pubkey::new_unique(),
+        fee_growth_global_b: 0,
+        reward_last_updated_timestamp: 0,
+        reward_infos: [reward_info.clone(), reward_info.clone(), reward_info],
+    }
+}
+
+pub fn whirlpool_account(whirlpool: &Whirlpool) -> Account {
+    borsh_account(&WHIRLPOOL_ID, whirlpool)
+}
+
+pub fn test_orca_position(whirlpool: &Pubkey, position_mint: &Pubkey, tick_lower_index: i32, tick_upper_index: i32) -> Position {
+    let reward_info = PositionRewardInfo {
+        growth_inside_checkpoint: 0,
+        amount_owed: 0,
+    };
+
+    Position {
+        discriminator: POSITION_DISCRIMINATOR.try_into().unwrap(),
+        whirlpool: *whirlpool,
+        position_mint: *position_mint,
+        liquidity: 0,
+        tick_lower_index,
+        tick_upper_index,
+        fee_growth_checkpoint_a: 0,
+        fee_owed_a: 0,
+        fee_growth_checkpoint_b: 0,
+        fee_owed_b: 0,
+        reward_infos: [reward_info.clone(), reward_info.clone(), reward_info],
+    }
+}
+
+pub fn orca_position_account(position: &Position) -> (Pubkey, Account) {
+    (get_position_address(&position.position_mint).unwrap().0, borsh_account(&WHIRLPOOL_ID, position))
+}
+
+/// Returns an empty fixed tick array containing the tick index.
+pub fn orca_tick_array_account(whirlpool: &Pubkey, tick_index: i32, tick_spacing: u16) -> (Pubkey, Account) {
+    let start_tick_index = get_tick_array_start_tick_index(tick_index, tick_spacing);
+    let tick = Tick {
+        initialized: false,
+        liquidity_net: 0,
+        liquidity_gross: 0,
+        fee_growth_outside_a: 0,
+        fee_growth_outside_b: 0,
+        reward_growths_outside: [0; 3],
+    };
+    let tick_array = FixedTickArray {
+        discriminator: FIXED_TICK_ARRAY_DISCRIMINATOR.try_into().unwrap(),
+        start_tick_index,
+        ticks: std::array::from_fn(|_| tick.clone()),
+        whirlpool: *whirlpool,
+    };
+
+    (get_tick_array_address(whirlpool, start_tick_index).unwrap().0, borsh_account(&WHIRLPOOL_ID, &tick_array))
+}
+
+pub fn test_tuna_lp_position(authority: &Pubkey, pool: &Pubkey, mint_a: &Pubkey, mint_b: &Pubkey, position_mint: &Pubkey) -> TunaLpPosition {
+    TunaLpPosition {
+        discriminator: TUNA_LP_POSITION_DISCRIMINATOR,
+        version: 1,
+        bump: [get_tuna_liquidity_position_address(position_mint).1],
+        authority: *authority,
+        pool: *pool,
+        mint_a: *mint_a,
+        mint_b: *mint_b,
+        position_mint: *position_mint,
+        liquidity: 0,
+        tick_lower_index: -128,
+        tick_upper_index: 128,
+        loan_shares_a: 0,
+        loan_shares_b: 0,
+        loan_funds_a: 0,
+        loan_funds_b: 0,
+        leftovers_a: 0,
+        leftovers_b: 0,
+        tick_entry_index: 0,
+        tick_stop_loss_index: 0,
+        tick_take_profit_index: 0,
+        state: TunaPositionState::Normal,
+        unused1: 0,
+        compounded_yield_a: 0,
+        compounded_yield_b: 0,
+        flags: 0,
+        market_maker: MarketMaker::Orca,
+        entry_sqrt_price: 0,
+        lower_limit_order_sqrt_price: 0,
+        upper_limit_order_sqrt_price: 0,
+        rebalance_threshold_ticks: 0,
+        reserved: [0; 9],
+    }
+}
+
+pub fn tuna_lp_position_account(tuna_position: &TunaLpPosition) -> (Pubkey, Account) {
+    (get_tuna_liquidity_position_address(&tuna_position.position_mint).0, tuna_account(tuna_position))
+}
+
+pub fn test_tuna_spot_position(
+    authority: &Pubkey,
+    pool: &Pubkey,
+    mint_a: &Pubkey,
+    mint_b: &Pubkey,
+    position_token: PoolToken,
+    collateral_token: PoolToken,
+) -> TunaSpotPosition {
+    TunaSpotPosition {
+        discriminator: TUNA_SPOT_POSITION_DISCRIMINATOR,
+        version: 1,
+        bump: [get_tuna_spot_position_address(authority, pool).1],
+        authority: *authority,
+        pool: *pool,
+        mint_a: *mint_a,
+        mint_b: *mint_b,
+        market_maker: MarketMaker::Orca,
+        position_token,
+        collateral_token,
+        flags: 0,
+        amount: 0,
+        loan_shares: 0,
+        loan_funds: 0,
+        entry_sqrt_price: 0,
+        lower_limit_order_sqrt_price: 0,
+        upper_limit_order_sqrt_price: 0,
+        reserved: [0; 128],
+    }
+}
+
+pub fn tuna_spot_position_account(tuna_position: &TunaSpotPosition) -> (Pubkey, Account) {
+    (get_tuna_spot_position_address(&tuna_position.authority, &tuna_position.pool).0, tuna_account(tuna_position))
+}
+
+pub fn test_lending_position(authority: &Pubkey, mint: &Pubkey, deposited_funds: u64) -> LendingPosition {
+    LendingPosition {
+        discriminator: LENDING_POSITION_DISCRIMINATOR,
+        version: 1,
+        bump: [get_lending_position_address(authority, mint).1],
+        authority: *authority,
+        mint: *mint,
+        deposited_funds,
+        deposited_shares: deposited_funds,
+        vault: Pubkey::default(),
+        reserved: [0; 32],
+    }
+}
+
+pub fn lending_position_account(lending_position: &LendingPosition) -> (Pubkey, Account) {
+    (get_lending_position_address(&lending_position.authority, &lending_position.mint).0, tuna_account(lending_position))
+}
+
+/// Creates an address lookup table account holding the addresses.
+pub fn lookup_table_account(authority: &Pubkey, addresses: &[Pubkey]) -> Account {
+    let lookup_table = AddressLookupTable {
+        meta: LookupTableMeta {
+            authority: Some(*authority),
+            ..Default::default()
+        },
+        addresses: Cow::Borrowed(addresses),
+    };
+    Account {
+        lamports: 1_000_000_000,
+        data: lookup_table.serialize_for_tests().unwrap(),
+        owner: solana_address_lookup_table_interface::program::ID,
+        executable: false,
+        rent_epoch: 0,
+    }
+}
@@ -0,0 +1,302 @@
+#[cfg(test)]
+mod tests {
+    use crate::instructions::OpenAndIncreaseTunaSpotPositionJupiterInstructionArgs;
+    use crate::modify_tuna_spot_position_jupiter::{modify_tuna_spot_position_jupiter_instructions, ModifyTunaSpotPositionJupiterArgs};
+    use crate::tests::*;
+    use crate::types::{AccountsType, MarketMaker, PoolToken};
+    use crate::utils::{get_jupiter_route_accounts, JupiterRoute};
+    use crate::{
+        get_tuna_spot_position_address, get_vault_address, open_and_increase_tuna_spot_position_jupiter_instructions, AccountSnapshot,
+        OpenAndIncreaseTunaSpotPositionJupiterArgs, HUNDRED_PERCENT, JUPITER_PROGRAM_ID, TUNA_ID,
+    };
+    use borsh::BorshDeserialize;
+    use defituna_core::{
+        JupiterRouteKind, JUPITER_EXACT_OUT_ROUTE_DISCRIMINATOR, JUPITER_EXACT_OUT_ROUTE_V2_DISCRIMINATOR, JUPITER_ROUTE_DISCRIMINATOR,
+        JUPITER_ROUTE_V2_DISCRIMINATOR, JUPITER_SHARED_ACCOUNTS_EXACT_OUT_ROUTE_DISCRIMINATOR,
+        JUPITER_SHARED_ACCOUNTS_EXACT_OUT_ROUTE_V2_DISCRIMINATOR, JUPITER_SHARED_ACCOUNTS_ROUTE_DISCRIMINATOR,
+        JUPITER_SHARED_ACCOUNTS_ROUTE_V2_DISCRIMINATOR,
+    };
+    use solana_instruction::{AccountMeta, Instruction};
+    use solana_pubkey::Pubkey;
+    use solana_sysvar::rent::Rent;
+    use spl_associated_token_account::get_associated_token_address_with_program_id;
+
+    const ROUTE_KINDS: [([u8; 8], JupiterRouteKind); 8] = [
+        (JUPITER_ROUTE_DISCRIMINATOR, JupiterRouteKind::Route),
+        (JUPITER_ROUTE_V2_DISCRIMINATOR, JupiterRouteKind::RouteV2),
+        (JUPITER_SHARED_ACCOUNTS_ROUTE_DISCRIMINATOR, JupiterRouteKind::SharedAccountsRoute),
+        (JUPITER_SHARED_ACCOUNTS_ROUTE_V2_DISCRIMINATOR, JupiterRouteKind::SharedAccountsRouteV2),
+        (JUPITER_EXACT_OUT_ROUTE_DISCRIMINATOR, JupiterRouteKind::ExactOutRoute),
+        (JUPITER_EXACT_OUT_ROUTE_V2_DISCRIMINATOR, JupiterRouteKind::ExactOutRouteV2),
+        (JUPITER_SHARED_ACCOUNTS_EXACT_OUT_ROUTE_DISCRIMINATOR, JupiterRouteKind::SharedAccountsExactOutRoute),
+        (JUPITER_SHARED_ACCOUNTS_EXACT_OUT_ROUTE_V2_DISCRIMINATOR, JupiterRouteKind::SharedAccountsExactOutRouteV2),
+    ];
+
+    /// Encodes a single step route swapping `amount` (the input amount, or the output amount of exact-out routes).
+    fn route_data(discriminator: [u8; 8], amount: u64, quoted_amount: u64) -> Vec<u8> {
+        let kind = JupiterRouteKind::from_discriminator(&discriminator).unwrap();
+        let mut data = discriminator.to_vec();
+        if kind.is_shared_accounts() {
+            data.push(0);
+        }
+        if kind.is_v2() {
+            data.extend_from_slice(&amount.to_le_bytes());
+            data.extend_from_slice(&quoted_amount.to_le_bytes());
+            data.extend_from_slice(&0u16.to_le_bytes());
+            data.extend_from_slice(&0u16.to_le_bytes());
+            data.extend_from_slice(&0u16.to_le_bytes());
+            // One RaydiumCP step, 100%.
+            data.extend_from_slice(&1u32.to_le_bytes());
+            data.push(46);
+            data.extend_from_slice(&10000u16.to_le_bytes());
+            data.extend_from_slice(&[0, 1]);
+        } else {
+            data.extend_from_slice(&1u32.to_le_bytes());
+            data.extend_from_slice(&[46, 100, 0, 1]);
+            data.extend_from_slice(&amount.to_le_bytes());
+            data.extend_from_slice(&quoted_amount.to_le_bytes());
+            data.extend_from_slice(&0u16.to_le_bytes());
+            data.push(0);
+        }
+        data
+    }
+
+    /// Returns a route instruction as built by the Jupiter API for the wallet. The user token accounts are also
+    /// referenced by the swap step accounts, and the wallet signs the instruction.
+    fn route_instruction(discriminator: [u8; 8], wallet: &Pubkey, source: &Pubkey, destination: &Pubkey, amount: u64) -> Instruction {
+        let kind = JupiterRouteKind::from_discriminator(&discriminator).unwrap();
+        let (authority_index, source_index, destination_index) = kind.user_account_indexes();
+
+        let mut accounts: Vec<AccountMeta> = (0..9).map(|_| AccountMeta::new_readonly(Pubkey::new_unique(), false)).collect();
+        accounts[authority_index] = AccountMeta::new_readonly(*wallet, true);
+        accounts[source_index] = AccountMeta::new(*source, false);
+        accounts[destination_index] = AccountMeta::new(*destination, false);
+        accounts.push(AccountMeta::new(*source, false));
+        accounts.push(AccountMeta::new(*destination, false));
+
+        Instruction {
+            program_id: JUPITER_PROGRAM_ID,
+            accounts,
+            data: route_data(discriminator, amount, amount),
+        }
+    }
+
+    #[test]
+    fn test_route_accounts_for_all_route_kinds() {
+        let wallet = Pubkey::new_unique();
+        let wallet_source = Pubkey::new_unique();
+        let wallet_destination = Pubkey::new_unique();
+        let tuna_position = Pubkey::new_unique();
+        let source_mint = Pubkey::new_unique();
+        let destination_mint = Pubkey::new_unique();
+
+        let tuna_position_source = get_associated_token_address_with_program_id(&tuna_position, &source_mint, &spl_token::ID);
+        let tuna_position_destination = get_associated_token_address_with_program_id(&tuna_position, &destination_mint, &spl_token_2022::ID);
+
+        for (discriminator, kind) in ROUTE_KINDS {
+            let ix = route_instruction(discriminator, &wallet, &wallet_source, &wallet_destination, 1000);
+            let accounts =
+                get_jupiter_route_accounts(&ix, &tuna_position, &source_mint, &spl_token::ID, &destination_mint, &spl_token_2022::ID).unwrap();
+
+            let (authority_index, source_index, destination_index) = kind.user_account_indexes();
+            assert_eq!(accounts.len(), ix.accounts.len(), "{:?}", kind);
+            assert_eq!(accounts[authority_index], AccountMeta::new_readonly(tuna_position, false), "{:?}", kind);
+            assert_eq!(accounts[source_index], AccountMeta::new(tuna_position_source, false), "{:?}", kind);
+            assert_eq!(accounts[destination_index], AccountMeta::new(tuna_position_destination, false), "{:?}", kind);
+            assert_eq!(accounts[9], AccountMeta::new(tuna_position_source, false), "{:?}", kind);
+            assert_eq!(accounts[10], AccountMeta::new(tuna_position_destination, false), "{:?}", kind);
+
+            for (i, (account, original)) in accounts.iter().zip(&ix.accounts).enumerate() {
+                assert!(!account.is_signer, "{:?}", kind);
+                if ![authority_index, source_index, destination_index, 9, 10].contains(&i) {
+                    assert_eq!(account, original, "{:?}", kind);
+                }
+            }
+
+            let route_ix = Instruction {
+                program_id: ix.program_id,
+                accounts,
+                data: ix.data.clone(),
+            };
+            assert!(defituna_core::validate_jupiter_route_instruction(
+                &route_ix,
+                &tuna_position,
+                &tuna_position_source,
+                &tuna_position_destination,
+                1000
+            )
+            .is_ok());
+        }
+    }
+
+    #[test]
+    fn test_route_accounts_rejects_unsupported_instructions() {
+        let wallet = Pubkey::new_unique();
+        let mint = Pubkey::new_unique();
+
+        let mut ix = route_instruction(JUPITER_ROUTE_V2_DISCRIMINATOR, &wallet, &Pubkey::new_unique(), &Pubkey::new_unique(), 1000);
+        ix.program_id = Pubkey::new_unique();
+        assert!(get_jupiter_route_accounts(&ix, &Pubkey::new_unique(), &mint, &spl_token::ID, &mint, &spl_token::ID).is_err());
+
+        let mut ix = route_instruction(JUPITER_ROUTE_V2_DISCRIMINATOR, &wallet, &Pubkey::new_unique(), &Pubkey::new_unique(), 1000);
+        ix.data[0] = 0;
+        assert!(get_jupiter_route_accounts(&ix, &Pubkey::new_unique(), &mint, &spl_token::ID, &mint, &spl_token::ID).is_err());
+
+        let mut ix = route_instruction(JUPITER_SHARED_ACCOUNTS_ROUTE_DISCRIMINATOR, &wallet, &Pubkey::new_unique(), &Pubkey::new_unique(), 1000);
+        ix.accounts.truncate(6);
+        assert!(get_jupiter_route_accounts(&ix, &Pubkey::new_unique(), &mint, &spl_token::ID, &mint, &spl_token::ID).is_err());
+    }
+
+    struct TestMarket {
+        authority: Pubkey,
+        pool: Pubkey,
+        mint_a: Pubkey,
+        mint_b: Pubkey,
+        snapshot: AccountSnapshot,
+    }
+
+    fn setup() -> TestMarket {
+        let authority = Pubkey::new_unique();
+        let pool = Pubkey::new_unique();
+        let mint_a = Pubkey::new_unique();
+        let mint_b = Pubkey::new_unique();
+
+        let market = test_market(&pool, MarketMaker::Orca, &get_vault_address(&mint_a, None).0, &get_vault_address(&mint_b, None).0);
+
+        let mut snapshot = AccountSnapshot::from_iter([
+            tuna_config_account(&test_tuna_config(&Pubkey::new_unique())),
+            market_account(&market),
+            vault_account(&test_vault(&mint_a, 0, 0, 0)),
+            vault_account(&test_vault(&mint_b, 0, 0, 0)),
+            (mint_a, mint_account(9)),
+            (mint_b, mint_account(6)),
+        ]);
+        snapshot.insert_sysvar(&Rent::default()).unwrap();
+
+        TestMarket {
+            authority,
+            pool,
+            mint_a,
+            mint_b,
+            snapshot,
+        }
+    }
+
+    fn jupiter_route(instruction: Instruction) -> JupiterRoute {
+        JupiterRoute {
+            instruction,
+            address_lookup_table_addresses: vec![],
+        }
+    }
+
+    #[test]
+    fn test_open_and_increase_spot_position_jupiter_route() {
+        let test = setup();
+        let wallet_source = Pubkey::new_unique();
+        let wallet_destination = Pubkey::new_unique();
+        let tuna_position = get_tuna_spot_position_address(&test.authority, &test.pool).0;
+
+        let args = || OpenAndIncreaseTunaSpotPositionJupiterArgs {
+            position_token: PoolToken::A,
+            collateral_token: PoolToken::A,
+            collateral_amount: 1_000_000,
+            borrow_amount: 200_000,
+        };
+
+        // The borrowed funds less the 0.1% protocol fee are swapped.
+        for discriminator in [JUPITER_ROUTE_V2_DISCRIMINATOR, JUPITER_SHARED_ACCOUNTS_ROUTE_DISCRIMINATOR] {
+            let route = jupiter_route(route_instruction(discriminator, &test.authority, &wallet_source, &wallet_destination, 199_800));
+            let instructions =
+                open_and_increase_tuna_spot_position_jupiter_instructions(&test.snapshot, &test.authority, &test.pool, &route, vec![], args())
+                    .unwrap()
+                    .instructions;
+
+            let ix = instructions.iter().find(|ix| ix.program_id == TUNA_ID).unwrap();
+            let ix_args = OpenAndIncreaseTunaSpotPositionJupiterInstructionArgs::deserialize(&mut &ix.data[8..]).unwrap();
+            let route_slice = &ix_args.remaining_accounts_info.slices[0];
+            assert_eq!(route_slice.accounts_type, AccountsType::JupiterRoute);
+            assert_eq!(route_slice.length as usize, route.instruction.accounts.len());
+
+            let route_accounts = &ix.accounts[ix.accounts.len() - route.instruction.accounts.len()..];
+            let (authority_index, source_index, destination_index) =
+                JupiterRouteKind::from_discriminator(&discriminator).unwrap().user_account_indexes();
+            assert_eq!(route_accounts[authority_index].pubkey, tuna_position);
+            assert_eq!(
+                route_accounts[source_index].pubkey,
+                get_associated_token_address_with_program_id(&tuna_position, &test.mint_b, &spl_token::ID)
+            );
+            assert_eq!(
+                route_accounts[destination_index].pubkey,
+                get_associated_token_address_with_program_id(&tuna_position, &test.mint_a, &spl_token::ID)
+            );
+        }
+
+        // The route must swap the amount the program swaps.
+        let route = jupiter_route(route_instruction(JUPITER_ROUTE_V2_DISCRIMINATOR, &test.authority, &wallet_source, &wallet_destination, 200_000));
+        assert!(
+            open_and_increase_tuna_spot_position_jupiter_instructions(&test.snapshot, &test.authority, &test.pool, &route, vec![], args()).is_err()
+        );
+    }
+
+    #[test]
+    fn test_modify_spot_position_jupiter_route() {
+        let mut test = setup();
+        let wallet_source = Pubkey::new_unique();
+        let wallet_destination = Pubkey::new_unique();
+
+        let mut tuna_position = test_tuna_spot_position(&test.authority, &test.pool, &test.mint_a, &test.mint_b, PoolToken::A, PoolToken::B);
+        tuna_position.amount = 3_000_000;
+        test.snapshot.extend([tuna_spot_position_account(&tuna_position)]);
+
+        let modify = |snapshot: &AccountSnapshot, route: &JupiterRoute, decrease_percent: u32| {
+            modify_tuna_spot_position_jupiter_instructions(
+                snapshot,
+                &test.authority,
+                &test.pool,
+                route,
+                vec![],
+                ModifyTunaSpotPositionJupiterArgs {
+                    decrease_percent,
+                    collateral_amount: 0,
+                    borrow_amount: 0,
+                },
+            )
+        };
+
+        // The whole removed amount is swapped if the collateral token isn't the position token.
+        let route = jupiter_route(route_instruction(
+            JUPITER_SHARED_ACCOUNTS_ROUTE_V2_DISCRIMINATOR,
+            &test.authority,
+            &wallet_source,
+            &wallet_destination,
+            1_500_000,
+        ));
+        assert!(modify(&test.snapshot, &route, HUNDRED_PERCENT / 2).is_ok());
+        let route = jupiter_route(route_instruction(
+            JUPITER_SHARED_ACCOUNTS_ROUTE_V2_DISCRIMINATOR,
+            &test.authority,
+            &wallet_source,
+            &wallet_destination,
+            1_000_000,
+        ));
+        assert!(modify(&test.snapshot, &route, HUNDRED_PERCENT / 2).is_err());
+
+        // Exact-out routes may spend less than the removed amount.
+        let route = jupiter_route(route_instruction(
+            JUPITER_EXACT_OUT_ROUTE_DISCRIMINATOR,
+            &test.authority,
+            &wallet_source,
+            &wallet_destination,
+            1_000_000,
+        ));
+        assert!(modify(&test.snapshot, &route, HUNDRED_PERCENT / 2).is_ok());
+
+        // Otherwise, only the funds required to repay the debt are swapped.
+        tuna_position.collateral_token = PoolToken::A;
+        test.snapshot.extend([tuna_spot_position_account(&tuna_position)]);
+        let route = jupiter_route(route_instruction(JUPITER_ROUTE_DISCRIMINATOR, &test.authority, &wallet_source, &wallet_destination, 1_000_000));
+        assert!(modify(&test.snapshot, &route, HUNDRED_PERCENT / 2).is_ok());
+        let route = jupiter_route(route_instruction(JUPITER_ROUTE_DISCRIMINATOR, &test.authority, &wallet_source, &wallet_destination, 1_500_001));
+        assert!(modify(&test.snapshot, &route, HUNDRED_PERCENT / 2).is_err());
+    }
+}
@@ -0,0 +1,145 @@
+#[cfg(test)]
+mod tests {
+    use crate::instructions::SetTunaLpPositionLimitOrdersInstructionArgs;
+    use crate::tests::*;
+    use crate::utils::get_limit_order_sqrt_prices;
+    use crate::{
+        set_tuna_lp_position_limit_orders_instruction, set_tuna_lp_position_limit_orders_instructions, AccountSnapshot,
+        SetTunaLpPositionLimitOrdersArgs, TunaError, TunaLimitOrderType, TUNA_POSITION_FLAGS_LOWER_LIMIT_ORDER_SWAP_TO_TOKEN_A,
+        TUNA_POSITION_FLAGS_LOWER_LIMIT_ORDER_SWAP_TO_TOKEN_B, TUNA_POSITION_FLAGS_UPPER_LIMIT_ORDER_SWAP_TO_TOKEN_B,
+    };
+    use defituna_core::{TOKEN_A, TOKEN_B};
+    use fusionamm_core::{price_to_sqrt_price, MAX_SQRT_PRICE, MIN_SQRT_PRICE};
+    use solana_pubkey::Pubkey;
+
+    const DECIMALS_A: u8 = 9;
+    const DECIMALS_B: u8 = 6;
+
+    fn pool_sqrt_price() -> u128 {
+        price_to_sqrt_price(200.0, DECIMALS_A, DECIMALS_B)
+    }
+
+    #[test]
+    fn test_disabled_limit_orders() {
+        let (lower, upper) = get_limit_order_sqrt_prices(None, None, pool_sqrt_price(), DECIMALS_A, DECIMALS_B).unwrap();
+        assert_eq!(lower, MIN_SQRT_PRICE);
+        assert_eq!(upper, MAX_SQRT_PRICE);
+    }
+
+    #[test]
+    fn test_limit_orders_around_pool_price() {
+        let (lower, upper) = get_limit_order_sqrt_prices(Some(150.0), Some(250.0), pool_sqrt_price(), DECIMALS_A, DECIMALS_B).unwrap();
+        assert_eq!(lower, price_to_sqrt_price(150.0, DECIMALS_A, DECIMALS_B));
+        assert_eq!(upper, price_to_sqrt_price(250.0, DECIMALS_A, DECIMALS_B));
+
+        let (lower, upper) = get_limit_order_sqrt_prices(Some(150.0), None, pool_sqrt_price(), DECIMALS_A, DECIMALS_B).unwrap();
+        assert_eq!(lower, price_to_sqrt_price(150.0, DECIMALS_A, DECIMALS_B));
+        assert_eq!(upper, MAX_SQRT_PRICE);
+
+        let (lower, upper) = get_limit_order_sqrt_prices(None, Some(250.0), pool_sqrt_price(), DECIMALS_A, DECIMALS_B).unwrap();
+        assert_eq!(lower, MIN_SQRT_PRICE);
+        assert_eq!(upper, price_to_sqrt_price(250.0, DECIMALS_A, DECIMALS_B));
+    }
+
+    #[test]
+    fn test_lower_limit_order_must_be_below_pool_price() {
+        for price in [200.0, 250.0] {
+            assert!(get_limit_order_sqrt_prices(Some(price), None, pool_sqrt_price(), DECIMALS_A, DECIMALS_B).is_err());
+        }
+        // Below the minimal pool price.
+        assert!(get_limit_order_sqrt_prices(Some(1e-30), None, pool_sqrt_price(), DECIMALS_A, DECIMALS_B).is_err());
+    }
+
+    #[test]
+    fn test_upper_limit_order_must_be_above_pool_price() {
+        for price in [200.0, 150.0] {
+            assert!(get_limit_order_sqrt_prices(None, Some(price), pool_sqrt_price(), DECIMALS_A, DECIMALS_B).is_err());
+        }
+        // Above the maximal pool price.
+        assert!(get_limit_order_sqrt_prices(None, Some(1e30), pool_sqrt_price(), DECIMALS_A, DECIMALS_B).is_err());
+    }
+
+    #[test]
+    fn test_set_lp_position_limit_orders_instructions() {
+        let authority = Pubkey::new_unique();
+        let position_mint = Pubkey::new_unique();
+        let mint_a = Pubkey::new_unique();
+        let mint_b = Pubkey::new_unique();
+        let pool_address = Pubkey::new_unique();
+
+        let whirlpool = test_whirlpool(&mint_a, &mint_b, 64, pool_sqrt_price());
+        let tuna_position = test_tuna_lp_position(&authority, &pool_address, &mint_a, &mint_b, &position_mint);
+        let snapshot = AccountSnapshot::from_iter([
+            tuna_lp_position_account(&tuna_position),
+            (pool_address, whirlpool_account(&whirlpool)),
+            (mint_a, mint_account(DECIMALS_A)),
+            (mint_b, mint_account(DECIMALS_B)),
+        ]);
+
+        let instructions = set_tuna_lp_position_limit_orders_instructions(
+            &snapshot,
+            &authority,
+            &position_mint,
+            SetTunaLpPositionLimitOrdersArgs {
+                lower_limit_order_price: Some(150.0),
+                upper_limit_order_price: Some(250.0),
+                lower_limit_order_swap_to_token: Some(TOKEN_A),
+                upper_limit_order_swap_to_token: Some(TOKEN_B),
+            },
+        )
+        .unwrap()
+        .instructions;
+
+        let expected = set_tuna_lp_position_limit_orders_instruction(
+            &authority,
+            &position_mint,
+            SetTunaLpPositionLimitOrdersInstructionArgs {
+                lower_limit_order_sqrt_price: price_to_sqrt_price(150.0, DECIMALS_A, DECIMALS_B),
+                upper_limit_order_sqrt_price: price_to_sqrt_price(250.0, DECIMALS_A, DECIMALS_B),
+                swap_to_token_on_limit_order: (TUNA_POSITION_FLAGS_LOWER_LIMIT_ORDER_SWAP_TO_TOKEN_A
+                    | TUNA_POSITION_FLAGS_UPPER_LIMIT_ORDER_SWAP_TO_TOKEN_B) as u8,
+            },
+        );
+        assert_eq!(instructions, vec![expected]);
+
+        // The take profit price is below the current pool price.
+        let result = set_tuna_lp_position_limit_orders_instructions(
+            &snapshot,
+            &authority,
+            &position_mint,
+            SetTunaLpPositionLimitOrdersArgs {
+                upper_limit_order_price: Some(190.0),
+                ..Default::default()
+            },
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_lp_limit_order_execution_with_conflicting_swap_tokens() {
+        let mint_a = Pubkey::new_unique();
+        let mint_b = Pubkey::new_unique();
+        let vault_a = test_vault(&mint_a, 0, 0, 0);
+        let vault_b = test_vault(&mint_b, 0, 0, 0);
+
+        let mut tuna_position = test_tuna_lp_position(&Pubkey::new_unique(), &Pubkey::new_unique(), &mint_a, &mint_b, &Pubkey::new_unique());
+        tuna_position.version = 7;
+        tuna_position.liquidity = 1_000_000_000;
+        tuna_position.lower_limit_order_sqrt_price = fusionamm_core::tick_index_to_sqrt_price(-64);
+        tuna_position.upper_limit_order_sqrt_price = fusionamm_core::tick_index_to_sqrt_price(64);
+        tuna_position.flags = TUNA_POSITION_FLAGS_LOWER_LIMIT_ORDER_SWAP_TO_TOKEN_A | TUNA_POSITION_FLAGS_LOWER_LIMIT_ORDER_SWAP_TO_TOKEN_B;
+
+        // The limit order can't swap to both tokens, which is an argument error rather than a math overflow.
+        assert_eq!(
+            tuna_position.compute_limit_order_execution(TunaLimitOrderType::StopLoss, 3000, &vault_a, &vault_b),
+            Err(TunaError::InvalidInstructionArguments)
+        );
+
+        tuna_position.flags = TUNA_POSITION_FLAGS_UPPER_LIMIT_ORDER_SWAP_TO_TOKEN_B;
+        let quote = tuna_position
+            .compute_limit_order_execution(TunaLimitOrderType::TakeProfit, 3000, &vault_a, &vault_b)
+            .unwrap();
+        assert!(quote.swap_a_to_b);
+        assert_eq!(quote.amount_a, 0);
+    }
+}
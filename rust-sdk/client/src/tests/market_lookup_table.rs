@@ -0,0 +1,177 @@
+#[cfg(test)]
+mod tests {
+    use crate::accounts::Market;
+    use crate::instructions::{UpdateMarketInstructionArgs, UPDATE_MARKET_DISCRIMINATOR};
+    use crate::tests::*;
+    use crate::types::MarketMaker;
+    use crate::{
+        create_market_lookup_table_instructions, extend_market_lookup_table_instructions, get_addresses_for_market_lookup_table, get_market_address,
+        get_vault_address, update_market_instruction, AccountSnapshot, TUNA_ID,
+    };
+    use borsh::BorshDeserialize;
+    use orca_whirlpools_core::tick_index_to_sqrt_price;
+    use solana_address_lookup_table_interface::instruction::{create_lookup_table, extend_lookup_table};
+    use solana_instruction::Instruction;
+    use solana_pubkey::Pubkey;
+
+    const LOOKUP_TABLE_EXTEND_CHUNK_SIZE: usize = 20;
+
+    struct TestMarket {
+        authority: Pubkey,
+        pool: Pubkey,
+        market: Market,
+        snapshot: AccountSnapshot,
+    }
+
+    /// Sets up an Orca market of SOL, so the native mint is listed twice before the addresses are deduplicated.
+    fn setup() -> TestMarket {
+        let authority = Pubkey::new_unique();
+        let mint_a = spl_token::native_mint::ID;
+        let mint_b = Pubkey::new_unique();
+        let pool = Pubkey::new_unique();
+
+        let whirlpool = test_whirlpool(&mint_a, &mint_b, 64, tick_index_to_sqrt_price(0));
+
+        let mut market = test_market(&pool, MarketMaker::Orca, &get_vault_address(&mint_a, None).0, &get_vault_address(&mint_b, None).0);
+        market.oracle_price_deviation_threshold = 50_000;
+        market.disabled = true;
+        market.borrow_limit_a = 1_000_000;
+        market.borrow_limit_b = 2_000_000;
+        market.max_swap_slippage = 30_000;
+        market.rebalance_protocol_fee = 500;
+        market.spot_position_size_limit_a = 3_000_000;
+        market.spot_position_size_limit_b = 4_000_000;
+
+        let snapshot = AccountSnapshot::from_iter([
+            tuna_config_account(&test_tuna_config(&Pubkey::new_unique())),
+            market_account(&market),
+            vault_account(&test_vault(&mint_a, 0, 0, 0)),
+            vault_account(&test_vault(&mint_b, 0, 0, 0)),
+            (pool, whirlpool_account(&whirlpool)),
+            (mint_a, mint_account(9)),
+            (mint_b, mint_account(6)),
+        ]);
+
+        TestMarket {
+            authority,
+            pool,
+            market,
+            snapshot,
+        }
+    }
+
+    /// Returns the market args of the update market instruction.
+    fn update_market_args(instruction: &Instruction) -> UpdateMarketInstructionArgs {
+        assert_eq!(instruction.program_id, TUNA_ID);
+        assert_eq!(instruction.data[..8], UPDATE_MARKET_DISCRIMINATOR);
+        UpdateMarketInstructionArgs::try_from_slice(&instruction.data[8..]).unwrap()
+    }
+
+    fn expected_update_market_args(market: &Market, lookup_table_address: &Pubkey) -> UpdateMarketInstructionArgs {
+        UpdateMarketInstructionArgs {
+            address_lookup_table: *lookup_table_address,
+            max_leverage: market.max_leverage,
+            protocol_fee: market.protocol_fee,
+            protocol_fee_on_collateral: market.protocol_fee_on_collateral,
+            liquidation_fee: market.liquidation_fee,
+            liquidation_threshold: market.liquidation_threshold,
+            oracle_price_deviation_threshold: market.oracle_price_deviation_threshold,
+            disabled: market.disabled,
+            borrow_limit_a: market.borrow_limit_a,
+            borrow_limit_b: market.borrow_limit_b,
+            max_swap_slippage: market.max_swap_slippage,
+            rebalance_protocol_fee: market.rebalance_protocol_fee,
+            spot_position_size_limit_a: market.spot_position_size_limit_a,
+            spot_position_size_limit_b: market.spot_position_size_limit_b,
+        }
+    }
+
+    #[test]
+    fn test_market_lookup_table_addresses_are_unique() {
+        let test = setup();
+
+        let addresses = get_addresses_for_market_lookup_table(&test.snapshot, &test.pool).unwrap();
+        for (i, address) in addresses.iter().enumerate() {
+            assert!(!addresses[i + 1..].contains(address), "{} is listed twice", address);
+        }
+
+        assert_eq!(addresses[5], spl_token::native_mint::ID);
+        assert!(addresses.contains(&get_market_address(&test.pool).0));
+        assert!(addresses.contains(&test.pool));
+    }
+
+    #[test]
+    fn test_create_market_lookup_table_instructions() {
+        let test = setup();
+        let addresses = get_addresses_for_market_lookup_table(&test.snapshot, &test.pool).unwrap();
+        assert!(addresses.len() > LOOKUP_TABLE_EXTEND_CHUNK_SIZE);
+
+        let tx_plans = create_market_lookup_table_instructions(&test.snapshot, &test.authority, &test.pool, 100).unwrap();
+        let (create_instruction, lookup_table_address) = create_lookup_table(test.authority, test.authority, 100);
+
+        // The addresses are added in chunks, one transaction for each.
+        let chunks: Vec<&[Pubkey]> = addresses.chunks(LOOKUP_TABLE_EXTEND_CHUNK_SIZE).collect();
+        assert_eq!(tx_plans.len(), chunks.len());
+        for (tx_plan, chunk) in tx_plans.iter().zip(&chunks) {
+            let extend_instruction = extend_lookup_table(lookup_table_address, test.authority, Some(test.authority), chunk.to_vec());
+            assert!(tx_plan.instructions.contains(&extend_instruction));
+        }
+
+        // The lookup table is created and recorded in the market by the first transaction.
+        let first_instructions = &tx_plans[0].instructions;
+        assert_eq!(first_instructions.len(), 3);
+        assert_eq!(first_instructions[0], create_instruction);
+        assert_eq!(update_market_args(&first_instructions[2]), expected_update_market_args(&test.market, &lookup_table_address));
+        assert_eq!(tx_plans[0].created_accounts, vec![lookup_table_address]);
+        assert!(tx_plans[1..].iter().all(|tx_plan| tx_plan.instructions.len() == 1));
+    }
+
+    #[test]
+    fn test_extend_market_lookup_table_instructions() {
+        let mut test = setup();
+        let addresses = get_addresses_for_market_lookup_table(&test.snapshot, &test.pool).unwrap();
+        let lookup_table_address = Pubkey::new_unique();
+
+        // Only the missing addresses are added. The market already uses the lookup table.
+        let mut market = test.market.clone();
+        market.address_lookup_table = lookup_table_address;
+        test.snapshot.extend([
+            market_account(&market),
+            (lookup_table_address, lookup_table_account(&test.authority, &addresses[..5])),
+        ]);
+        let tx_plans = extend_market_lookup_table_instructions(&test.snapshot, &test.authority, &test.pool, &lookup_table_address).unwrap();
+        let chunks: Vec<&[Pubkey]> = addresses[5..].chunks(LOOKUP_TABLE_EXTEND_CHUNK_SIZE).collect();
+        assert_eq!(tx_plans.len(), chunks.len());
+        for (tx_plan, chunk) in tx_plans.iter().zip(&chunks) {
+            assert_eq!(
+                tx_plan.instructions,
+                vec![extend_lookup_table(
+                    lookup_table_address,
+                    test.authority,
+                    Some(test.authority),
+                    chunk.to_vec()
+                )]
+            );
+        }
+
+        // Nothing is missing.
+        test.snapshot
+            .extend([(lookup_table_address, lookup_table_account(&test.authority, &addresses))]);
+        assert!(extend_market_lookup_table_instructions(&test.snapshot, &test.authority, &test.pool, &lookup_table_address)
+            .unwrap()
+            .is_empty());
+
+        // The market doesn't use the lookup table yet, so only the market is updated, keeping its other args.
+        test.snapshot.extend([market_account(&test.market)]);
+        let tx_plans = extend_market_lookup_table_instructions(&test.snapshot, &test.authority, &test.pool, &lookup_table_address).unwrap();
+        assert_eq!(tx_plans.len(), 1);
+        assert_eq!(
+            tx_plans[0].instructions,
+            vec![update_market_instruction(
+                &test.authority,
+                &test.pool,
+                expected_update_market_args(&test.market, &lookup_table_address)
+            )]
+        );
+    }
+}
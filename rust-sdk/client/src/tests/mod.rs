@@ -1,9 +1,19 @@
+mod account_snapshot;
+mod async_txbuilders;
 mod helpers;
+mod jupiter_route_accounts;
+mod limit_orders;
+mod market_lookup_table;
+mod orca_rewards;
+mod repay_tuna_lp_position_debt;
 mod tuna_liquidity_position_fusion;
 mod tuna_liquidity_position_fusion_jupiter;
 mod tuna_liquidity_position_orca;
 mod tuna_liquidity_position_orca_jupiter;
+mod tuna_position_flags;
 mod tuna_spot_position_fusion;
 mod tuna_spot_position_orca;
+mod tx_plan;
+mod versioned_transaction;
 
 pub use helpers::*;
@@ -0,0 +1,298 @@
+#[cfg(test)]
+mod tests {
+    use crate::accounts::TunaLpPosition;
+    use crate::instructions::{CollectRewardOrcaInstructionArgs, DecreaseTunaLpPositionOrcaInstructionArgs, COLLECT_REWARD_ORCA_DISCRIMINATOR};
+    use crate::tests::*;
+    use crate::types::{AccountsType, MarketMaker};
+    use crate::utils::orca::{get_rewards_remaining_accounts, prepare_rewards_to_collect, RewardToCollect};
+    use crate::{
+        close_active_tuna_lp_position_orca_instructions, collect_reward_orca_instruction, collect_reward_orca_instructions,
+        decrease_tuna_lp_position_orca_instructions, get_vault_address, AccountSnapshot, CloseActiveTunaLpPositionArgs, DecreaseTunaLpPositionArgs,
+        TUNA_ID,
+    };
+    use borsh::BorshDeserialize;
+    use orca_whirlpools_client::Whirlpool;
+    use orca_whirlpools_core::tick_index_to_sqrt_price;
+    use solana_instruction::{AccountMeta, Instruction};
+    use solana_pubkey::Pubkey;
+    use solana_sysvar::clock::Clock;
+    use spl_associated_token_account::get_associated_token_address_with_program_id;
+    use spl_associated_token_account::instruction::create_associated_token_account_idempotent;
+    use spl_token_2022::instruction::close_account;
+
+    const TICK_SPACING: u16 = 64;
+
+    struct TestPosition {
+        authority: Pubkey,
+        position_mint: Pubkey,
+        tuna_position: TunaLpPosition,
+        whirlpool: Whirlpool,
+        snapshot: AccountSnapshot,
+    }
+
+    /// Sets up a position with rewards owed for the rewards 0 and 2. Reward 1 is initialized, but nothing is owed.
+    /// Reward 1 is the native mint and reward 2 is a Token-2022 mint.
+    fn setup() -> TestPosition {
+        let authority = Pubkey::new_unique();
+        let position_mint = Pubkey::new_unique();
+        let mint_a = Pubkey::new_unique();
+        let mint_b = Pubkey::new_unique();
+        let whirlpool_address = Pubkey::new_unique();
+
+        let mut whirlpool = test_whirlpool(&mint_a, &mint_b, TICK_SPACING, tick_index_to_sqrt_price(0));
+        for reward_info in whirlpool.reward_infos.iter_mut() {
+            reward_info.mint = Pubkey::new_unique();
+            reward_info.vault = Pubkey::new_unique();
+        }
+        whirlpool.reward_infos[1].mint = spl_token::native_mint::ID;
+
+        let mut orca_position = test_orca_position(&whirlpool_address, &position_mint, -128, 128);
+        orca_position.reward_infos[0].amount_owed = 1_000;
+        orca_position.reward_infos[2].amount_owed = 2_000;
+
+        let tuna_position = test_tuna_lp_position(&authority, &whirlpool_address, &mint_a, &mint_b, &position_mint);
+        let market = test_market(&whirlpool_address, MarketMaker::Orca, &get_vault_address(&mint_a, None).0, &get_vault_address(&mint_b, None).0);
+
+        let mut reward_2022_mint = mint_account(6);
+        reward_2022_mint.owner = spl_token_2022::ID;
+
+        let mut snapshot = AccountSnapshot::from_iter([
+            tuna_lp_position_account(&tuna_position),
+            (whirlpool_address, whirlpool_account(&whirlpool)),
+            orca_position_account(&orca_position),
+            orca_tick_array_account(&whirlpool_address, -128, TICK_SPACING),
+            orca_tick_array_account(&whirlpool_address, 128, TICK_SPACING),
+            market_account(&market),
+            vault_account(&test_vault(&mint_a, 0, 0, 0)),
+            vault_account(&test_vault(&mint_b, 0, 0, 0)),
+            (mint_a, mint_account(9)),
+            (mint_b, mint_account(6)),
+            (whirlpool.reward_infos[0].mint, mint_account(6)),
+            (whirlpool.reward_infos[1].mint, mint_account(9)),
+            (whirlpool.reward_infos[2].mint, reward_2022_mint),
+        ]);
+        snapshot.insert_sysvar(&Clock::default()).unwrap();
+
+        TestPosition {
+            authority,
+            position_mint,
+            tuna_position,
+            whirlpool,
+            snapshot,
+        }
+    }
+
+    fn reward_token_program(reward_index: u8) -> Pubkey {
+        if reward_index == 2 {
+            spl_token_2022::ID
+        } else {
+            spl_token::ID
+        }
+    }
+
+    /// Returns the reward index of a collect reward instruction, or None for other instructions.
+    fn collected_reward_index(instruction: &Instruction) -> Option<u8> {
+        if instruction.program_id != TUNA_ID || instruction.data[..8] != COLLECT_REWARD_ORCA_DISCRIMINATOR {
+            return None;
+        }
+        Some(
+            CollectRewardOrcaInstructionArgs::deserialize(&mut &instruction.data[8..])
+                .unwrap()
+                .reward_index,
+        )
+    }
+
+    #[test]
+    fn test_rewards_remaining_accounts_layout() {
+        let test = setup();
+        let rewards = [
+            RewardToCollect {
+                reward_index: 0,
+                reward_token_program: spl_token::ID,
+            },
+            RewardToCollect {
+                reward_index: 2,
+                reward_token_program: spl_token_2022::ID,
+            },
+        ];
+
+        let accounts = get_rewards_remaining_accounts(&test.authority, &test.whirlpool, &rewards);
+        assert_eq!(accounts.len(), 4 * rewards.len());
+
+        for (reward, accounts) in rewards.iter().zip(accounts.chunks(4)) {
+            let reward_info = &test.whirlpool.reward_infos[reward.reward_index as usize];
+            let authority_ata = get_associated_token_address_with_program_id(&test.authority, &reward_info.mint, &reward.reward_token_program);
+            assert_eq!(
+                accounts,
+                [
+                    AccountMeta::new_readonly(reward_info.mint, false),
+                    AccountMeta::new_readonly(reward.reward_token_program, false),
+                    AccountMeta::new(authority_ata, false),
+                    AccountMeta::new(reward_info.vault, false),
+                ]
+            );
+        }
+
+        assert!(get_rewards_remaining_accounts(&test.authority, &test.whirlpool, &[]).is_empty());
+    }
+
+    #[test]
+    fn test_prepare_rewards_to_collect() {
+        let test = setup();
+
+        let (rewards, ata_instructions) = prepare_rewards_to_collect(&test.snapshot, &test.authority, &test.whirlpool, &test.position_mint).unwrap();
+
+        let rewards: Vec<(u8, Pubkey)> = rewards.iter().map(|r| (r.reward_index, r.reward_token_program)).collect();
+        assert_eq!(rewards, vec![(0, spl_token::ID), (2, spl_token_2022::ID)]);
+
+        // One authority ATA per collected reward, nothing to close.
+        assert_eq!(ata_instructions.create.len(), 2);
+        assert!(ata_instructions.cleanup.is_empty());
+    }
+
+    #[test]
+    fn test_decrease_instruction_rewards_slice() {
+        let test = setup();
+
+        let instructions = decrease_tuna_lp_position_orca_instructions(
+            &test.snapshot,
+            &test.authority,
+            &test.position_mint,
+            DecreaseTunaLpPositionArgs {
+                decrease_percent: 500_000,
+                ..Default::default()
+            },
+        )
+        .unwrap()
+        .instructions;
+
+        let decrease_instruction = instructions.iter().find(|ix| ix.program_id == TUNA_ID).unwrap();
+        let args = DecreaseTunaLpPositionOrcaInstructionArgs::deserialize(&mut &decrease_instruction.data[8..]).unwrap();
+
+        let rewards_slice = args.remaining_accounts_info.slices.last().unwrap();
+        assert_eq!(rewards_slice.accounts_type, AccountsType::Rewards);
+        assert_eq!(rewards_slice.length, 8);
+
+        let remaining_accounts_len: usize = args.remaining_accounts_info.slices.iter().map(|slice| slice.length as usize).sum();
+        let remaining_accounts = &decrease_instruction.accounts[decrease_instruction.accounts.len() - remaining_accounts_len..];
+
+        let expected_rewards = get_rewards_remaining_accounts(
+            &test.authority,
+            &test.whirlpool,
+            &[
+                RewardToCollect {
+                    reward_index: 0,
+                    reward_token_program: spl_token::ID,
+                },
+                RewardToCollect {
+                    reward_index: 2,
+                    reward_token_program: spl_token_2022::ID,
+                },
+            ],
+        );
+        assert_eq!(&remaining_accounts[remaining_accounts_len - 8..], expected_rewards.as_slice());
+    }
+
+    #[test]
+    fn test_collect_reward_instructions() {
+        let mut test = setup();
+
+        let instructions = collect_reward_orca_instructions(&test.snapshot, &test.authority, &test.position_mint)
+            .unwrap()
+            .instructions;
+
+        // All initialized rewards are collected, including the ones with nothing owed yet. Each reward creates the authority ATA
+        // first, and the WSOL account of the native mint reward is closed at the end.
+        let mut expected_instructions = vec![];
+        for reward_index in 0..3u8 {
+            let reward_mint = test.whirlpool.reward_infos[reward_index as usize].mint;
+            let token_program = reward_token_program(reward_index);
+            expected_instructions.push(create_associated_token_account_idempotent(&test.authority, &test.authority, &reward_mint, &token_program));
+            expected_instructions.push(collect_reward_orca_instruction(
+                &test.authority,
+                &test.tuna_position,
+                &test.whirlpool,
+                reward_index,
+                &token_program,
+            ));
+        }
+        let authority_wsol_ata = get_associated_token_address_with_program_id(&test.authority, &spl_token::native_mint::ID, &spl_token::ID);
+        expected_instructions.push(close_account(&spl_token::ID, &authority_wsol_ata, &test.authority, &test.authority, &[]).unwrap());
+        assert_eq!(instructions, expected_instructions);
+
+        let reward_indexes: Vec<u8> = instructions.iter().filter_map(collected_reward_index).collect();
+        assert_eq!(reward_indexes, vec![0, 1, 2]);
+
+        // The collect instruction pays out to the authority ATA of the reward token program.
+        let collect_instruction = &instructions[5];
+        let reward_2_ata = get_associated_token_address_with_program_id(&test.authority, &test.whirlpool.reward_infos[2].mint, &spl_token_2022::ID);
+        assert!(collect_instruction
+            .accounts
+            .contains(&AccountMeta::new_readonly(spl_token_2022::ID, false)));
+        assert!(collect_instruction.accounts.contains(&AccountMeta::new(reward_2_ata, false)));
+
+        // Uninitialized rewards are skipped.
+        test.whirlpool.reward_infos[1].mint = Pubkey::default();
+        test.snapshot.extend([(test.tuna_position.pool, whirlpool_account(&test.whirlpool))]);
+        let instructions = collect_reward_orca_instructions(&test.snapshot, &test.authority, &test.position_mint)
+            .unwrap()
+            .instructions;
+        assert_eq!(instructions.len(), 4);
+        let reward_indexes: Vec<u8> = instructions.iter().filter_map(collected_reward_index).collect();
+        assert_eq!(reward_indexes, vec![0, 2]);
+    }
+
+    #[test]
+    fn test_close_collects_owed_rewards() {
+        let test = setup();
+
+        let instructions = close_active_tuna_lp_position_orca_instructions(
+            &test.snapshot,
+            &test.authority,
+            &test.position_mint,
+            CloseActiveTunaLpPositionArgs::default(),
+        )
+        .unwrap()
+        .instructions;
+
+        // The authority ATAs of the owed rewards are created before the position is decreased.
+        let decrease_instruction_index = instructions.iter().position(|ix| ix.program_id == TUNA_ID).unwrap();
+        for reward_index in [0u8, 2] {
+            let reward_mint = test.whirlpool.reward_infos[reward_index as usize].mint;
+            let create_ata_instruction =
+                create_associated_token_account_idempotent(&test.authority, &test.authority, &reward_mint, &reward_token_program(reward_index));
+            let create_ata_instruction_index = instructions.iter().position(|ix| *ix == create_ata_instruction).unwrap();
+            assert!(create_ata_instruction_index < decrease_instruction_index);
+        }
+
+        // Reward 1 has nothing owed, so neither its ATA is created nor its WSOL account closed.
+        let authority_wsol_ata = get_associated_token_address_with_program_id(&test.authority, &spl_token::native_mint::ID, &spl_token::ID);
+        assert!(!instructions
+            .iter()
+            .any(|ix| ix.accounts.iter().any(|account| account.pubkey == authority_wsol_ata)));
+
+        // The owed rewards are collected by the decrease instruction, the position is closed with no rewards left.
+        let decrease_instruction = &instructions[decrease_instruction_index];
+        let args = DecreaseTunaLpPositionOrcaInstructionArgs::deserialize(&mut &decrease_instruction.data[8..]).unwrap();
+        let rewards_slice = args.remaining_accounts_info.slices.last().unwrap();
+        assert_eq!(rewards_slice.accounts_type, AccountsType::Rewards);
+
+        let expected_rewards = get_rewards_remaining_accounts(
+            &test.authority,
+            &test.whirlpool,
+            &[
+                RewardToCollect {
+                    reward_index: 0,
+                    reward_token_program: spl_token::ID,
+                },
+                RewardToCollect {
+                    reward_index: 2,
+                    reward_token_program: spl_token_2022::ID,
+                },
+            ],
+        );
+        assert_eq!(rewards_slice.length as usize, expected_rewards.len());
+        assert!(decrease_instruction.accounts.ends_with(&expected_rewards));
+        assert!(!instructions.iter().any(|ix| collected_reward_index(ix).is_some()));
+    }
+}
@@ -0,0 +1,154 @@
+#[cfg(test)]
+mod tests {
+    use crate::accounts::{TunaLpPosition, Vault};
+    use crate::tests::*;
+    use crate::types::MarketMaker;
+    use crate::{
+        get_vault_address, repay_tuna_lp_position_debt_instruction, repay_tuna_lp_position_debt_instructions, AccountSnapshot,
+        RepayTunaLpPositionDebtArgs, TUNA_ID,
+    };
+    use defituna_core::fixed::Rounding;
+    use solana_instruction::Instruction;
+    use solana_pubkey::Pubkey;
+    use solana_sysvar::clock::Clock;
+
+    const TIMESTAMP: u64 = 1_750_000_000;
+
+    struct TestPosition {
+        authority: Pubkey,
+        position_mint: Pubkey,
+        tuna_position: TunaLpPosition,
+        vault_a: Vault,
+        vault_b: Vault,
+        snapshot: AccountSnapshot,
+    }
+
+    fn setup(loan_shares_a: u64, loan_shares_b: u64, interest_rate: u64, elapsed: u64) -> TestPosition {
+        let authority = Pubkey::new_unique();
+        let position_mint = Pubkey::new_unique();
+        let mint_a = Pubkey::new_unique();
+        let mint_b = Pubkey::new_unique();
+        let pool = Pubkey::new_unique();
+
+        let mut vault_a = test_vault(&mint_a, 100_000_000, 10_000_000, TIMESTAMP);
+        vault_a.interest_rate = interest_rate;
+        let mut vault_b = test_vault(&mint_b, 100_000_000, 10_000_000, TIMESTAMP);
+        vault_b.interest_rate = interest_rate;
+
+        let market = test_market(&pool, MarketMaker::Orca, &get_vault_address(&mint_a, None).0, &get_vault_address(&mint_b, None).0);
+
+        let mut tuna_position = test_tuna_lp_position(&authority, &pool, &mint_a, &mint_b, &position_mint);
+        tuna_position.loan_shares_a = loan_shares_a;
+        tuna_position.loan_shares_b = loan_shares_b;
+
+        let mut snapshot = AccountSnapshot::from_iter([
+            tuna_lp_position_account(&tuna_position),
+            market_account(&market),
+            vault_account(&vault_a),
+            vault_account(&vault_b),
+            (mint_a, mint_account(6)),
+            (mint_b, mint_account(6)),
+        ]);
+        snapshot
+            .insert_sysvar(&Clock {
+                unix_timestamp: (TIMESTAMP + elapsed) as i64,
+                ..Default::default()
+            })
+            .unwrap();
+
+        TestPosition {
+            authority,
+            position_mint,
+            tuna_position,
+            vault_a,
+            vault_b,
+            snapshot,
+        }
+    }
+
+    fn repay_instruction(instructions: &[Instruction]) -> &Instruction {
+        let mut tuna_instructions = instructions.iter().filter(|ix| ix.program_id == TUNA_ID);
+        let instruction = tuna_instructions.next().unwrap();
+        assert!(tuna_instructions.next().is_none());
+        instruction
+    }
+
+    fn expected_instruction(test: &TestPosition, collateral_funds_a: u64, collateral_funds_b: u64) -> Instruction {
+        repay_tuna_lp_position_debt_instruction(
+            &test.authority,
+            &test.tuna_position,
+            &get_vault_address(&test.vault_a.mint, None).0,
+            &test.vault_a,
+            &get_vault_address(&test.vault_b.mint, None).0,
+            &test.vault_b,
+            &spl_token::ID,
+            &spl_token::ID,
+            RepayTunaLpPositionDebtArgs {
+                collateral_funds_a,
+                collateral_funds_b,
+            },
+        )
+    }
+
+    #[test]
+    fn test_repay_amounts_are_capped_at_debt() {
+        let test = setup(1_000_000, 0, 0, 0);
+
+        let args = RepayTunaLpPositionDebtArgs {
+            collateral_funds_a: 5_000_000,
+            collateral_funds_b: 100,
+        };
+        let instructions = repay_tuna_lp_position_debt_instructions(&test.snapshot, &test.authority, &test.position_mint, args)
+            .unwrap()
+            .instructions;
+
+        assert_eq!(repay_instruction(&instructions), &expected_instruction(&test, 1_000_000, 0));
+    }
+
+    #[test]
+    fn test_repay_amounts_below_debt_are_unchanged() {
+        let test = setup(1_000_000, 2_000_000, 0, 0);
+
+        let args = RepayTunaLpPositionDebtArgs {
+            collateral_funds_a: 400_000,
+            collateral_funds_b: 2_000_000,
+        };
+        let instructions = repay_tuna_lp_position_debt_instructions(&test.snapshot, &test.authority, &test.position_mint, args)
+            .unwrap()
+            .instructions;
+
+        assert_eq!(repay_instruction(&instructions), &expected_instruction(&test, 400_000, 2_000_000));
+    }
+
+    #[test]
+    fn test_repay_cap_includes_accrued_interest() {
+        let elapsed = 86400;
+        let test = setup(1_000_000, 0, 1 << 40, elapsed);
+
+        let mut vault_a = test.vault_a.clone();
+        vault_a.accrue_interest(TIMESTAMP + elapsed).unwrap();
+        let debt_a = vault_a.calculate_borrowed_funds(1_000_000, Rounding::Up).unwrap();
+        assert!(debt_a > 1_000_000);
+
+        let args = RepayTunaLpPositionDebtArgs {
+            collateral_funds_a: u64::MAX,
+            collateral_funds_b: 0,
+        };
+        let instructions = repay_tuna_lp_position_debt_instructions(&test.snapshot, &test.authority, &test.position_mint, args)
+            .unwrap()
+            .instructions;
+
+        assert_eq!(repay_instruction(&instructions), &expected_instruction(&test, debt_a, 0));
+    }
+
+    #[test]
+    fn test_repay_without_debt_fails() {
+        let test = setup(0, 0, 0, 0);
+
+        let args = RepayTunaLpPositionDebtArgs {
+            collateral_funds_a: 1_000,
+            collateral_funds_b: 1_000,
+        };
+        assert!(repay_tuna_lp_position_debt_instructions(&test.snapshot, &test.authority, &test.position_mint, args).is_err());
+    }
+}
@@ -10,8 +10,7 @@ mod tests {
         get_tuna_config_address, get_tuna_liquidity_position_address, increase_tuna_lp_position_fusion_instructions,
         liquidate_tuna_lp_position_fusion_instructions, open_and_increase_tuna_lp_position_fusion_instructions,
         open_tuna_lp_position_fusion_instruction, rebalance_tuna_lp_position_fusion_instructions, CloseActiveTunaLpPositionArgs,
-        DecreaseTunaLpPositionArgs, IncreaseTunaLpPositionArgs, OpenAndIncreaseTunaLpPositionArgs, HUNDRED_PERCENT, LEVERAGE_ONE,
-        TUNA_POSITION_FLAGS_ALLOW_REBALANCING,
+        DecreaseTunaLpPositionArgs, IncreaseTunaLpPositionArgs, OpenAndIncreaseTunaLpPositionArgs, TunaPositionFlags, HUNDRED_PERCENT, LEVERAGE_ONE,
     };
     use fusionamm_client::fetch_fusion_pool;
     use rstest::rstest;
@@ -99,7 +98,8 @@ mod tests {
                         max_swap_slippage: 0,
                     },
                 )
-                .unwrap(),
+                .unwrap()
+                .instructions,
             )
             .unwrap();
 
@@ -116,7 +116,8 @@ mod tests {
                         max_swap_slippage: 0,
                     },
                 )
-                .unwrap(),
+                .unwrap()
+                .instructions,
             )
             .unwrap();
 
@@ -170,7 +171,7 @@ mod tests {
                     tick_upper_index: actual_tick_index + pool.data.tick_spacing as i32 * 5,
                     lower_limit_order_sqrt_price: 0,
                     upper_limit_order_sqrt_price: 0,
-                    flags: 0,
+                    flags: TunaPositionFlags::default(),
                     collateral_a: 1_000_000_000,
                     collateral_b: 100_000_000,
                     borrow_a: 1_000_000_000,
@@ -189,10 +190,11 @@ mod tests {
                 close_active_tuna_lp_position_fusion_instructions(
                     &ctx.rpc,
                     &ctx.signer.pubkey(),
-                    &ix.position_mint,
+                    &ix.position_mint.unwrap(),
                     CloseActiveTunaLpPositionArgs::default(),
                 )
-                .unwrap(),
+                .unwrap()
+                .instructions,
             )
             .unwrap();
         });
@@ -232,7 +234,7 @@ mod tests {
                     tick_upper_index: actual_tick_index + pool.data.tick_spacing as i32 * 5,
                     lower_limit_order_sqrt_price: 0,
                     upper_limit_order_sqrt_price: 0,
-                    flags: TUNA_POSITION_FLAGS_ALLOW_REBALANCING,
+                    flags: TunaPositionFlags::default().with_allow_rebalancing(true),
                     collateral_a: 1_000_000_000,
                     collateral_b: 1_000_000,
                     borrow_a: 2_000_000_000,
@@ -252,7 +254,7 @@ mod tests {
                 .unwrap();
 
             ctx.send_transaction(
-                rebalance_tuna_lp_position_fusion_instructions(&ctx.rpc, &ctx.signer.pubkey(), &ix.position_mint)
+                rebalance_tuna_lp_position_fusion_instructions(&ctx.rpc, &ctx.signer.pubkey(), &ix.position_mint.unwrap())
                     .unwrap()
                     .instructions,
             )
@@ -295,7 +297,7 @@ mod tests {
                     tick_upper_index: actual_tick_index + pool.data.tick_spacing as i32 * 3,
                     lower_limit_order_sqrt_price: 0,
                     upper_limit_order_sqrt_price: 0,
-                    flags: 0,
+                    flags: TunaPositionFlags::default(),
                     collateral_a: 1_000_000_000,
                     collateral_b: 0,
                     borrow_a: 4_000_000_000,
@@ -314,22 +316,25 @@ mod tests {
                 .await
                 .unwrap();
 
-            let tuna_position = fetch_tuna_lp_position(&ctx.rpc, &get_tuna_liquidity_position_address(&ix.position_mint).0).unwrap();
+            let tuna_position = fetch_tuna_lp_position(&ctx.rpc, &get_tuna_liquidity_position_address(&ix.position_mint.unwrap()).0).unwrap();
             let vaults = fetch_all_vault(&ctx.rpc, &[test_market.vault_a, test_market.vault_b]).unwrap();
 
-            ctx.send_transaction(liquidate_tuna_lp_position_fusion_instructions(
-                &ctx.signer.pubkey(),
-                &tuna_position.data,
-                &tuna_config.data,
-                &vaults[0].address,
-                &vaults[0].data,
-                &vaults[1].address,
-                &vaults[1].data,
-                &pool.data,
-                &test_market.token_program_a,
-                &test_market.token_program_b,
-                None,
-            ))
+            ctx.send_transaction(
+                liquidate_tuna_lp_position_fusion_instructions(
+                    &ctx.signer.pubkey(),
+                    &tuna_position.data,
+                    &tuna_config.data,
+                    &vaults[0].address,
+                    &vaults[0].data,
+                    &vaults[1].address,
+                    &vaults[1].data,
+                    &pool.data,
+                    &test_market.token_program_a,
+                    &test_market.token_program_b,
+                    None,
+                )
+                .instructions,
+            )
             .unwrap();
         });
     }
@@ -10,7 +10,7 @@ mod tests {
         get_tuna_config_address, get_tuna_liquidity_position_address, get_vault_address, increase_tuna_lp_position_orca_instructions,
         liquidate_tuna_lp_position_orca_instructions, open_and_increase_tuna_lp_position_orca_instructions, open_tuna_lp_position_orca_instruction,
         rebalance_tuna_lp_position_orca_instructions, CloseActiveTunaLpPositionArgs, DecreaseTunaLpPositionArgs, IncreaseTunaLpPositionArgs,
-        OpenAndIncreaseTunaLpPositionArgs, HUNDRED_PERCENT, LEVERAGE_ONE, TUNA_POSITION_FLAGS_ALLOW_REBALANCING,
+        OpenAndIncreaseTunaLpPositionArgs, TunaPositionFlags, HUNDRED_PERCENT, LEVERAGE_ONE,
     };
     use orca_whirlpools_client::fetch_whirlpool;
     use serial_test::serial;
@@ -87,7 +87,8 @@ mod tests {
                         max_swap_slippage: 0,
                     },
                 )
-                .unwrap(),
+                .unwrap()
+                .instructions,
             )
             .unwrap();
 
@@ -98,7 +99,8 @@ mod tests {
                     &position_mint.pubkey(),
                     DecreaseTunaLpPositionArgs::default(),
                 )
-                .unwrap(),
+                .unwrap()
+                .instructions,
             )
             .unwrap();
 
@@ -139,7 +141,7 @@ mod tests {
                     tick_upper_index: actual_tick_index + pool.data.tick_spacing as i32 * 5,
                     lower_limit_order_sqrt_price: 0,
                     upper_limit_order_sqrt_price: 0,
-                    flags: 0,
+                    flags: TunaPositionFlags::default(),
                     collateral_a: 1_000_000_000,
                     collateral_b: 100_000_000,
                     borrow_a: 1_000_000_000,
@@ -158,10 +160,11 @@ mod tests {
                 close_active_tuna_lp_position_orca_instructions(
                     &ctx.rpc,
                     &ctx.signer.pubkey(),
-                    &ix.position_mint,
+                    &ix.position_mint.unwrap(),
                     CloseActiveTunaLpPositionArgs::default(),
                 )
-                .unwrap(),
+                .unwrap()
+                .instructions,
             )
             .unwrap();
         });
@@ -191,7 +194,7 @@ mod tests {
                     tick_upper_index: actual_tick_index + pool.data.tick_spacing as i32 * 5,
                     lower_limit_order_sqrt_price: 0,
                     upper_limit_order_sqrt_price: 0,
-                    flags: TUNA_POSITION_FLAGS_ALLOW_REBALANCING,
+                    flags: TunaPositionFlags::default().with_allow_rebalancing(true),
                     collateral_a: 1_000_000_000,
                     collateral_b: 1_000_000,
                     borrow_a: 2_000_000_000,
@@ -211,7 +214,7 @@ mod tests {
                 .unwrap();
 
             ctx.send_transaction(
-                rebalance_tuna_lp_position_orca_instructions(&ctx.rpc, &ctx.signer.pubkey(), &ix.position_mint)
+                rebalance_tuna_lp_position_orca_instructions(&ctx.rpc, &ctx.signer.pubkey(), &ix.position_mint.unwrap())
                     .unwrap()
                     .instructions,
             )
@@ -244,7 +247,7 @@ mod tests {
                     tick_upper_index: actual_tick_index + pool.data.tick_spacing as i32 * 3,
                     lower_limit_order_sqrt_price: 0,
                     upper_limit_order_sqrt_price: 0,
-                    flags: 0,
+                    flags: TunaPositionFlags::default(),
                     collateral_a: 1_000_000_000,
                     collateral_b: 0,
                     borrow_a: 4_000_000_000,
@@ -263,7 +266,7 @@ mod tests {
                 .await
                 .unwrap();
 
-            let tuna_position = fetch_tuna_lp_position(&ctx.rpc, &get_tuna_liquidity_position_address(&ix.position_mint).0).unwrap();
+            let tuna_position = fetch_tuna_lp_position(&ctx.rpc, &get_tuna_liquidity_position_address(&ix.position_mint.unwrap()).0).unwrap();
             let vaults = fetch_all_vault(
                 &ctx.rpc,
                 &[
@@ -273,19 +276,22 @@ mod tests {
             )
             .unwrap();
 
-            ctx.send_transaction(liquidate_tuna_lp_position_orca_instructions(
-                &ctx.signer.pubkey(),
-                &tuna_position.data,
-                &tuna_config.data,
-                &vaults[0].address,
-                &vaults[0].data,
-                &vaults[1].address,
-                &vaults[1].data,
-                &pool.data,
-                &test_market.token_program_a,
-                &test_market.token_program_b,
-                None,
-            ))
+            ctx.send_transaction(
+                liquidate_tuna_lp_position_orca_instructions(
+                    &ctx.signer.pubkey(),
+                    &tuna_position.data,
+                    &tuna_config.data,
+                    &vaults[0].address,
+                    &vaults[0].data,
+                    &vaults[1].address,
+                    &vaults[1].data,
+                    &pool.data,
+                    &test_market.token_program_a,
+                    &test_market.token_program_b,
+                    None,
+                )
+                .instructions,
+            )
             .unwrap();
         });
     }
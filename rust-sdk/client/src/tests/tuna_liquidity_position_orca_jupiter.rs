@@ -8,8 +8,8 @@ mod tests {
     use crate::utils::orca::get_swap_tick_arrays;
     use crate::{
         get_tuna_config_address, get_tuna_liquidity_position_address, liquidate_tuna_lp_position_orca_jupiter_instructions,
-        open_and_increase_tuna_lp_position_orca_instructions, LiquidateTunaLpPositionJupiterArgs, OpenAndIncreaseTunaLpPositionArgs, HUNDRED_PERCENT,
-        JUPITER_EVENT_AUTHORITY, JUPITER_PROGRAM_ID, LEVERAGE_ONE,
+        open_and_increase_tuna_lp_position_orca_instructions, LiquidateTunaLpPositionJupiterArgs, OpenAndIncreaseTunaLpPositionArgs,
+        TunaPositionFlags, HUNDRED_PERCENT, JUPITER_EVENT_AUTHORITY, JUPITER_PROGRAM_ID, LEVERAGE_ONE,
     };
     use jupiter_solana_client::instructions::{RouteV2, RouteV2InstructionArgs};
     use jupiter_solana_client::types::{AccountsType, RemainingAccountsInfo, RemainingAccountsSlice, RoutePlanStepV2, Swap};
@@ -110,7 +110,7 @@ mod tests {
                     tick_upper_index: actual_tick_index + pool.data.tick_spacing as i32 * 3,
                     lower_limit_order_sqrt_price: 0,
                     upper_limit_order_sqrt_price: 0,
-                    flags: 0,
+                    flags: TunaPositionFlags::default(),
                     collateral_a: 1_000_000_000,
                     collateral_b: 0,
                     borrow_a: 4_000_000_000,
@@ -129,7 +129,7 @@ mod tests {
                 .await
                 .unwrap();
 
-            let tuna_position = fetch_tuna_lp_position(&ctx.rpc, &get_tuna_liquidity_position_address(&ix.position_mint).0).unwrap();
+            let tuna_position = fetch_tuna_lp_position(&ctx.rpc, &get_tuna_liquidity_position_address(&ix.position_mint.unwrap()).0).unwrap();
             let vaults = fetch_all_vault(&ctx.rpc, &[test_market.vault_a, test_market.vault_b]).unwrap();
 
             let tuna_position_ata_a =
@@ -182,24 +182,27 @@ mod tests {
 
             route_ix.accounts[0].is_signer = false;
 
-            ctx.send_transaction(liquidate_tuna_lp_position_orca_jupiter_instructions(
-                &ctx.signer.pubkey(),
-                &tuna_position.data,
-                &tuna_config.data,
-                &vaults[0].address,
-                &vaults[0].data,
-                &vaults[1].address,
-                &vaults[1].data,
-                &pool.data,
-                &test_market.token_program_a,
-                &test_market.token_program_b,
-                route_ix.accounts,
-                vec![],
-                LiquidateTunaLpPositionJupiterArgs {
-                    decrease_percent: HUNDRED_PERCENT,
-                    jupiter_route_data: route_ix.data,
-                },
-            ))
+            ctx.send_transaction(
+                liquidate_tuna_lp_position_orca_jupiter_instructions(
+                    &ctx.signer.pubkey(),
+                    &tuna_position.data,
+                    &tuna_config.data,
+                    &vaults[0].address,
+                    &vaults[0].data,
+                    &vaults[1].address,
+                    &vaults[1].data,
+                    &pool.data,
+                    &test_market.token_program_a,
+                    &test_market.token_program_b,
+                    route_ix.accounts,
+                    vec![],
+                    LiquidateTunaLpPositionJupiterArgs {
+                        decrease_percent: HUNDRED_PERCENT,
+                        jupiter_route_data: route_ix.data,
+                    },
+                )
+                .instructions,
+            )
             .unwrap();
         });
     }
@@ -0,0 +1,212 @@
+#[cfg(test)]
+mod tests {
+    use crate::tests::*;
+    use crate::{
+        increase_tuna_lp_position_fusion_instructions, increase_tuna_lp_position_orca_instructions, AccountSnapshot, IncreaseTunaLpPositionArgs,
+        TunaError, TunaPositionFlags, TUNA_POSITION_FLAGS_ALLOW_REBALANCING, TUNA_POSITION_FLAGS_AUTO_COMPOUND_YIELD,
+        TUNA_POSITION_FLAGS_AUTO_COMPOUND_YIELD_WITH_LEVERAGE, TUNA_POSITION_FLAGS_LOCKED, TUNA_POSITION_FLAGS_LOWER_LIMIT_ORDER_SWAP_TO_TOKEN_A,
+        TUNA_POSITION_FLAGS_LOWER_LIMIT_ORDER_SWAP_TO_TOKEN_B, TUNA_POSITION_FLAGS_MASK, TUNA_POSITION_FLAGS_UPPER_LIMIT_ORDER_SWAP_TO_TOKEN_A,
+        TUNA_POSITION_FLAGS_UPPER_LIMIT_ORDER_SWAP_TO_TOKEN_B,
+    };
+    use defituna_core::{TOKEN_A, TOKEN_B};
+    use solana_pubkey::Pubkey;
+
+    #[test]
+    fn test_from_bits_rejects_unknown_bits() {
+        assert_eq!(TunaPositionFlags::from_bits(0).unwrap().bits(), 0);
+        assert_eq!(TunaPositionFlags::from_bits(TUNA_POSITION_FLAGS_MASK).unwrap().bits(), TUNA_POSITION_FLAGS_MASK);
+
+        for bits in [TUNA_POSITION_FLAGS_MASK + 1, 1 << 31, u32::MAX, TUNA_POSITION_FLAGS_LOCKED | 1 << 12] {
+            assert_eq!(TunaPositionFlags::from_bits(bits), Err(TunaError::InvalidInstructionArguments));
+            assert_eq!(TunaPositionFlags::try_from(bits), Err(TunaError::InvalidInstructionArguments));
+        }
+    }
+
+    #[test]
+    fn test_from_bits_retain_keeps_unknown_bits() {
+        let bits = TUNA_POSITION_FLAGS_LOCKED | 1 << 31;
+        let flags = TunaPositionFlags::from_bits_retain(bits);
+        assert_eq!(flags.bits(), bits);
+        assert!(flags.locked());
+        assert_eq!(flags.validate(false), Ok(()));
+        assert_eq!(flags.validate(true), Err(TunaError::LeveragedLockedPositionNotAllowed));
+    }
+
+    #[test]
+    fn test_u32_conversions() {
+        let bits = TUNA_POSITION_FLAGS_AUTO_COMPOUND_YIELD | TUNA_POSITION_FLAGS_UPPER_LIMIT_ORDER_SWAP_TO_TOKEN_B;
+        let flags = TunaPositionFlags::try_from(bits).unwrap();
+        assert_eq!(u32::from(flags), bits);
+        assert_eq!(flags.to_string(), "0b00011000");
+    }
+
+    #[test]
+    fn test_boolean_accessors() {
+        let flags = TunaPositionFlags::default();
+        assert!(!flags.auto_compound_yield());
+        assert!(!flags.auto_compound_yield_with_leverage());
+        assert!(!flags.allow_rebalancing());
+        assert!(!flags.locked());
+
+        let flags = flags.with_auto_compound_yield(true);
+        assert!(flags.auto_compound_yield());
+        assert_eq!(flags.bits(), TUNA_POSITION_FLAGS_AUTO_COMPOUND_YIELD);
+
+        let flags = flags.with_auto_compound_yield_with_leverage(true);
+        assert!(flags.auto_compound_yield_with_leverage());
+
+        let flags = flags.with_allow_rebalancing(true);
+        assert!(flags.allow_rebalancing());
+
+        let flags = flags.with_locked(true);
+        assert!(flags.locked());
+        assert_eq!(
+            flags.bits(),
+            TUNA_POSITION_FLAGS_AUTO_COMPOUND_YIELD
+                | TUNA_POSITION_FLAGS_AUTO_COMPOUND_YIELD_WITH_LEVERAGE
+                | TUNA_POSITION_FLAGS_ALLOW_REBALANCING
+                | TUNA_POSITION_FLAGS_LOCKED
+        );
+
+        let flags = flags
+            .with_auto_compound_yield(false)
+            .with_auto_compound_yield_with_leverage(false)
+            .with_allow_rebalancing(false)
+            .with_locked(false);
+        assert_eq!(flags.bits(), 0);
+    }
+
+    #[test]
+    fn test_limit_order_swap_to_token_accessors() {
+        let flags = TunaPositionFlags::default();
+        assert_eq!(flags.lower_limit_order_swap_to_token(), None);
+        assert_eq!(flags.upper_limit_order_swap_to_token(), None);
+
+        let flags = flags.with_lower_limit_order_swap_to_token(Some(TOKEN_A));
+        assert_eq!(flags.lower_limit_order_swap_to_token(), Some(TOKEN_A));
+        assert_eq!(flags.upper_limit_order_swap_to_token(), None);
+        assert_eq!(flags.bits(), TUNA_POSITION_FLAGS_LOWER_LIMIT_ORDER_SWAP_TO_TOKEN_A);
+
+        // Switching the token clears the previous one.
+        let flags = flags.with_lower_limit_order_swap_to_token(Some(TOKEN_B));
+        assert_eq!(flags.lower_limit_order_swap_to_token(), Some(TOKEN_B));
+        assert_eq!(flags.bits(), TUNA_POSITION_FLAGS_LOWER_LIMIT_ORDER_SWAP_TO_TOKEN_B);
+
+        let flags = flags.with_upper_limit_order_swap_to_token(Some(TOKEN_A));
+        assert_eq!(flags.upper_limit_order_swap_to_token(), Some(TOKEN_A));
+        let flags = flags.with_upper_limit_order_swap_to_token(Some(TOKEN_B));
+        assert_eq!(flags.upper_limit_order_swap_to_token(), Some(TOKEN_B));
+        assert_eq!(
+            flags.bits(),
+            TUNA_POSITION_FLAGS_LOWER_LIMIT_ORDER_SWAP_TO_TOKEN_B | TUNA_POSITION_FLAGS_UPPER_LIMIT_ORDER_SWAP_TO_TOKEN_B
+        );
+
+        let flags = flags
+            .with_lower_limit_order_swap_to_token(None)
+            .with_upper_limit_order_swap_to_token(None);
+        assert_eq!(flags.lower_limit_order_swap_to_token(), None);
+        assert_eq!(flags.upper_limit_order_swap_to_token(), None);
+        assert_eq!(flags.bits(), 0);
+    }
+
+    #[test]
+    fn test_validate() {
+        let flags = TunaPositionFlags::default()
+            .with_lower_limit_order_swap_to_token(Some(TOKEN_A))
+            .with_upper_limit_order_swap_to_token(Some(TOKEN_B))
+            .with_auto_compound_yield(true);
+        assert_eq!(flags.validate(true), Ok(()));
+
+        let both_lower = TunaPositionFlags::from_bits(
+            TUNA_POSITION_FLAGS_LOWER_LIMIT_ORDER_SWAP_TO_TOKEN_A | TUNA_POSITION_FLAGS_LOWER_LIMIT_ORDER_SWAP_TO_TOKEN_B,
+        )
+        .unwrap();
+        assert_eq!(both_lower.validate(false), Err(TunaError::InvalidInstructionArguments));
+
+        let both_upper = TunaPositionFlags::from_bits(
+            TUNA_POSITION_FLAGS_UPPER_LIMIT_ORDER_SWAP_TO_TOKEN_A | TUNA_POSITION_FLAGS_UPPER_LIMIT_ORDER_SWAP_TO_TOKEN_B,
+        )
+        .unwrap();
+        assert_eq!(both_upper.validate(false), Err(TunaError::InvalidInstructionArguments));
+
+        let locked = TunaPositionFlags::default().with_locked(true);
+        assert_eq!(locked.validate(false), Ok(()));
+        assert_eq!(locked.validate(true), Err(TunaError::LeveragedLockedPositionNotAllowed));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde() {
+        let flags = TunaPositionFlags::default().with_locked(true).with_allow_rebalancing(true);
+        let json = serde_json::to_string(&flags).unwrap();
+        assert_eq!(json, (TUNA_POSITION_FLAGS_LOCKED | TUNA_POSITION_FLAGS_ALLOW_REBALANCING).to_string());
+        assert_eq!(serde_json::from_str::<TunaPositionFlags>(&json).unwrap(), flags);
+
+        assert!(serde_json::from_str::<TunaPositionFlags>(&(TUNA_POSITION_FLAGS_MASK + 1).to_string()).is_err());
+    }
+
+    fn locked_position_snapshot(authority: &Pubkey, position_mint: &Pubkey, loan_shares_a: u64) -> AccountSnapshot {
+        position_snapshot(authority, position_mint, TunaPositionFlags::default().with_locked(true).bits(), loan_shares_a)
+    }
+
+    fn position_snapshot(authority: &Pubkey, position_mint: &Pubkey, flags: u32, loan_shares_a: u64) -> AccountSnapshot {
+        let mut tuna_position = test_tuna_lp_position(authority, &Pubkey::new_unique(), &Pubkey::new_unique(), &Pubkey::new_unique(), position_mint);
+        tuna_position.flags = flags;
+        tuna_position.loan_shares_a = loan_shares_a;
+        AccountSnapshot::from_iter([tuna_lp_position_account(&tuna_position)])
+    }
+
+    #[test]
+    fn test_increase_rejects_borrowing_for_locked_position() {
+        let authority = Pubkey::new_unique();
+        let position_mint = Pubkey::new_unique();
+        let snapshot = locked_position_snapshot(&authority, &position_mint, 0);
+
+        let args = || IncreaseTunaLpPositionArgs {
+            collateral_a: 1_000_000,
+            borrow_a: 1_000_000,
+            ..Default::default()
+        };
+
+        let err = increase_tuna_lp_position_orca_instructions(&snapshot, &authority, &position_mint, args()).unwrap_err();
+        assert_eq!(err.downcast_ref::<TunaError>(), Some(&TunaError::LeveragedLockedPositionNotAllowed));
+
+        let err = increase_tuna_lp_position_fusion_instructions(&snapshot, &authority, &position_mint, args()).unwrap_err();
+        assert_eq!(err.downcast_ref::<TunaError>(), Some(&TunaError::LeveragedLockedPositionNotAllowed));
+    }
+
+    #[test]
+    fn test_increase_rejects_locked_position_with_debt() {
+        let authority = Pubkey::new_unique();
+        let position_mint = Pubkey::new_unique();
+        let snapshot = locked_position_snapshot(&authority, &position_mint, 1_000);
+
+        let args = IncreaseTunaLpPositionArgs {
+            collateral_a: 1_000_000,
+            ..Default::default()
+        };
+
+        let err = increase_tuna_lp_position_orca_instructions(&snapshot, &authority, &position_mint, args).unwrap_err();
+        assert_eq!(err.downcast_ref::<TunaError>(), Some(&TunaError::LeveragedLockedPositionNotAllowed));
+    }
+
+    #[test]
+    fn test_increase_keeps_unknown_on_chain_flag_bits() {
+        let authority = Pubkey::new_unique();
+        let position_mint = Pubkey::new_unique();
+
+        // A flag unknown to the client doesn't fail the validation of the position, the known flags are still checked.
+        let snapshot = position_snapshot(&authority, &position_mint, TUNA_POSITION_FLAGS_LOCKED | 1 << 31, 0);
+        let args = || IncreaseTunaLpPositionArgs {
+            collateral_a: 1_000_000,
+            borrow_a: 1_000_000,
+            ..Default::default()
+        };
+
+        let err = increase_tuna_lp_position_orca_instructions(&snapshot, &authority, &position_mint, args()).unwrap_err();
+        assert_eq!(err.downcast_ref::<TunaError>(), Some(&TunaError::LeveragedLockedPositionNotAllowed));
+
+        let err = increase_tuna_lp_position_fusion_instructions(&snapshot, &authority, &position_mint, args()).unwrap_err();
+        assert_eq!(err.downcast_ref::<TunaError>(), Some(&TunaError::LeveragedLockedPositionNotAllowed));
+    }
+}
@@ -66,7 +66,8 @@ mod tests {
                     collateral_token: PoolToken::A,
                 },
             )
-            .unwrap();
+            .unwrap()
+            .instructions;
 
             ctx.send_transaction(instructions).unwrap();
 
@@ -83,7 +84,8 @@ mod tests {
                         required_swap_amount: 0,
                     },
                 )
-                .unwrap(),
+                .unwrap()
+                .instructions,
             )
             .unwrap();
 
@@ -100,12 +102,17 @@ mod tests {
                         required_swap_amount: 0,
                     },
                 )
-                .unwrap(),
+                .unwrap()
+                .instructions,
             )
             .unwrap();
 
-            ctx.send_transaction(close_tuna_spot_position_instructions(&ctx.rpc, &ctx.signer.pubkey(), &test_market.pool).unwrap())
-                .unwrap();
+            ctx.send_transaction(
+                close_tuna_spot_position_instructions(&ctx.rpc, &ctx.signer.pubkey(), &test_market.pool)
+                    .unwrap()
+                    .instructions,
+            )
+            .unwrap();
         });
     }
 
@@ -142,7 +149,8 @@ mod tests {
                     collateral_token: PoolToken::A,
                 },
             )
-            .unwrap();
+            .unwrap()
+            .instructions;
 
             let mut increase_ixs = modify_tuna_spot_position_fusion_instructions(
                 &ctx.rpc,
@@ -156,7 +164,8 @@ mod tests {
                     required_swap_amount: 0,
                 },
             )
-            .unwrap();
+            .unwrap()
+            .instructions;
             instructions.append(&mut increase_ixs);
 
             ctx.send_transaction(instructions).unwrap();
@@ -169,19 +178,22 @@ mod tests {
             let tuna_position = fetch_tuna_spot_position(&ctx.rpc, &tuna_position_address).unwrap();
             let vaults = fetch_all_vault(&ctx.rpc, &[test_market.vault_a, test_market.vault_b]).unwrap();
 
-            ctx.send_transaction(liquidate_tuna_spot_position_fusion_instructions(
-                &ctx.signer.pubkey(),
-                &tuna_position.data,
-                &tuna_config.data,
-                &vaults[0].address,
-                &vaults[0].data,
-                &vaults[1].address,
-                &vaults[1].data,
-                &pool.data,
-                &test_market.token_program_a,
-                &test_market.token_program_b,
-                None,
-            ))
+            ctx.send_transaction(
+                liquidate_tuna_spot_position_fusion_instructions(
+                    &ctx.signer.pubkey(),
+                    &tuna_position.data,
+                    &tuna_config.data,
+                    &vaults[0].address,
+                    &vaults[0].data,
+                    &vaults[1].address,
+                    &vaults[1].data,
+                    &pool.data,
+                    &test_market.token_program_a,
+                    &test_market.token_program_b,
+                    None,
+                )
+                .instructions,
+            )
             .unwrap();
         });
     }
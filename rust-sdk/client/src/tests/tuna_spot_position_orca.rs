@@ -56,7 +56,8 @@ mod tests {
                     collateral_token: PoolToken::A,
                 },
             )
-            .unwrap();
+            .unwrap()
+            .instructions;
 
             ctx.send_transaction(instructions).unwrap();
 
@@ -73,7 +74,8 @@ mod tests {
                         required_swap_amount: 0,
                     },
                 )
-                .unwrap(),
+                .unwrap()
+                .instructions,
             )
             .unwrap();
 
@@ -90,12 +92,17 @@ mod tests {
                         required_swap_amount: 0,
                     },
                 )
-                .unwrap(),
+                .unwrap()
+                .instructions,
             )
             .unwrap();
 
-            ctx.send_transaction(close_tuna_spot_position_instructions(&ctx.rpc, &ctx.signer.pubkey(), &test_market.pool).unwrap())
-                .unwrap();
+            ctx.send_transaction(
+                close_tuna_spot_position_instructions(&ctx.rpc, &ctx.signer.pubkey(), &test_market.pool)
+                    .unwrap()
+                    .instructions,
+            )
+            .unwrap();
         });
     }
 
@@ -122,7 +129,8 @@ mod tests {
                     collateral_token: PoolToken::A,
                 },
             )
-            .unwrap();
+            .unwrap()
+            .instructions;
 
             let mut increase_ixs = modify_tuna_spot_position_orca_instructions(
                 &ctx.rpc,
@@ -136,7 +144,8 @@ mod tests {
                     required_swap_amount: 0,
                 },
             )
-            .unwrap();
+            .unwrap()
+            .instructions;
             instructions.append(&mut increase_ixs);
 
             ctx.send_transaction(instructions).unwrap();
@@ -156,19 +165,22 @@ mod tests {
             )
             .unwrap();
 
-            ctx.send_transaction(liquidate_tuna_spot_position_orca_instructions(
-                &ctx.signer.pubkey(),
-                &tuna_position.data,
-                &tuna_config.data,
-                &vaults[0].address,
-                &vaults[0].data,
-                &vaults[1].address,
-                &vaults[1].data,
-                &pool.data,
-                &test_market.token_program_a,
-                &test_market.token_program_b,
-                None,
-            ))
+            ctx.send_transaction(
+                liquidate_tuna_spot_position_orca_instructions(
+                    &ctx.signer.pubkey(),
+                    &tuna_position.data,
+                    &tuna_config.data,
+                    &vaults[0].address,
+                    &vaults[0].data,
+                    &vaults[1].address,
+                    &vaults[1].data,
+                    &pool.data,
+                    &test_market.token_program_a,
+                    &test_market.token_program_b,
+                    None,
+                )
+                .instructions,
+            )
             .unwrap();
         });
     }
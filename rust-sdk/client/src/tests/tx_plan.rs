@@ -0,0 +1,240 @@
+#[cfg(test)]
+mod tests {
+    use crate::accounts::TunaLpPosition;
+    use crate::tests::*;
+    use crate::types::MarketMaker;
+    use crate::utils::POSITION_NFT_TOKEN_ACCOUNT_LEN;
+    use crate::{
+        close_active_tuna_lp_position_orca_instructions, deposit_instructions, get_tuna_liquidity_position_address, get_vault_address,
+        open_and_increase_tuna_lp_position_orca_instructions, AccountSnapshot, CloseActiveTunaLpPositionArgs, OpenAndIncreaseTunaLpPositionArgs,
+    };
+    use orca_whirlpools_client::{get_position_address, Position};
+    use orca_whirlpools_core::tick_index_to_sqrt_price;
+    use solana_pubkey::Pubkey;
+    use solana_signer::Signer;
+    use solana_sysvar::clock::Clock;
+    use solana_sysvar::rent::Rent;
+    use spl_associated_token_account::get_associated_token_address_with_program_id;
+    use spl_token::solana_program::program_pack::Pack;
+    use spl_token_2022::extension::ExtensionType;
+
+    const TICK_SPACING: u16 = 64;
+
+    struct TestPool {
+        authority: Pubkey,
+        fee_recipient: Pubkey,
+        whirlpool_address: Pubkey,
+        mint_a: Pubkey,
+        mint_b: Pubkey,
+        snapshot: AccountSnapshot,
+    }
+
+    /// Sets up a whirlpool of a SOL and a Token-2022 mint.
+    fn setup() -> TestPool {
+        let authority = Pubkey::new_unique();
+        let fee_recipient = Pubkey::new_unique();
+        let mint_a = spl_token::native_mint::ID;
+        let mint_b = Pubkey::new_unique();
+        let whirlpool_address = Pubkey::new_unique();
+
+        let whirlpool = test_whirlpool(&mint_a, &mint_b, TICK_SPACING, tick_index_to_sqrt_price(0));
+        let market = test_market(&whirlpool_address, MarketMaker::Orca, &get_vault_address(&mint_a, None).0, &get_vault_address(&mint_b, None).0);
+
+        let mut mint_b_account = mint_account(6);
+        mint_b_account.owner = spl_token_2022::ID;
+
+        let mut snapshot = AccountSnapshot::from_iter([
+            tuna_config_account(&test_tuna_config(&fee_recipient)),
+            (whirlpool_address, whirlpool_account(&whirlpool)),
+            orca_tick_array_account(&whirlpool_address, -128, TICK_SPACING),
+            orca_tick_array_account(&whirlpool_address, 128, TICK_SPACING),
+            market_account(&market),
+            vault_account(&test_vault(&mint_a, 0, 0, 0)),
+            vault_account(&test_vault(&mint_b, 0, 0, 0)),
+            (mint_a, mint_account(9)),
+            (mint_b, mint_b_account),
+        ]);
+        snapshot.insert_sysvar(&Clock::default()).unwrap();
+        snapshot.insert_sysvar(&Rent::default()).unwrap();
+
+        TestPool {
+            authority,
+            fee_recipient,
+            whirlpool_address,
+            mint_a,
+            mint_b,
+            snapshot,
+        }
+    }
+
+    #[test]
+    fn test_open_lp_position_accounts_and_rent() {
+        let test = setup();
+        let rent = Rent::default();
+
+        let tx_plan = open_and_increase_tuna_lp_position_orca_instructions(
+            &test.snapshot,
+            &test.authority,
+            &test.whirlpool_address,
+            OpenAndIncreaseTunaLpPositionArgs {
+                tick_lower_index: -128,
+                tick_upper_index: 128,
+                collateral_a: 1_000_000,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        let position_mint = tx_plan.position_mint.unwrap();
+        assert_eq!(tx_plan.additional_signers.len(), 1);
+        assert_eq!(tx_plan.additional_signers[0].pubkey(), position_mint);
+        let tuna_position_address = get_tuna_liquidity_position_address(&position_mint).0;
+        let authority_ata_a = get_associated_token_address_with_program_id(&test.authority, &test.mint_a, &spl_token::ID);
+        let authority_ata_b = get_associated_token_address_with_program_id(&test.authority, &test.mint_b, &spl_token_2022::ID);
+
+        assert_eq!(
+            tx_plan.created_accounts,
+            vec![
+                position_mint,
+                tuna_position_address,
+                get_position_address(&position_mint).unwrap().0,
+                get_associated_token_address_with_program_id(&tuna_position_address, &position_mint, &spl_token_2022::ID),
+                get_associated_token_address_with_program_id(&tuna_position_address, &test.mint_a, &spl_token::ID),
+                get_associated_token_address_with_program_id(&tuna_position_address, &test.mint_b, &spl_token_2022::ID),
+                authority_ata_a,
+                authority_ata_b,
+                get_associated_token_address_with_program_id(&test.fee_recipient, &test.mint_a, &spl_token::ID),
+                get_associated_token_address_with_program_id(&test.fee_recipient, &test.mint_b, &spl_token_2022::ID),
+            ]
+        );
+
+        // The WSOL account used to deposit the collateral is closed afterward.
+        assert_eq!(tx_plan.closed_accounts, vec![authority_ata_a]);
+
+        // The tick arrays already exist.
+        assert_eq!(tx_plan.non_refundable_rent, 0);
+
+        // The market has no address lookup table.
+        assert!(tx_plan.address_lookup_table_addresses.is_empty());
+
+        let token_2022_account_len =
+            ExtensionType::try_calculate_account_len::<spl_token_2022::state::Account>(&[ExtensionType::ImmutableOwner]).unwrap();
+        assert_eq!(POSITION_NFT_TOKEN_ACCOUNT_LEN, token_2022_account_len);
+        assert_eq!(
+            tx_plan.refundable_rent,
+            rent.minimum_balance(TunaLpPosition::LEN)
+                + rent.minimum_balance(Position::LEN)
+                + rent.minimum_balance(POSITION_NFT_TOKEN_ACCOUNT_LEN)
+                + rent.minimum_balance(spl_token::state::Account::LEN)
+                + rent.minimum_balance(token_2022_account_len)
+        );
+    }
+
+    #[test]
+    fn test_close_lp_position_accounts() {
+        let mut test = setup();
+        let position_mint = Pubkey::new_unique();
+
+        test.snapshot.extend([
+            tuna_lp_position_account(&test_tuna_lp_position(&test.authority, &test.whirlpool_address, &test.mint_a, &test.mint_b, &position_mint)),
+            orca_position_account(&test_orca_position(&test.whirlpool_address, &position_mint, -128, 128)),
+        ]);
+
+        let tx_plan = close_active_tuna_lp_position_orca_instructions(
+            &test.snapshot,
+            &test.authority,
+            &position_mint,
+            CloseActiveTunaLpPositionArgs::default(),
+        )
+        .unwrap();
+
+        let tuna_position_address = get_tuna_liquidity_position_address(&position_mint).0;
+        let authority_ata_a = get_associated_token_address_with_program_id(&test.authority, &test.mint_a, &spl_token::ID);
+
+        assert_eq!(
+            tx_plan.closed_accounts,
+            vec![
+                tuna_position_address,
+                get_position_address(&position_mint).unwrap().0,
+                position_mint,
+                get_associated_token_address_with_program_id(&tuna_position_address, &position_mint, &spl_token_2022::ID),
+                get_associated_token_address_with_program_id(&tuna_position_address, &test.mint_a, &spl_token::ID),
+                get_associated_token_address_with_program_id(&tuna_position_address, &test.mint_b, &spl_token_2022::ID),
+                authority_ata_a,
+            ]
+        );
+        assert_eq!(
+            tx_plan.created_accounts,
+            vec![
+                authority_ata_a,
+                get_associated_token_address_with_program_id(&test.authority, &test.mint_b, &spl_token_2022::ID),
+            ]
+        );
+        assert_eq!(tx_plan.refundable_rent, 0);
+    }
+
+    #[test]
+    fn test_instructions_into_plan_reports_token_accounts() {
+        let test = setup();
+
+        // Builders returning the instructions only report the token accounts they create and close.
+        let tx_plan = deposit_instructions(&test.snapshot, &test.authority, &test.mint_a, None, 1_000_000).unwrap();
+        let authority_ata_a = get_associated_token_address_with_program_id(&test.authority, &test.mint_a, &spl_token::ID);
+        assert_eq!(tx_plan.created_accounts, vec![authority_ata_a]);
+        assert_eq!(tx_plan.closed_accounts, vec![authority_ata_a]);
+
+        let tx_plan = deposit_instructions(&test.snapshot, &test.authority, &test.mint_b, None, 1_000_000).unwrap();
+        assert_eq!(
+            tx_plan.created_accounts,
+            vec![get_associated_token_address_with_program_id(
+                &test.authority,
+                &test.mint_b,
+                &spl_token_2022::ID
+            )]
+        );
+        assert!(tx_plan.closed_accounts.is_empty());
+    }
+
+    #[test]
+    fn test_market_lookup_table_is_requested() {
+        let mut test = setup();
+        let position_mint = Pubkey::new_unique();
+        let market_lookup_table = Pubkey::new_unique();
+
+        let mut market = test_market(
+            &test.whirlpool_address,
+            MarketMaker::Orca,
+            &get_vault_address(&test.mint_a, None).0,
+            &get_vault_address(&test.mint_b, None).0,
+        );
+        market.address_lookup_table = market_lookup_table;
+        test.snapshot.extend([
+            market_account(&market),
+            tuna_lp_position_account(&test_tuna_lp_position(&test.authority, &test.whirlpool_address, &test.mint_a, &test.mint_b, &position_mint)),
+            orca_position_account(&test_orca_position(&test.whirlpool_address, &position_mint, -128, 128)),
+        ]);
+
+        let tx_plan = open_and_increase_tuna_lp_position_orca_instructions(
+            &test.snapshot,
+            &test.authority,
+            &test.whirlpool_address,
+            OpenAndIncreaseTunaLpPositionArgs {
+                tick_lower_index: -128,
+                tick_upper_index: 128,
+                collateral_a: 1_000_000,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(tx_plan.address_lookup_table_addresses, vec![market_lookup_table]);
+
+        let tx_plan = close_active_tuna_lp_position_orca_instructions(
+            &test.snapshot,
+            &test.authority,
+            &position_mint,
+            CloseActiveTunaLpPositionArgs::default(),
+        )
+        .unwrap();
+        assert_eq!(tx_plan.address_lookup_table_addresses, vec![market_lookup_table]);
+    }
+}
@@ -0,0 +1,181 @@
+#[cfg(test)]
+mod tests {
+    use crate::tests::*;
+    use crate::utils::{create_versioned_transaction, VersionedTransactionConfig};
+    use crate::{AccountSnapshot, TxPlan};
+    use solana_compute_budget_interface::ComputeBudgetInstruction;
+    use solana_hash::Hash;
+    use solana_instruction::{AccountMeta, Instruction};
+    use solana_keypair::Keypair;
+    use solana_pubkey::Pubkey;
+    use solana_signature::Signature;
+    use solana_signer::Signer;
+    use solana_transaction::versioned::VersionedTransaction;
+
+    fn test_instruction(accounts: Vec<AccountMeta>) -> Instruction {
+        Instruction {
+            program_id: Pubkey::new_unique(),
+            accounts,
+            data: vec![1, 2, 3],
+        }
+    }
+
+    /// Returns the index of the signer in the message, checking it's a required signer.
+    fn signer_index(transaction: &VersionedTransaction, signer: &Pubkey) -> usize {
+        let num_required_signatures = transaction.message.header().num_required_signatures as usize;
+        transaction.message.static_account_keys()[..num_required_signatures]
+            .iter()
+            .position(|address| address == signer)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_additional_signers_are_placed_at_their_signer_index() {
+        let payer = Pubkey::new_unique();
+        let authority = Pubkey::new_unique();
+        let position_mint = Keypair::new();
+        let market_maker = Keypair::new();
+
+        // The keypairs are listed in a different order than their accounts appear in the instructions.
+        let tx_plan = TxPlan {
+            instructions: vec![test_instruction(vec![
+                AccountMeta::new(payer, true),
+                AccountMeta::new_readonly(authority, true),
+                AccountMeta::new_readonly(market_maker.pubkey(), true),
+                AccountMeta::new(position_mint.pubkey(), true),
+                AccountMeta::new(Pubkey::new_unique(), false),
+            ])],
+            additional_signers: vec![position_mint.insecure_clone(), market_maker.insecure_clone()],
+            ..Default::default()
+        };
+
+        let transaction =
+            create_versioned_transaction(&AccountSnapshot::new(), &payer, &tx_plan, Hash::new_unique(), &VersionedTransactionConfig::default())
+                .unwrap();
+
+        assert_eq!(transaction.message.header().num_required_signatures, 4);
+        assert_eq!(transaction.signatures.len(), 4);
+        assert_eq!(transaction.message.static_account_keys()[0], payer);
+
+        let message_data = transaction.message.serialize();
+        for signer in [&position_mint, &market_maker] {
+            let signature = transaction.signatures[signer_index(&transaction, &signer.pubkey())];
+            assert!(signature.verify(signer.pubkey().as_ref(), &message_data));
+        }
+
+        // The payer and the authority sign later.
+        assert_eq!(transaction.signatures[0], Signature::default());
+        assert_eq!(transaction.signatures[signer_index(&transaction, &authority)], Signature::default());
+    }
+
+    #[test]
+    fn test_payer_can_be_an_additional_signer() {
+        let payer = Keypair::new();
+
+        let tx_plan = TxPlan {
+            instructions: vec![test_instruction(vec![AccountMeta::new(payer.pubkey(), true)])],
+            additional_signers: vec![payer.insecure_clone()],
+            ..Default::default()
+        };
+
+        let transaction = create_versioned_transaction(
+            &AccountSnapshot::new(),
+            &payer.pubkey(),
+            &tx_plan,
+            Hash::new_unique(),
+            &VersionedTransactionConfig::default(),
+        )
+        .unwrap();
+
+        assert_eq!(transaction.signatures.len(), 1);
+        assert!(transaction.signatures[0].verify(payer.pubkey().as_ref(), &transaction.message.serialize()));
+        assert!(transaction.verify_with_results().iter().all(|valid| *valid));
+    }
+
+    #[test]
+    fn test_additional_signer_must_sign_an_instruction() {
+        let payer = Pubkey::new_unique();
+        let keypair = Keypair::new();
+
+        // The keypair is referenced as a non-signer account only.
+        let tx_plan = TxPlan {
+            instructions: vec![test_instruction(vec![
+                AccountMeta::new(payer, true),
+                AccountMeta::new(keypair.pubkey(), false),
+            ])],
+            additional_signers: vec![keypair],
+            ..Default::default()
+        };
+
+        assert!(
+            create_versioned_transaction(&AccountSnapshot::new(), &payer, &tx_plan, Hash::new_unique(), &VersionedTransactionConfig::default())
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_compute_budget_and_lookup_tables() {
+        let payer = Pubkey::new_unique();
+        let market_lookup_table = Pubkey::new_unique();
+        let route_lookup_table = Pubkey::new_unique();
+        let market_accounts: Vec<Pubkey> = (0..3).map(|_| Pubkey::new_unique()).collect();
+        let route_accounts: Vec<Pubkey> = (0..2).map(|_| Pubkey::new_unique()).collect();
+
+        let snapshot = AccountSnapshot::from_iter([
+            (market_lookup_table, lookup_table_account(&payer, &market_accounts)),
+            (route_lookup_table, lookup_table_account(&payer, &route_accounts)),
+        ]);
+
+        let mut accounts = vec![AccountMeta::new(payer, true)];
+        accounts.extend(
+            market_accounts
+                .iter()
+                .chain(&route_accounts)
+                .map(|address| AccountMeta::new_readonly(*address, false)),
+        );
+        let tx_plan = TxPlan {
+            instructions: vec![test_instruction(accounts)],
+            address_lookup_table_addresses: vec![market_lookup_table, route_lookup_table],
+            ..Default::default()
+        };
+
+        // The route lookup table is requested twice, but used once.
+        let config = VersionedTransactionConfig {
+            address_lookup_table_addresses: vec![route_lookup_table],
+            compute_unit_limit: Some(400_000),
+            compute_unit_price: Some(1_000),
+        };
+        let transaction = create_versioned_transaction(&snapshot, &payer, &tx_plan, Hash::new_unique(), &config).unwrap();
+
+        let lookups = transaction.message.address_table_lookups().unwrap();
+        let lookup_table_keys: Vec<Pubkey> = lookups.iter().map(|lookup| lookup.account_key).collect();
+        assert_eq!(lookup_table_keys, vec![market_lookup_table, route_lookup_table]);
+        assert_eq!(lookups[0].readonly_indexes, vec![0, 1, 2]);
+        assert_eq!(lookups[1].readonly_indexes, vec![0, 1]);
+
+        // The lookup table accounts aren't in the static keys.
+        let static_keys = transaction.message.static_account_keys();
+        assert!(market_accounts
+            .iter()
+            .chain(&route_accounts)
+            .all(|address| !static_keys.contains(address)));
+
+        let instructions = transaction.message.instructions();
+        assert_eq!(instructions.len(), 3);
+        let compute_budget_data: Vec<Vec<u8>> = instructions[..2].iter().map(|ix| ix.data.clone()).collect();
+        assert_eq!(
+            compute_budget_data,
+            vec![
+                ComputeBudgetInstruction::set_compute_unit_limit(400_000).data,
+                ComputeBudgetInstruction::set_compute_unit_price(1_000).data,
+            ]
+        );
+
+        // The lookup tables must exist.
+        let config = VersionedTransactionConfig {
+            address_lookup_table_addresses: vec![Pubkey::new_unique()],
+            ..Default::default()
+        };
+        assert!(create_versioned_transaction(&snapshot, &payer, &tx_plan, Hash::new_unique(), &config).is_err());
+    }
+}
@@ -1,15 +1,14 @@
-use crate::accounts::{fetch_all_vault, fetch_market, fetch_tuna_lp_position};
+use crate::fetcher::{block_on, fetch_all_vault, fetch_fusion_pool, fetch_market, fetch_tuna_lp_position, AccountFetcher, SyncAccountFetcher};
 use crate::types::PoolToken;
 use crate::utils::get_create_ata_instructions;
 use crate::{
     close_tuna_lp_position_fusion_instruction, decrease_tuna_lp_position_fusion_instruction, get_market_address, get_tuna_liquidity_position_address,
-    DecreaseTunaLpPositionArgs, HUNDRED_PERCENT,
+    DecreaseTunaLpPositionArgs, TxPlan, HUNDRED_PERCENT,
 };
 use anyhow::{anyhow, Result};
-use fusionamm_client::fetch_fusion_pool;
-use solana_client::rpc_client::RpcClient;
-use solana_instruction::Instruction;
+use fusionamm_client::get_position_address;
 use solana_pubkey::Pubkey;
+use spl_associated_token_account::get_associated_token_address_with_program_id;
 
 #[derive(Default)]
 pub struct CloseActiveTunaLpPositionArgs {
@@ -20,26 +19,35 @@ pub struct CloseActiveTunaLpPositionArgs {
 }
 
 pub fn close_active_tuna_lp_position_fusion_instructions(
-    rpc: &RpcClient,
+    rpc: &impl SyncAccountFetcher,
     authority: &Pubkey,
     position_mint: &Pubkey,
     args: CloseActiveTunaLpPositionArgs,
-) -> Result<Vec<Instruction>> {
-    let tuna_position = fetch_tuna_lp_position(&rpc, &get_tuna_liquidity_position_address(&position_mint).0)?;
+) -> Result<TxPlan> {
+    block_on(close_active_tuna_lp_position_fusion_instructions_async(rpc, authority, position_mint, args))
+}
+
+pub async fn close_active_tuna_lp_position_fusion_instructions_async(
+    rpc: &impl AccountFetcher,
+    authority: &Pubkey,
+    position_mint: &Pubkey,
+    args: CloseActiveTunaLpPositionArgs,
+) -> Result<TxPlan> {
+    let tuna_position = fetch_tuna_lp_position(rpc, &get_tuna_liquidity_position_address(&position_mint).0).await?;
 
-    let fusion_pool = fetch_fusion_pool(rpc, &tuna_position.data.pool)?;
+    let fusion_pool = fetch_fusion_pool(rpc, &tuna_position.data.pool).await?;
     let mint_a_address = fusion_pool.data.token_mint_a;
     let mint_b_address = fusion_pool.data.token_mint_b;
 
     let market_address = get_market_address(&tuna_position.data.pool).0;
-    let market = fetch_market(&rpc, &market_address)?;
+    let market = fetch_market(rpc, &market_address).await?;
 
-    let vaults = fetch_all_vault(&rpc, &[market.data.vault_a, market.data.vault_b])?;
+    let vaults = fetch_all_vault(rpc, &[market.data.vault_a, market.data.vault_b]).await?;
     let (vault_a, vault_b) = (&vaults[0], &vaults[1]);
 
     let all_mint_addresses = vec![mint_a_address, mint_b_address];
 
-    let mint_accounts = rpc.get_multiple_accounts(all_mint_addresses[0..all_mint_addresses.len()].into())?;
+    let mint_accounts = rpc.get_multiple_accounts(all_mint_addresses[0..all_mint_addresses.len()].into()).await?;
     let mint_a_account = mint_accounts[0].as_ref().ok_or(anyhow!("Token A mint account not found"))?;
     let mint_b_account = mint_accounts[1].as_ref().ok_or(anyhow!("Token B mint account not found"))?;
 
@@ -74,5 +82,16 @@ pub fn close_active_tuna_lp_position_fusion_instructions(
 
     instructions.push(close_tuna_lp_position_fusion_instruction(&authority, &tuna_position.data, &mint_a_account.owner, &mint_b_account.owner));
 
-    Ok(instructions)
+    let closed_accounts = vec![
+        tuna_position.address,
+        get_position_address(position_mint)?.0,
+        *position_mint,
+        get_associated_token_address_with_program_id(&tuna_position.address, position_mint, &spl_token_2022::ID),
+        get_associated_token_address_with_program_id(&tuna_position.address, &mint_a_address, &mint_a_account.owner),
+        get_associated_token_address_with_program_id(&tuna_position.address, &mint_b_address, &mint_b_account.owner),
+    ];
+
+    Ok(TxPlan::from(instructions)
+        .with_accounts(vec![], closed_accounts)
+        .with_market_lookup_table(&market.data))
 }
@@ -1,51 +1,56 @@
-use crate::accounts::{fetch_all_vault, fetch_market, fetch_tuna_lp_position};
+use crate::fetcher::{block_on, fetch_all_vault, fetch_market, fetch_tuna_lp_position, fetch_whirlpool, AccountFetcher, SyncAccountFetcher};
 use crate::utils::get_create_ata_instructions;
+use crate::utils::orca::prepare_rewards_to_collect_async;
 use crate::{
     close_tuna_lp_position_orca_instruction, decrease_tuna_lp_position_orca_instruction, get_market_address, get_tuna_liquidity_position_address,
-    CloseActiveTunaLpPositionArgs, DecreaseTunaLpPositionArgs, HUNDRED_PERCENT,
+    CloseActiveTunaLpPositionArgs, DecreaseTunaLpPositionArgs, TxPlan, HUNDRED_PERCENT,
 };
 use anyhow::{anyhow, Result};
-use orca_whirlpools_client::fetch_whirlpool;
-use solana_client::rpc_client::RpcClient;
-use solana_instruction::Instruction;
+use orca_whirlpools_client::get_position_address;
 use solana_pubkey::Pubkey;
+use spl_associated_token_account::get_associated_token_address_with_program_id;
 
-// TODO: rewards support
 pub fn close_active_tuna_lp_position_orca_instructions(
-    rpc: &RpcClient,
+    rpc: &impl SyncAccountFetcher,
     authority: &Pubkey,
     position_mint: &Pubkey,
     args: CloseActiveTunaLpPositionArgs,
-) -> Result<Vec<Instruction>> {
-    let tuna_position = fetch_tuna_lp_position(&rpc, &get_tuna_liquidity_position_address(&position_mint).0)?;
+) -> Result<TxPlan> {
+    block_on(close_active_tuna_lp_position_orca_instructions_async(rpc, authority, position_mint, args))
+}
+
+pub async fn close_active_tuna_lp_position_orca_instructions_async(
+    rpc: &impl AccountFetcher,
+    authority: &Pubkey,
+    position_mint: &Pubkey,
+    args: CloseActiveTunaLpPositionArgs,
+) -> Result<TxPlan> {
+    let tuna_position = fetch_tuna_lp_position(rpc, &get_tuna_liquidity_position_address(&position_mint).0).await?;
 
-    let whirlpool = fetch_whirlpool(rpc, &tuna_position.data.pool)?;
+    let whirlpool = fetch_whirlpool(rpc, &tuna_position.data.pool).await?;
     let mint_a_address = whirlpool.data.token_mint_a;
     let mint_b_address = whirlpool.data.token_mint_b;
 
     let market_address = get_market_address(&tuna_position.data.pool).0;
-    let market = fetch_market(&rpc, &market_address)?;
+    let market = fetch_market(rpc, &market_address).await?;
 
-    let vaults = fetch_all_vault(&rpc, &[market.data.vault_a, market.data.vault_b])?;
+    let vaults = fetch_all_vault(rpc, &[market.data.vault_a, market.data.vault_b]).await?;
     let (vault_a, vault_b) = (&vaults[0], &vaults[1]);
 
-    let mut all_mint_addresses = vec![mint_a_address, mint_b_address];
-    for reward_info in &whirlpool.data.reward_infos {
-        if reward_info.mint != Pubkey::default() {
-            all_mint_addresses.push(reward_info.mint);
-        }
-    }
-
-    let mint_accounts = rpc.get_multiple_accounts(all_mint_addresses[0..all_mint_addresses.len()].into())?;
+    let mint_accounts = rpc.get_multiple_accounts(&[mint_a_address, mint_b_address]).await?;
     let mint_a_account = mint_accounts[0].as_ref().ok_or(anyhow!("Token A mint account not found"))?;
     let mint_b_account = mint_accounts[1].as_ref().ok_or(anyhow!("Token B mint account not found"))?;
 
     let authority_ata_a_instructions = get_create_ata_instructions(&mint_a_address, authority, authority, &mint_a_account.owner, 0);
     let authority_ata_b_instructions = get_create_ata_instructions(&mint_b_address, authority, authority, &mint_b_account.owner, 0);
 
+    // All owed rewards must be collected before the position is closed.
+    let (rewards, authority_reward_ata_instructions) = prepare_rewards_to_collect_async(rpc, authority, &whirlpool.data, position_mint).await?;
+
     let mut instructions = vec![];
     instructions.extend(authority_ata_a_instructions.create);
     instructions.extend(authority_ata_b_instructions.create);
+    instructions.extend(authority_reward_ata_instructions.create);
 
     instructions.push(decrease_tuna_lp_position_orca_instruction(
         authority,
@@ -57,6 +62,7 @@ pub fn close_active_tuna_lp_position_orca_instructions(
         &whirlpool.data,
         &mint_a_account.owner,
         &mint_b_account.owner,
+        &rewards,
         DecreaseTunaLpPositionArgs {
             decrease_percent: HUNDRED_PERCENT,
             swap_to_token: args.swap_to_token,
@@ -68,8 +74,20 @@ pub fn close_active_tuna_lp_position_orca_instructions(
 
     instructions.extend(authority_ata_a_instructions.cleanup);
     instructions.extend(authority_ata_b_instructions.cleanup);
+    instructions.extend(authority_reward_ata_instructions.cleanup);
 
     instructions.push(close_tuna_lp_position_orca_instruction(&authority, &tuna_position.data, &mint_a_account.owner, &mint_b_account.owner));
 
-    Ok(instructions)
+    let closed_accounts = vec![
+        tuna_position.address,
+        get_position_address(position_mint)?.0,
+        *position_mint,
+        get_associated_token_address_with_program_id(&tuna_position.address, position_mint, &spl_token_2022::ID),
+        get_associated_token_address_with_program_id(&tuna_position.address, &mint_a_address, &mint_a_account.owner),
+        get_associated_token_address_with_program_id(&tuna_position.address, &mint_b_address, &mint_b_account.owner),
+    ];
+
+    Ok(TxPlan::from(instructions)
+        .with_accounts(vec![], closed_accounts)
+        .with_market_lookup_table(&market.data))
 }
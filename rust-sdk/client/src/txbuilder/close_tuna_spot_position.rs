@@ -1,31 +1,43 @@
-use crate::accounts::fetch_tuna_spot_position;
+use crate::fetcher::{block_on, fetch_tuna_spot_position, AccountFetcher, SyncAccountFetcher};
 use crate::get_tuna_spot_position_address;
 use crate::instructions::CloseTunaSpotPosition;
+use crate::TxPlan;
 use anyhow::{anyhow, Result};
-use solana_client::rpc_client::RpcClient;
 use solana_instruction::Instruction;
 use solana_pubkey::Pubkey;
 use spl_associated_token_account::get_associated_token_address_with_program_id;
 
-pub fn close_tuna_spot_position_instructions(rpc: &RpcClient, authority: &Pubkey, pool_address: &Pubkey) -> Result<Vec<Instruction>> {
+pub fn close_tuna_spot_position_instructions(rpc: &impl SyncAccountFetcher, authority: &Pubkey, pool_address: &Pubkey) -> Result<TxPlan> {
+    block_on(close_tuna_spot_position_instructions_async(rpc, authority, pool_address))
+}
+
+pub async fn close_tuna_spot_position_instructions_async(rpc: &impl AccountFetcher, authority: &Pubkey, pool_address: &Pubkey) -> Result<TxPlan> {
     let tuna_position_address = get_tuna_spot_position_address(authority, pool_address).0;
-    let tuna_position = fetch_tuna_spot_position(rpc, &tuna_position_address)?;
+    let tuna_position = fetch_tuna_spot_position(rpc, &tuna_position_address).await?;
 
     let mint_a_address = tuna_position.data.mint_a;
     let mint_b_address = tuna_position.data.mint_b;
 
-    let mint_accounts = rpc.get_multiple_accounts(&[mint_a_address.into(), mint_b_address.into()])?;
+    let mint_accounts = rpc.get_multiple_accounts(&[mint_a_address.into(), mint_b_address.into()]).await?;
     let mint_a_account = mint_accounts[0].as_ref().ok_or(anyhow!("Token A mint account not found"))?;
     let mint_b_account = mint_accounts[1].as_ref().ok_or(anyhow!("Token B mint account not found"))?;
 
-    Ok(vec![close_tuna_spot_position_instruction(
+    let instructions = vec![close_tuna_spot_position_instruction(
         authority,
         &tuna_position_address,
         &mint_a_address,
         &mint_b_address,
         &mint_a_account.owner,
         &mint_b_account.owner,
-    )])
+    )];
+
+    let closed_accounts = vec![
+        tuna_position_address,
+        get_associated_token_address_with_program_id(&tuna_position_address, &mint_a_address, &mint_a_account.owner),
+        get_associated_token_address_with_program_id(&tuna_position_address, &mint_b_address, &mint_b_account.owner),
+    ];
+
+    Ok(TxPlan::from(instructions).with_accounts(vec![], closed_accounts))
 }
 
 pub fn close_tuna_spot_position_instruction(
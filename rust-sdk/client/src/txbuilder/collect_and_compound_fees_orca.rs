@@ -1,12 +1,12 @@
-use crate::accounts::{fetch_all_vault, fetch_tuna_config, fetch_tuna_lp_position, TunaConfig, TunaLpPosition, Vault};
+use crate::accounts::{TunaConfig, TunaLpPosition, Vault};
+use crate::fetcher::{block_on, fetch_all_vault, fetch_tuna_config, fetch_tuna_lp_position, fetch_whirlpool, AccountFetcher, SyncAccountFetcher};
 use crate::instructions::{CollectAndCompoundFeesOrca, CollectAndCompoundFeesOrcaInstructionArgs};
 use crate::types::{AccountsType, RemainingAccountsInfo, RemainingAccountsSlice};
 use crate::utils::orca::get_swap_tick_arrays;
-use crate::{get_market_address, get_tuna_config_address, get_tuna_liquidity_position_address, get_vault_address};
+use crate::{get_market_address, get_tuna_config_address, get_tuna_liquidity_position_address, get_vault_address, TxPlan};
 use anyhow::{anyhow, Result};
-use orca_whirlpools_client::{fetch_whirlpool, get_oracle_address, get_position_address, get_tick_array_address, Whirlpool};
+use orca_whirlpools_client::{get_oracle_address, get_position_address, get_tick_array_address, Whirlpool};
 use orca_whirlpools_core::get_tick_array_start_tick_index;
-use solana_client::rpc_client::RpcClient;
 use solana_instruction::{AccountMeta, Instruction};
 use solana_pubkey::Pubkey;
 use solana_sdk_ids::system_program;
@@ -14,23 +14,32 @@ use spl_associated_token_account::get_associated_token_address_with_program_id;
 use spl_associated_token_account::instruction::create_associated_token_account_idempotent;
 
 pub fn collect_and_compound_fees_orca_instructions(
-    rpc: &RpcClient,
+    rpc: &impl SyncAccountFetcher,
     authority: &Pubkey,
     position_mint: &Pubkey,
     use_leverage: bool,
-) -> Result<Vec<Instruction>> {
-    let tuna_position = fetch_tuna_lp_position(&rpc, &get_tuna_liquidity_position_address(&position_mint).0)?;
+) -> Result<TxPlan> {
+    block_on(collect_and_compound_fees_orca_instructions_async(rpc, authority, position_mint, use_leverage))
+}
+
+pub async fn collect_and_compound_fees_orca_instructions_async(
+    rpc: &impl AccountFetcher,
+    authority: &Pubkey,
+    position_mint: &Pubkey,
+    use_leverage: bool,
+) -> Result<TxPlan> {
+    let tuna_position = fetch_tuna_lp_position(rpc, &get_tuna_liquidity_position_address(&position_mint).0).await?;
 
-    let whirlpool = fetch_whirlpool(rpc, &tuna_position.data.pool)?;
+    let whirlpool = fetch_whirlpool(rpc, &tuna_position.data.pool).await?;
     let mint_a_address = whirlpool.data.token_mint_a;
     let mint_b_address = whirlpool.data.token_mint_b;
 
-    let tuna_config = fetch_tuna_config(rpc, &get_tuna_config_address().0)?;
+    let tuna_config = fetch_tuna_config(rpc, &get_tuna_config_address().0).await?;
 
-    let vaults = fetch_all_vault(&rpc, &[get_vault_address(&mint_a_address, None).0, get_vault_address(&mint_b_address, None).0])?;
+    let vaults = fetch_all_vault(rpc, &[get_vault_address(&mint_a_address, None).0, get_vault_address(&mint_b_address, None).0]).await?;
     let (vault_a, vault_b) = (&vaults[0], &vaults[1]);
 
-    let mint_accounts = rpc.get_multiple_accounts(&[mint_a_address.into(), mint_b_address.into()])?;
+    let mint_accounts = rpc.get_multiple_accounts(&[mint_a_address.into(), mint_b_address.into()]).await?;
     let mint_a_account = mint_accounts[0].as_ref().ok_or(anyhow!("Token A mint account not found"))?;
     let mint_b_account = mint_accounts[1].as_ref().ok_or(anyhow!("Token B mint account not found"))?;
 
@@ -46,7 +55,8 @@ pub fn collect_and_compound_fees_orca_instructions(
         &mint_a_account.owner,
         &mint_b_account.owner,
         use_leverage,
-    ))
+    )
+    .into())
 }
 
 pub fn _collect_and_compound_fees_orca_instructions(
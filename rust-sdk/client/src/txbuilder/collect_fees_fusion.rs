@@ -1,24 +1,28 @@
-use crate::accounts::{fetch_tuna_lp_position, TunaLpPosition};
+use crate::accounts::TunaLpPosition;
+use crate::fetcher::{block_on, fetch_fusion_pool, fetch_tuna_lp_position, AccountFetcher, SyncAccountFetcher};
 use crate::instructions::{CollectFeesFusion, CollectFeesFusionInstructionArgs};
 use crate::types::{AccountsType, RemainingAccountsInfo, RemainingAccountsSlice};
 use crate::utils::get_create_ata_instructions;
-use crate::{get_tuna_config_address, get_tuna_liquidity_position_address};
+use crate::{get_tuna_config_address, get_tuna_liquidity_position_address, TxPlan};
 use anyhow::{anyhow, Result};
-use fusionamm_client::{fetch_fusion_pool, get_position_address, get_tick_array_address, FusionPool};
+use fusionamm_client::{get_position_address, get_tick_array_address, FusionPool};
 use fusionamm_core::get_tick_array_start_tick_index;
-use solana_client::rpc_client::RpcClient;
 use solana_instruction::{AccountMeta, Instruction};
 use solana_pubkey::Pubkey;
 use spl_associated_token_account::get_associated_token_address_with_program_id;
 
-pub fn collect_fees_fusion_instructions(rpc: &RpcClient, authority: &Pubkey, position_mint: &Pubkey) -> Result<Vec<Instruction>> {
-    let tuna_position = fetch_tuna_lp_position(&rpc, &get_tuna_liquidity_position_address(&position_mint).0)?;
+pub fn collect_fees_fusion_instructions(rpc: &impl SyncAccountFetcher, authority: &Pubkey, position_mint: &Pubkey) -> Result<TxPlan> {
+    block_on(collect_fees_fusion_instructions_async(rpc, authority, position_mint))
+}
+
+pub async fn collect_fees_fusion_instructions_async(rpc: &impl AccountFetcher, authority: &Pubkey, position_mint: &Pubkey) -> Result<TxPlan> {
+    let tuna_position = fetch_tuna_lp_position(rpc, &get_tuna_liquidity_position_address(&position_mint).0).await?;
 
-    let fusion_pool = fetch_fusion_pool(rpc, &tuna_position.data.pool)?;
+    let fusion_pool = fetch_fusion_pool(rpc, &tuna_position.data.pool).await?;
     let mint_a_address = fusion_pool.data.token_mint_a;
     let mint_b_address = fusion_pool.data.token_mint_b;
 
-    let mint_accounts = rpc.get_multiple_accounts(&[mint_a_address.into(), mint_b_address.into()])?;
+    let mint_accounts = rpc.get_multiple_accounts(&[mint_a_address.into(), mint_b_address.into()]).await?;
     let mint_a_account = mint_accounts[0].as_ref().ok_or(anyhow!("Token A mint account not found"))?;
     let mint_b_account = mint_accounts[1].as_ref().ok_or(anyhow!("Token B mint account not found"))?;
 
@@ -40,7 +44,7 @@ pub fn collect_fees_fusion_instructions(rpc: &RpcClient, authority: &Pubkey, pos
     instructions.extend(authority_ata_a_instructions.cleanup);
     instructions.extend(authority_ata_b_instructions.cleanup);
 
-    Ok(instructions)
+    Ok(instructions.into())
 }
 
 pub fn collect_fees_fusion_instruction(
@@ -0,0 +1,93 @@
+use crate::accounts::TunaLpPosition;
+use crate::fetcher::{block_on, fetch_tuna_lp_position, fetch_whirlpool, AccountFetcher, SyncAccountFetcher};
+use crate::instructions::{CollectRewardOrca, CollectRewardOrcaInstructionArgs};
+use crate::utils::get_create_ata_instructions;
+use crate::{get_tuna_config_address, get_tuna_liquidity_position_address, TxPlan};
+use anyhow::{anyhow, Result};
+use orca_whirlpools_client::{get_position_address, get_tick_array_address, Whirlpool};
+use orca_whirlpools_core::get_tick_array_start_tick_index;
+use solana_instruction::Instruction;
+use solana_pubkey::Pubkey;
+use spl_associated_token_account::get_associated_token_address_with_program_id;
+
+/// Collects all initialized whirlpool rewards of the position.
+pub fn collect_reward_orca_instructions(rpc: &impl SyncAccountFetcher, authority: &Pubkey, position_mint: &Pubkey) -> Result<TxPlan> {
+    block_on(collect_reward_orca_instructions_async(rpc, authority, position_mint))
+}
+
+/// Async version of [`collect_reward_orca_instructions`].
+pub async fn collect_reward_orca_instructions_async(rpc: &impl AccountFetcher, authority: &Pubkey, position_mint: &Pubkey) -> Result<TxPlan> {
+    let tuna_position = fetch_tuna_lp_position(rpc, &get_tuna_liquidity_position_address(position_mint).0).await?;
+
+    let whirlpool = fetch_whirlpool(rpc, &tuna_position.data.pool).await?;
+
+    let reward_indices: Vec<usize> = (0..whirlpool.data.reward_infos.len())
+        .filter(|&i| whirlpool.data.reward_infos[i].mint != Pubkey::default())
+        .collect();
+    let reward_mint_addresses: Vec<Pubkey> = reward_indices.iter().map(|&i| whirlpool.data.reward_infos[i].mint).collect();
+
+    let mint_accounts = rpc.get_multiple_accounts(&reward_mint_addresses).await?;
+
+    let mut instructions = vec![];
+    let mut cleanup_instructions = vec![];
+
+    for (i, &reward_index) in reward_indices.iter().enumerate() {
+        let reward_mint_address = reward_mint_addresses[i];
+        let reward_mint_account = mint_accounts[i].as_ref().ok_or(anyhow!("Reward mint account not found"))?;
+
+        let authority_ata_instructions = get_create_ata_instructions(&reward_mint_address, authority, authority, &reward_mint_account.owner, 0);
+        instructions.extend(authority_ata_instructions.create);
+        cleanup_instructions.extend(authority_ata_instructions.cleanup);
+
+        instructions.push(collect_reward_orca_instruction(
+            authority,
+            &tuna_position.data,
+            &whirlpool.data,
+            reward_index as u8,
+            &reward_mint_account.owner,
+        ));
+    }
+
+    instructions.extend(cleanup_instructions);
+
+    Ok(instructions.into())
+}
+
+pub fn collect_reward_orca_instruction(
+    authority: &Pubkey,
+    tuna_position: &TunaLpPosition,
+    whirlpool: &Whirlpool,
+    reward_index: u8,
+    reward_token_program: &Pubkey,
+) -> Instruction {
+    let reward_info = &whirlpool.reward_infos[reward_index as usize];
+    let whirlpool_address = tuna_position.pool;
+
+    let tuna_config_address = get_tuna_config_address().0;
+    let tuna_position_address = get_tuna_liquidity_position_address(&tuna_position.position_mint).0;
+
+    let tick_array_lower_start_tick_index = get_tick_array_start_tick_index(tuna_position.tick_lower_index, whirlpool.tick_spacing);
+    let tick_array_lower_address = get_tick_array_address(&whirlpool_address, tick_array_lower_start_tick_index).unwrap().0;
+
+    let tick_array_upper_start_tick_index = get_tick_array_start_tick_index(tuna_position.tick_upper_index, whirlpool.tick_spacing);
+    let tick_array_upper_address = get_tick_array_address(&whirlpool_address, tick_array_upper_start_tick_index).unwrap().0;
+
+    let ix_builder = CollectRewardOrca {
+        authority: *authority,
+        tuna_config: tuna_config_address,
+        reward_mint: reward_info.mint,
+        reward_token_program: *reward_token_program,
+        tuna_position: tuna_position_address,
+        tuna_position_ata: get_associated_token_address_with_program_id(&tuna_position_address, &tuna_position.position_mint, &spl_token_2022::ID),
+        tuna_position_owner_reward_ata: get_associated_token_address_with_program_id(authority, &reward_info.mint, reward_token_program),
+        whirlpool_program: orca_whirlpools_client::ID,
+        whirlpool: whirlpool_address,
+        orca_position: get_position_address(&tuna_position.position_mint).unwrap().0,
+        reward_vault: reward_info.vault,
+        tick_array_lower: tick_array_lower_address,
+        tick_array_upper: tick_array_upper_address,
+        memo_program: spl_memo::ID,
+    };
+
+    ix_builder.instruction(CollectRewardOrcaInstructionArgs { reward_index })
+}
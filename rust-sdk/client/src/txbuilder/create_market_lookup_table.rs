@@ -0,0 +1,227 @@
+use crate::accounts::Market;
+use crate::fetcher::{
+    block_on, fetch_all_vault, fetch_fusion_pool, fetch_market, fetch_tuna_config, fetch_whirlpool, AccountFetcher, SyncAccountFetcher,
+};
+use crate::instructions::UpdateMarketInstructionArgs;
+use crate::types::MarketMaker;
+use crate::utils::{fusion, orca};
+use crate::{get_market_address, get_tuna_config_address, get_tuna_price_update_address, update_market_instruction, TxPlan, WP_NFT_UPDATE_AUTH};
+use anyhow::{anyhow, Result};
+use fusionamm_client::{FP_NFT_UPDATE_AUTH, FUSIONAMM_ID};
+use orca_whirlpools_client::{get_oracle_address, WHIRLPOOL_ID};
+use solana_address_lookup_table_interface::instruction::{create_lookup_table, extend_lookup_table};
+use solana_address_lookup_table_interface::state::AddressLookupTable;
+use solana_pubkey::Pubkey;
+use solana_sdk_ids::{system_program, sysvar};
+use spl_associated_token_account::get_associated_token_address_with_program_id;
+
+/// The number of addresses added to the lookup table by one transaction.
+const LOOKUP_TABLE_EXTEND_CHUNK_SIZE: usize = 20;
+
+/// Returns the static accounts used by the instructions of a market, including the tick arrays near the current pool price.
+pub fn get_addresses_for_market_lookup_table(rpc: &impl SyncAccountFetcher, pool_address: &Pubkey) -> Result<Vec<Pubkey>> {
+    block_on(get_addresses_for_market_lookup_table_async(rpc, pool_address))
+}
+
+/// Async version of [`get_addresses_for_market_lookup_table`].
+pub async fn get_addresses_for_market_lookup_table_async(rpc: &impl AccountFetcher, pool_address: &Pubkey) -> Result<Vec<Pubkey>> {
+    let tuna_config_address = get_tuna_config_address().0;
+    let tuna_config = fetch_tuna_config(rpc, &tuna_config_address).await?;
+
+    let market_address = get_market_address(pool_address).0;
+    let market = fetch_market(rpc, &market_address).await?;
+
+    let vaults = fetch_all_vault(rpc, &[market.data.vault_a, market.data.vault_b]).await?;
+    let (vault_a, vault_b) = (&vaults[0], &vaults[1]);
+    let mint_a_address = vault_a.data.mint;
+    let mint_b_address = vault_b.data.mint;
+
+    let mint_accounts = rpc.get_multiple_accounts(&[mint_a_address, mint_b_address]).await?;
+    let mint_a_account = mint_accounts[0].as_ref().ok_or(anyhow!("Token A mint account not found"))?;
+    let mint_b_account = mint_accounts[1].as_ref().ok_or(anyhow!("Token B mint account not found"))?;
+
+    let tuna_price_update_a = get_tuna_price_update_address(&mint_a_address).0;
+    let tuna_price_update_b = get_tuna_price_update_address(&mint_b_address).0;
+
+    let mut addresses = vec![
+        system_program::ID,
+        sysvar::rent::ID,
+        spl_associated_token_account::ID,
+        spl_token::ID,
+        spl_token_2022::ID,
+        spl_token::native_mint::ID,
+        spl_memo::ID,
+        tuna_config_address,
+        market_address,
+        vault_a.address,
+        vault_b.address,
+        get_associated_token_address_with_program_id(&vault_a.address, &mint_a_address, &mint_a_account.owner),
+        get_associated_token_address_with_program_id(&vault_b.address, &mint_b_address, &mint_b_account.owner),
+        *pool_address,
+        tuna_config.data.fee_recipient,
+        get_associated_token_address_with_program_id(&tuna_config.data.fee_recipient, &mint_a_address, &mint_a_account.owner),
+        get_associated_token_address_with_program_id(&tuna_config.data.fee_recipient, &mint_b_address, &mint_b_account.owner),
+        tuna_price_update_a,
+        tuna_price_update_b,
+        mint_a_address,
+        mint_b_address,
+    ];
+
+    for oracle_price_update in [vault_a.data.oracle_price_update, vault_b.data.oracle_price_update] {
+        if oracle_price_update != Pubkey::default() {
+            addresses.push(oracle_price_update);
+        }
+    }
+
+    match market.data.market_maker {
+        MarketMaker::Orca => {
+            let whirlpool = fetch_whirlpool(rpc, pool_address).await?;
+            addresses.extend([
+                whirlpool.data.token_vault_a,
+                whirlpool.data.token_vault_b,
+                WHIRLPOOL_ID,
+                WP_NFT_UPDATE_AUTH,
+                get_oracle_address(pool_address)?.0,
+            ]);
+            for reward_info in &whirlpool.data.reward_infos {
+                if reward_info.mint != Pubkey::default() {
+                    addresses.extend([reward_info.mint, reward_info.vault]);
+                }
+            }
+            addresses.extend(orca::get_swap_tick_arrays(whirlpool.data.tick_current_index, whirlpool.data.tick_spacing, pool_address));
+        }
+        MarketMaker::Fusion => {
+            let fusion_pool = fetch_fusion_pool(rpc, pool_address).await?;
+            addresses.extend([
+                fusion_pool.data.token_vault_a,
+                fusion_pool.data.token_vault_b,
+                FUSIONAMM_ID,
+                FP_NFT_UPDATE_AUTH,
+            ]);
+            addresses.extend(fusion::get_swap_tick_arrays(fusion_pool.data.tick_current_index, fusion_pool.data.tick_spacing, pool_address));
+        }
+    }
+
+    let mut unique_addresses = Vec::with_capacity(addresses.len());
+    for address in addresses {
+        if !unique_addresses.contains(&address) {
+            unique_addresses.push(address);
+        }
+    }
+
+    Ok(unique_addresses)
+}
+
+/// Creates the address lookup table of a market and records it in the market account.
+///
+/// The addresses don't fit into a single transaction, so a plan is returned for each transaction.
+/// The transactions must be executed in order. The authority must be the tuna config admin.
+pub fn create_market_lookup_table_instructions(
+    rpc: &impl SyncAccountFetcher,
+    authority: &Pubkey,
+    pool_address: &Pubkey,
+    recent_slot: u64,
+) -> Result<Vec<TxPlan>> {
+    block_on(create_market_lookup_table_instructions_async(rpc, authority, pool_address, recent_slot))
+}
+
+/// Async version of [`create_market_lookup_table_instructions`].
+pub async fn create_market_lookup_table_instructions_async(
+    rpc: &impl AccountFetcher,
+    authority: &Pubkey,
+    pool_address: &Pubkey,
+    recent_slot: u64,
+) -> Result<Vec<TxPlan>> {
+    let market = fetch_market(rpc, &get_market_address(pool_address).0).await?;
+    let addresses = get_addresses_for_market_lookup_table_async(rpc, pool_address).await?;
+
+    let (create_instruction, lookup_table_address) = create_lookup_table(*authority, *authority, recent_slot);
+
+    let mut tx_plans = get_extend_lookup_table_tx_plans(authority, &lookup_table_address, addresses);
+    tx_plans[0].instructions.insert(0, create_instruction);
+    tx_plans[0]
+        .instructions
+        .push(update_market_lookup_table_instruction(authority, &market.data, &lookup_table_address));
+    tx_plans[0].created_accounts.push(lookup_table_address);
+
+    Ok(tx_plans)
+}
+
+/// Adds the market addresses missing in the lookup table to it.
+///
+/// The market account is updated to use the lookup table if it doesn't already.
+/// A plan is returned for each transaction, the vector is empty if there is nothing to do.
+pub fn extend_market_lookup_table_instructions(
+    rpc: &impl SyncAccountFetcher,
+    authority: &Pubkey,
+    pool_address: &Pubkey,
+    lookup_table_address: &Pubkey,
+) -> Result<Vec<TxPlan>> {
+    block_on(extend_market_lookup_table_instructions_async(rpc, authority, pool_address, lookup_table_address))
+}
+
+/// Async version of [`extend_market_lookup_table_instructions`].
+pub async fn extend_market_lookup_table_instructions_async(
+    rpc: &impl AccountFetcher,
+    authority: &Pubkey,
+    pool_address: &Pubkey,
+    lookup_table_address: &Pubkey,
+) -> Result<Vec<TxPlan>> {
+    let market = fetch_market(rpc, &get_market_address(pool_address).0).await?;
+    let addresses = get_addresses_for_market_lookup_table_async(rpc, pool_address).await?;
+
+    let lookup_table_account = rpc.get_account(lookup_table_address).await?;
+    let lookup_table =
+        AddressLookupTable::deserialize(&lookup_table_account.data).map_err(|_| anyhow!("Invalid address lookup table: {}", lookup_table_address))?;
+
+    let new_addresses: Vec<Pubkey> = addresses
+        .into_iter()
+        .filter(|address| !lookup_table.addresses.contains(address))
+        .collect();
+
+    let mut tx_plans = if new_addresses.is_empty() {
+        vec![]
+    } else {
+        get_extend_lookup_table_tx_plans(authority, lookup_table_address, new_addresses)
+    };
+
+    if market.data.address_lookup_table != *lookup_table_address {
+        let instruction = update_market_lookup_table_instruction(authority, &market.data, lookup_table_address);
+        match tx_plans.first_mut() {
+            Some(tx_plan) => tx_plan.instructions.push(instruction),
+            None => tx_plans.push(vec![instruction].into()),
+        }
+    }
+
+    Ok(tx_plans)
+}
+
+fn get_extend_lookup_table_tx_plans(authority: &Pubkey, lookup_table_address: &Pubkey, addresses: Vec<Pubkey>) -> Vec<TxPlan> {
+    addresses
+        .chunks(LOOKUP_TABLE_EXTEND_CHUNK_SIZE)
+        .map(|chunk| vec![extend_lookup_table(*lookup_table_address, *authority, Some(*authority), chunk.to_vec())].into())
+        .collect()
+}
+
+fn update_market_lookup_table_instruction(authority: &Pubkey, market: &Market, lookup_table_address: &Pubkey) -> solana_instruction::Instruction {
+    update_market_instruction(
+        authority,
+        &market.pool,
+        UpdateMarketInstructionArgs {
+            address_lookup_table: *lookup_table_address,
+            max_leverage: market.max_leverage,
+            protocol_fee: market.protocol_fee,
+            protocol_fee_on_collateral: market.protocol_fee_on_collateral,
+            liquidation_fee: market.liquidation_fee,
+            liquidation_threshold: market.liquidation_threshold,
+            oracle_price_deviation_threshold: market.oracle_price_deviation_threshold,
+            disabled: market.disabled,
+            borrow_limit_a: market.borrow_limit_a,
+            borrow_limit_b: market.borrow_limit_b,
+            max_swap_slippage: market.max_swap_slippage,
+            rebalance_protocol_fee: market.rebalance_protocol_fee,
+            spot_position_size_limit_a: market.spot_position_size_limit_a,
+            spot_position_size_limit_b: market.spot_position_size_limit_b,
+        },
+    )
+}
@@ -1,16 +1,16 @@
 use crate::instructions::{CreateVault, CreateVaultInstructionArgs};
 use crate::utils::get_create_ata_instructions;
-use crate::{get_tuna_config_address, get_vault_address};
+use crate::{get_tuna_config_address, get_vault_address, TxPlan};
 use solana_instruction::Instruction;
 use solana_pubkey::Pubkey;
 use solana_sdk_ids::system_program;
 use spl_associated_token_account::get_associated_token_address_with_program_id;
 
-pub fn create_vault_instructions(authority: &Pubkey, mint: &Pubkey, token_program: &Pubkey, args: CreateVaultInstructionArgs) -> Vec<Instruction> {
+pub fn create_vault_instructions(authority: &Pubkey, mint: &Pubkey, token_program: &Pubkey, args: CreateVaultInstructionArgs) -> TxPlan {
     let vault_address = get_vault_address(mint, None).0;
     let mut instructions = get_create_ata_instructions(&mint, &vault_address, authority, &token_program, 0).create;
     instructions.push(create_vault_instruction(authority, mint, token_program, args));
-    instructions
+    instructions.into()
 }
 
 pub fn create_vault_instruction(authority: &Pubkey, mint: &Pubkey, token_program: &Pubkey, args: CreateVaultInstructionArgs) -> Instruction {
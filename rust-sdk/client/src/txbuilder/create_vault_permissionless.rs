@@ -1,6 +1,6 @@
 use crate::instructions::{CreateVaultPermissionless, CreateVaultPermissionlessInstructionArgs};
 use crate::utils::get_create_ata_instructions;
-use crate::{get_tuna_config_address, get_vault_address};
+use crate::{get_tuna_config_address, get_vault_address, TxPlan};
 use solana_instruction::Instruction;
 use solana_pubkey::Pubkey;
 use solana_sdk_ids::system_program;
@@ -12,11 +12,11 @@ pub fn create_vault_permissionless_instructions(
     token_program: &Pubkey,
     market: &Pubkey,
     args: CreateVaultPermissionlessInstructionArgs,
-) -> Vec<Instruction> {
+) -> TxPlan {
     let vault_address = get_vault_address(mint, Some(market)).0;
     let mut instructions = get_create_ata_instructions(&mint, &vault_address, authority, &token_program, 0).create;
     instructions.push(create_vault_permissionless_instruction(authority, mint, token_program, market, args));
-    instructions
+    instructions.into()
 }
 
 pub fn create_vault_permissionless_instruction(
@@ -1,13 +1,13 @@
-use crate::accounts::{fetch_all_vault, fetch_market, fetch_tuna_lp_position, TunaLpPosition, Vault};
+use crate::accounts::{TunaLpPosition, Vault};
+use crate::fetcher::{block_on, fetch_all_vault, fetch_fusion_pool, fetch_market, fetch_tuna_lp_position, AccountFetcher, SyncAccountFetcher};
 use crate::instructions::{DecreaseTunaLpPositionFusion, DecreaseTunaLpPositionFusionInstructionArgs};
 use crate::types::{AccountsType, PoolToken, RemainingAccountsInfo, RemainingAccountsSlice};
 use crate::utils::fusion::get_swap_tick_arrays;
 use crate::utils::get_create_ata_instructions;
-use crate::{get_market_address, get_tuna_config_address, get_tuna_liquidity_position_address, HUNDRED_PERCENT};
+use crate::{get_market_address, get_tuna_config_address, get_tuna_liquidity_position_address, TxPlan, HUNDRED_PERCENT};
 use anyhow::{anyhow, Result};
-use fusionamm_client::{fetch_fusion_pool, get_position_address, get_tick_array_address, FusionPool};
+use fusionamm_client::{get_position_address, get_tick_array_address, FusionPool};
 use fusionamm_core::get_tick_array_start_tick_index;
-use solana_client::rpc_client::RpcClient;
 use solana_instruction::{AccountMeta, Instruction};
 use solana_pubkey::Pubkey;
 use spl_associated_token_account::get_associated_token_address_with_program_id;
@@ -33,25 +33,34 @@ impl Default for DecreaseTunaLpPositionArgs {
 }
 
 pub fn decrease_tuna_lp_position_fusion_instructions(
-    rpc: &RpcClient,
+    rpc: &impl SyncAccountFetcher,
     authority: &Pubkey,
     position_mint: &Pubkey,
     args: DecreaseTunaLpPositionArgs,
-) -> Result<Vec<Instruction>> {
-    let tuna_position = fetch_tuna_lp_position(&rpc, &get_tuna_liquidity_position_address(&position_mint).0)?;
+) -> Result<TxPlan> {
+    block_on(decrease_tuna_lp_position_fusion_instructions_async(rpc, authority, position_mint, args))
+}
+
+pub async fn decrease_tuna_lp_position_fusion_instructions_async(
+    rpc: &impl AccountFetcher,
+    authority: &Pubkey,
+    position_mint: &Pubkey,
+    args: DecreaseTunaLpPositionArgs,
+) -> Result<TxPlan> {
+    let tuna_position = fetch_tuna_lp_position(rpc, &get_tuna_liquidity_position_address(&position_mint).0).await?;
 
-    let fusion_pool = fetch_fusion_pool(rpc, &tuna_position.data.pool)?;
+    let fusion_pool = fetch_fusion_pool(rpc, &tuna_position.data.pool).await?;
     let mint_a_address = fusion_pool.data.token_mint_a;
     let mint_b_address = fusion_pool.data.token_mint_b;
 
     let market_address = get_market_address(&tuna_position.data.pool).0;
-    let market = fetch_market(&rpc, &market_address)?;
+    let market = fetch_market(rpc, &market_address).await?;
 
-    let vaults = fetch_all_vault(&rpc, &[market.data.vault_a, market.data.vault_b])?;
+    let vaults = fetch_all_vault(rpc, &[market.data.vault_a, market.data.vault_b]).await?;
     let (vault_a, vault_b) = (&vaults[0], &vaults[1]);
 
     let all_mint_addresses = vec![mint_a_address, mint_b_address];
-    let mint_accounts = rpc.get_multiple_accounts(all_mint_addresses[0..all_mint_addresses.len()].into())?;
+    let mint_accounts = rpc.get_multiple_accounts(all_mint_addresses[0..all_mint_addresses.len()].into()).await?;
     let mint_a_account = mint_accounts[0].as_ref().ok_or(anyhow!("Token A mint account not found"))?;
     let mint_b_account = mint_accounts[1].as_ref().ok_or(anyhow!("Token B mint account not found"))?;
 
@@ -78,7 +87,7 @@ pub fn decrease_tuna_lp_position_fusion_instructions(
     instructions.extend(authority_ata_a_instructions.cleanup);
     instructions.extend(authority_ata_b_instructions.cleanup);
 
-    Ok(instructions)
+    Ok(TxPlan::from(instructions).with_market_lookup_table(&market.data))
 }
 
 pub fn decrease_tuna_lp_position_fusion_instruction(
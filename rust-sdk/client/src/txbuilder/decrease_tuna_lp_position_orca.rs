@@ -1,53 +1,58 @@
-use crate::accounts::{fetch_all_vault, fetch_market, fetch_tuna_lp_position, TunaLpPosition, Vault};
+use crate::accounts::{TunaLpPosition, Vault};
+use crate::fetcher::{block_on, fetch_all_vault, fetch_market, fetch_tuna_lp_position, fetch_whirlpool, AccountFetcher, SyncAccountFetcher};
 use crate::instructions::{DecreaseTunaLpPositionOrca, DecreaseTunaLpPositionOrcaInstructionArgs};
 use crate::types::{AccountsType, RemainingAccountsInfo, RemainingAccountsSlice};
 use crate::utils::get_create_ata_instructions;
-use crate::utils::orca::get_swap_tick_arrays;
-use crate::{get_market_address, get_tuna_config_address, get_tuna_liquidity_position_address, DecreaseTunaLpPositionArgs};
+use crate::utils::orca::{get_rewards_remaining_accounts, get_swap_tick_arrays, prepare_rewards_to_collect_async, RewardToCollect};
+use crate::{get_market_address, get_tuna_config_address, get_tuna_liquidity_position_address, DecreaseTunaLpPositionArgs, TxPlan};
 use anyhow::{anyhow, Result};
-use orca_whirlpools_client::{fetch_whirlpool, get_oracle_address, get_position_address, get_tick_array_address, Whirlpool};
+use orca_whirlpools_client::{get_oracle_address, get_position_address, get_tick_array_address, Whirlpool};
 use orca_whirlpools_core::get_tick_array_start_tick_index;
-use solana_client::rpc_client::RpcClient;
 use solana_instruction::{AccountMeta, Instruction};
 use solana_pubkey::Pubkey;
 use spl_associated_token_account::get_associated_token_address_with_program_id;
 
-// TODO: rewards support
 pub fn decrease_tuna_lp_position_orca_instructions(
-    rpc: &RpcClient,
+    rpc: &impl SyncAccountFetcher,
     authority: &Pubkey,
     position_mint: &Pubkey,
     args: DecreaseTunaLpPositionArgs,
-) -> Result<Vec<Instruction>> {
-    let tuna_position = fetch_tuna_lp_position(&rpc, &get_tuna_liquidity_position_address(&position_mint).0)?;
+) -> Result<TxPlan> {
+    block_on(decrease_tuna_lp_position_orca_instructions_async(rpc, authority, position_mint, args))
+}
+
+pub async fn decrease_tuna_lp_position_orca_instructions_async(
+    rpc: &impl AccountFetcher,
+    authority: &Pubkey,
+    position_mint: &Pubkey,
+    args: DecreaseTunaLpPositionArgs,
+) -> Result<TxPlan> {
+    let tuna_position = fetch_tuna_lp_position(rpc, &get_tuna_liquidity_position_address(&position_mint).0).await?;
 
-    let whirlpool = fetch_whirlpool(rpc, &tuna_position.data.pool)?;
+    let whirlpool = fetch_whirlpool(rpc, &tuna_position.data.pool).await?;
     let mint_a_address = whirlpool.data.token_mint_a;
     let mint_b_address = whirlpool.data.token_mint_b;
 
     let market_address = get_market_address(&tuna_position.data.pool).0;
-    let market = fetch_market(&rpc, &market_address)?;
+    let market = fetch_market(rpc, &market_address).await?;
 
-    let vaults = fetch_all_vault(&rpc, &[market.data.vault_a, market.data.vault_b])?;
+    let vaults = fetch_all_vault(rpc, &[market.data.vault_a, market.data.vault_b]).await?;
     let (vault_a, vault_b) = (&vaults[0], &vaults[1]);
 
-    let mut all_mint_addresses = vec![mint_a_address, mint_b_address];
-    for reward_info in &whirlpool.data.reward_infos {
-        if reward_info.mint != Pubkey::default() {
-            all_mint_addresses.push(reward_info.mint);
-        }
-    }
-
-    let mint_accounts = rpc.get_multiple_accounts(all_mint_addresses[0..all_mint_addresses.len()].into())?;
+    let mint_accounts = rpc.get_multiple_accounts(&[mint_a_address, mint_b_address]).await?;
     let mint_a_account = mint_accounts[0].as_ref().ok_or(anyhow!("Token A mint account not found"))?;
     let mint_b_account = mint_accounts[1].as_ref().ok_or(anyhow!("Token B mint account not found"))?;
 
     let authority_ata_a_instructions = get_create_ata_instructions(&mint_a_address, authority, authority, &mint_a_account.owner, 0);
     let authority_ata_b_instructions = get_create_ata_instructions(&mint_b_address, authority, authority, &mint_b_account.owner, 0);
 
+    // Owed rewards are collected along with the removed liquidity.
+    let (rewards, authority_reward_ata_instructions) = prepare_rewards_to_collect_async(rpc, authority, &whirlpool.data, position_mint).await?;
+
     let mut instructions = vec![];
     instructions.extend(authority_ata_a_instructions.create);
     instructions.extend(authority_ata_b_instructions.create);
+    instructions.extend(authority_reward_ata_instructions.create);
 
     instructions.push(decrease_tuna_lp_position_orca_instruction(
         authority,
@@ -59,16 +64,17 @@ pub fn decrease_tuna_lp_position_orca_instructions(
         &whirlpool.data,
         &mint_a_account.owner,
         &mint_b_account.owner,
+        &rewards,
         args,
     ));
 
     instructions.extend(authority_ata_a_instructions.cleanup);
     instructions.extend(authority_ata_b_instructions.cleanup);
+    instructions.extend(authority_reward_ata_instructions.cleanup);
 
-    Ok(instructions)
+    Ok(TxPlan::from(instructions).with_market_lookup_table(&market.data))
 }
 
-// TODO: rewards support
 pub fn decrease_tuna_lp_position_orca_instruction(
     authority: &Pubkey,
     tuna_position: &TunaLpPosition,
@@ -79,6 +85,7 @@ pub fn decrease_tuna_lp_position_orca_instruction(
     whirlpool: &Whirlpool,
     token_program_a: &Pubkey,
     token_program_b: &Pubkey,
+    rewards: &[RewardToCollect],
     args: DecreaseTunaLpPositionArgs,
 ) -> Instruction {
     let mint_a = whirlpool.token_mint_a;
@@ -106,6 +113,54 @@ pub fn decrease_tuna_lp_position_orca_instruction(
 
     let swap_ticks_arrays = get_swap_tick_arrays(whirlpool.tick_current_index, whirlpool.tick_spacing, &whirlpool_address);
 
+    let mut remaining_accounts_slices = vec![
+        RemainingAccountsSlice {
+            accounts_type: AccountsType::SwapTickArrays,
+            length: 5,
+        },
+        RemainingAccountsSlice {
+            accounts_type: AccountsType::TickArrayLower,
+            length: 1,
+        },
+        RemainingAccountsSlice {
+            accounts_type: AccountsType::TickArrayUpper,
+            length: 1,
+        },
+        RemainingAccountsSlice {
+            accounts_type: AccountsType::PoolVaultTokenA,
+            length: 1,
+        },
+        RemainingAccountsSlice {
+            accounts_type: AccountsType::PoolVaultTokenB,
+            length: 1,
+        },
+        RemainingAccountsSlice {
+            accounts_type: AccountsType::WhirlpoolOracle,
+            length: 1,
+        },
+    ];
+
+    let mut remaining_accounts = vec![
+        AccountMeta::new(swap_ticks_arrays[0], false),
+        AccountMeta::new(swap_ticks_arrays[1], false),
+        AccountMeta::new(swap_ticks_arrays[2], false),
+        AccountMeta::new(swap_ticks_arrays[3], false),
+        AccountMeta::new(swap_ticks_arrays[4], false),
+        AccountMeta::new(tick_array_lower_address, false),
+        AccountMeta::new(tick_array_upper_address, false),
+        AccountMeta::new(whirlpool.token_vault_a, false),
+        AccountMeta::new(whirlpool.token_vault_b, false),
+        AccountMeta::new(get_oracle_address(&tuna_position.pool).unwrap().0, false),
+    ];
+
+    if !rewards.is_empty() {
+        remaining_accounts_slices.push(RemainingAccountsSlice {
+            accounts_type: AccountsType::Rewards,
+            length: 4 * rewards.len() as u8,
+        });
+        remaining_accounts.extend(get_rewards_remaining_accounts(authority, whirlpool, rewards));
+    }
+
     let ix_builder = DecreaseTunaLpPositionOrca {
         authority: *authority,
         tuna_config: tuna_config_address,
@@ -140,45 +195,9 @@ pub fn decrease_tuna_lp_position_orca_instruction(
             min_removed_amount_b: args.min_removed_amount_b,
             max_swap_slippage: args.max_swap_slippage,
             remaining_accounts_info: RemainingAccountsInfo {
-                slices: vec![
-                    RemainingAccountsSlice {
-                        accounts_type: AccountsType::SwapTickArrays,
-                        length: 5,
-                    },
-                    RemainingAccountsSlice {
-                        accounts_type: AccountsType::TickArrayLower,
-                        length: 1,
-                    },
-                    RemainingAccountsSlice {
-                        accounts_type: AccountsType::TickArrayUpper,
-                        length: 1,
-                    },
-                    RemainingAccountsSlice {
-                        accounts_type: AccountsType::PoolVaultTokenA,
-                        length: 1,
-                    },
-                    RemainingAccountsSlice {
-                        accounts_type: AccountsType::PoolVaultTokenB,
-                        length: 1,
-                    },
-                    RemainingAccountsSlice {
-                        accounts_type: AccountsType::WhirlpoolOracle,
-                        length: 1,
-                    },
-                ],
+                slices: remaining_accounts_slices,
             },
         },
-        &[
-            AccountMeta::new(swap_ticks_arrays[0], false),
-            AccountMeta::new(swap_ticks_arrays[1], false),
-            AccountMeta::new(swap_ticks_arrays[2], false),
-            AccountMeta::new(swap_ticks_arrays[3], false),
-            AccountMeta::new(swap_ticks_arrays[4], false),
-            AccountMeta::new(tick_array_lower_address, false),
-            AccountMeta::new(tick_array_upper_address, false),
-            AccountMeta::new(whirlpool.token_vault_a, false),
-            AccountMeta::new(whirlpool.token_vault_b, false),
-            AccountMeta::new(get_oracle_address(&tuna_position.pool).unwrap().0, false),
-        ],
+        &remaining_accounts,
     )
 }
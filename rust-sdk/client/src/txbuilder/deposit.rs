@@ -1,14 +1,24 @@
+use crate::fetcher::{block_on, AccountFetcher, SyncAccountFetcher};
 use crate::instructions::{Deposit, DepositInstructionArgs};
 use crate::utils::get_create_ata_instructions;
-use crate::{get_lending_position_address, get_tuna_config_address, get_vault_address};
+use crate::{get_lending_position_address, get_tuna_config_address, get_vault_address, TxPlan};
 use anyhow::Result;
-use solana_client::rpc_client::RpcClient;
 use solana_instruction::Instruction;
 use solana_pubkey::Pubkey;
 use spl_associated_token_account::get_associated_token_address_with_program_id;
 
-pub fn deposit_instructions(rpc: &RpcClient, authority: &Pubkey, mint: &Pubkey, vault: Option<&Pubkey>, amount: u64) -> Result<Vec<Instruction>> {
-    let mint_account = rpc.get_account(mint.into())?;
+pub fn deposit_instructions(rpc: &impl SyncAccountFetcher, authority: &Pubkey, mint: &Pubkey, vault: Option<&Pubkey>, amount: u64) -> Result<TxPlan> {
+    block_on(deposit_instructions_async(rpc, authority, mint, vault, amount))
+}
+
+pub async fn deposit_instructions_async(
+    rpc: &impl AccountFetcher,
+    authority: &Pubkey,
+    mint: &Pubkey,
+    vault: Option<&Pubkey>,
+    amount: u64,
+) -> Result<TxPlan> {
+    let mint_account = rpc.get_account(mint.into()).await?;
 
     let authority_ata_instructions = get_create_ata_instructions(&mint, authority, authority, &mint_account.owner, amount);
 
@@ -17,7 +27,7 @@ pub fn deposit_instructions(rpc: &RpcClient, authority: &Pubkey, mint: &Pubkey,
     instructions.push(deposit_instruction(authority, mint, &mint_account.owner, vault, amount));
     instructions.extend(authority_ata_instructions.cleanup);
 
-    Ok(instructions)
+    Ok(instructions.into())
 }
 
 pub fn deposit_instruction(authority: &Pubkey, mint: &Pubkey, token_program: &Pubkey, vault: Option<&Pubkey>, amount: u64) -> Instruction {
@@ -1,15 +1,15 @@
-use crate::accounts::{fetch_all_vault, fetch_market, fetch_tuna_config, fetch_tuna_lp_position, TunaConfig, TunaLpPosition, Vault};
+use crate::accounts::{TunaConfig, TunaLpPosition, Vault};
+use crate::fetcher::{
+    block_on, fetch_all_vault, fetch_fusion_pool, fetch_market, fetch_tuna_config, fetch_tuna_lp_position, AccountFetcher, SyncAccountFetcher,
+};
 use crate::instructions::{IncreaseTunaLpPositionFusion, IncreaseTunaLpPositionFusionInstructionArgs};
 use crate::types::{AccountsType, RemainingAccountsInfo, RemainingAccountsSlice};
 use crate::utils::fusion::get_swap_tick_arrays;
 use crate::utils::get_create_ata_instructions;
-use crate::{get_market_address, get_tuna_config_address, get_tuna_liquidity_position_address};
+use crate::{get_market_address, get_tuna_config_address, get_tuna_liquidity_position_address, TunaPositionFlags, TxPlan};
 use anyhow::{anyhow, Result};
-use fusionamm_client::{
-    fetch_fusion_pool, get_position_address, get_tick_array_address, FusionPool, InitializeTickArray, InitializeTickArrayInstructionArgs,
-};
+use fusionamm_client::{get_position_address, get_tick_array_address, FusionPool, InitializeTickArray, InitializeTickArrayInstructionArgs};
 use fusionamm_core::get_tick_array_start_tick_index;
-use solana_client::rpc_client::RpcClient;
 use solana_instruction::{AccountMeta, Instruction};
 use solana_pubkey::Pubkey;
 use solana_sdk_ids::system_program;
@@ -28,26 +28,39 @@ pub struct IncreaseTunaLpPositionArgs {
 }
 
 pub fn increase_tuna_lp_position_fusion_instructions(
-    rpc: &RpcClient,
+    rpc: &impl SyncAccountFetcher,
+    authority: &Pubkey,
+    position_mint: &Pubkey,
+    args: IncreaseTunaLpPositionArgs,
+) -> Result<TxPlan> {
+    block_on(increase_tuna_lp_position_fusion_instructions_async(rpc, authority, position_mint, args))
+}
+
+pub async fn increase_tuna_lp_position_fusion_instructions_async(
+    rpc: &impl AccountFetcher,
     authority: &Pubkey,
     position_mint: &Pubkey,
     args: IncreaseTunaLpPositionArgs,
-) -> Result<Vec<Instruction>> {
-    let tuna_position = fetch_tuna_lp_position(&rpc, &get_tuna_liquidity_position_address(&position_mint).0)?;
+) -> Result<TxPlan> {
+    let tuna_position = fetch_tuna_lp_position(rpc, &get_tuna_liquidity_position_address(&position_mint).0).await?;
+
+    let leveraged = args.borrow_a > 0 || args.borrow_b > 0 || tuna_position.data.loan_shares_a > 0 || tuna_position.data.loan_shares_b > 0;
+    // The flags of the position may include bits added to the program later, so only the known combinations are checked.
+    TunaPositionFlags::from_bits_retain(tuna_position.data.flags).validate(leveraged)?;
 
-    let fusion_pool = fetch_fusion_pool(rpc, &tuna_position.data.pool)?;
+    let fusion_pool = fetch_fusion_pool(rpc, &tuna_position.data.pool).await?;
     let mint_a_address = fusion_pool.data.token_mint_a;
     let mint_b_address = fusion_pool.data.token_mint_b;
 
-    let tuna_config = fetch_tuna_config(rpc, &get_tuna_config_address().0)?;
+    let tuna_config = fetch_tuna_config(rpc, &get_tuna_config_address().0).await?;
 
     let market_address = get_market_address(&tuna_position.data.pool).0;
-    let market = fetch_market(&rpc, &market_address)?;
+    let market = fetch_market(rpc, &market_address).await?;
 
-    let vaults = fetch_all_vault(&rpc, &[market.data.vault_a, market.data.vault_b])?;
+    let vaults = fetch_all_vault(rpc, &[market.data.vault_a, market.data.vault_b]).await?;
     let (vault_a, vault_b) = (&vaults[0], &vaults[1]);
 
-    let mint_accounts = rpc.get_multiple_accounts(&[mint_a_address.into(), mint_b_address.into()])?;
+    let mint_accounts = rpc.get_multiple_accounts(&[mint_a_address.into(), mint_b_address.into()]).await?;
     let mint_a_account = mint_accounts[0].as_ref().ok_or(anyhow!("Token A mint account not found"))?;
     let mint_b_account = mint_accounts[1].as_ref().ok_or(anyhow!("Token B mint account not found"))?;
 
@@ -77,7 +90,9 @@ pub fn increase_tuna_lp_position_fusion_instructions(
     let lower_tick_array_address = get_tick_array_address(&fusion_pool.address, lower_tick_array_start_index)?.0;
     let upper_tick_array_address = get_tick_array_address(&fusion_pool.address, upper_tick_array_start_index)?.0;
 
-    let tick_array_infos = rpc.get_multiple_accounts(&[lower_tick_array_address.into(), upper_tick_array_address.into()])?;
+    let tick_array_infos = rpc
+        .get_multiple_accounts(&[lower_tick_array_address.into(), upper_tick_array_address.into()])
+        .await?;
 
     if tick_array_infos[0].is_none() {
         instructions.push(
@@ -123,7 +138,7 @@ pub fn increase_tuna_lp_position_fusion_instructions(
     instructions.extend(authority_ata_a_instructions.cleanup);
     instructions.extend(authority_ata_b_instructions.cleanup);
 
-    Ok(instructions)
+    Ok(TxPlan::from(instructions).with_market_lookup_table(&market.data))
 }
 
 pub fn increase_tuna_lp_position_fusion_instruction(
@@ -1,7 +1,7 @@
 use crate::accounts::{TunaConfig, TunaLpPosition, Vault};
 use crate::instructions::{LiquidateTunaLpPositionFusionJupiter, LiquidateTunaLpPositionFusionJupiterInstructionArgs};
 use crate::types::{AccountsType, RemainingAccountsInfo, RemainingAccountsSlice};
-use crate::{get_market_address, get_tuna_config_address, get_tuna_liquidity_position_address, JUPITER_PROGRAM_ID};
+use crate::{get_market_address, get_tuna_config_address, get_tuna_liquidity_position_address, TxPlan, JUPITER_PROGRAM_ID};
 use fusionamm_client::{get_position_address, get_tick_array_address, FusionPool};
 use fusionamm_core::get_tick_array_start_tick_index;
 use solana_instruction::{AccountMeta, Instruction};
@@ -30,7 +30,7 @@ pub fn liquidate_tuna_lp_position_fusion_jupiter_instructions(
     jupiter_route_accounts: Vec<AccountMeta>,
     jupiter_intermediate_token_accounts_and_programs: Vec<AccountMeta>,
     args: LiquidateTunaLpPositionJupiterArgs,
-) -> Vec<Instruction> {
+) -> TxPlan {
     vec![
         create_associated_token_account_idempotent(authority, &tuna_config.fee_recipient, &vault_a.mint, token_program_a),
         create_associated_token_account_idempotent(authority, &tuna_config.fee_recipient, &vault_b.mint, token_program_b),
@@ -50,6 +50,7 @@ pub fn liquidate_tuna_lp_position_fusion_jupiter_instructions(
             args,
         ),
     ]
+    .into()
 }
 
 pub fn liquidate_tuna_lp_position_fusion_jupiter_instruction(
@@ -153,10 +154,10 @@ pub fn liquidate_tuna_lp_position_fusion_jupiter_instruction(
     for account in jupiter_route_accounts {
         remaining_accounts.push(account);
     }
-    
+
     for account in jupiter_intermediate_token_accounts_and_programs {
         remaining_accounts.push(account);
-    }    
+    }
 
     ix_builder.instruction_with_remaining_accounts(
         LiquidateTunaLpPositionFusionJupiterInstructionArgs {
@@ -2,7 +2,7 @@ use crate::accounts::{TunaConfig, TunaLpPosition, Vault};
 use crate::instructions::{LiquidateTunaLpPositionOrca, LiquidateTunaLpPositionOrcaInstructionArgs};
 use crate::types::{AccountsType, RemainingAccountsInfo, RemainingAccountsSlice};
 use crate::utils::orca::get_swap_tick_arrays;
-use crate::{get_market_address, get_tuna_config_address, get_tuna_liquidity_position_address, HUNDRED_PERCENT};
+use crate::{get_market_address, get_tuna_config_address, get_tuna_liquidity_position_address, TxPlan, HUNDRED_PERCENT};
 use orca_whirlpools_client::{get_oracle_address, get_position_address, get_tick_array_address, Whirlpool};
 use orca_whirlpools_core::get_tick_array_start_tick_index;
 use solana_instruction::{AccountMeta, Instruction};
@@ -24,7 +24,7 @@ pub fn liquidate_tuna_lp_position_orca_instructions(
     token_program_a: &Pubkey,
     token_program_b: &Pubkey,
     decrease_percent: Option<u32>,
-) -> Vec<Instruction> {
+) -> TxPlan {
     vec![
         create_associated_token_account_idempotent(authority, &tuna_config.fee_recipient, &vault_a.mint, token_program_a),
         create_associated_token_account_idempotent(authority, &tuna_config.fee_recipient, &vault_b.mint, token_program_b),
@@ -42,6 +42,7 @@ pub fn liquidate_tuna_lp_position_orca_instructions(
             decrease_percent,
         ),
     ]
+    .into()
 }
 
 pub fn liquidate_tuna_lp_position_orca_instruction(
@@ -2,7 +2,7 @@ use crate::accounts::{TunaConfig, TunaLpPosition, Vault};
 use crate::instructions::{LiquidateTunaLpPositionOrcaJupiter, LiquidateTunaLpPositionOrcaJupiterInstructionArgs};
 use crate::types::{AccountsType, RemainingAccountsInfo, RemainingAccountsSlice};
 use crate::{
-    get_market_address, get_tuna_config_address, get_tuna_liquidity_position_address, LiquidateTunaLpPositionJupiterArgs, JUPITER_PROGRAM_ID,
+    get_market_address, get_tuna_config_address, get_tuna_liquidity_position_address, LiquidateTunaLpPositionJupiterArgs, TxPlan, JUPITER_PROGRAM_ID,
 };
 use orca_whirlpools_client::{get_position_address, get_tick_array_address, Whirlpool};
 use orca_whirlpools_core::get_tick_array_start_tick_index;
@@ -27,7 +27,7 @@ pub fn liquidate_tuna_lp_position_orca_jupiter_instructions(
     jupiter_route_accounts: Vec<AccountMeta>,
     jupiter_intermediate_token_accounts_and_programs: Vec<AccountMeta>,
     args: LiquidateTunaLpPositionJupiterArgs,
-) -> Vec<Instruction> {
+) -> TxPlan {
     vec![
         create_associated_token_account_idempotent(authority, &tuna_config.fee_recipient, &vault_a.mint, token_program_a),
         create_associated_token_account_idempotent(authority, &tuna_config.fee_recipient, &vault_b.mint, token_program_b),
@@ -47,6 +47,7 @@ pub fn liquidate_tuna_lp_position_orca_jupiter_instructions(
             args,
         ),
     ]
+    .into()
 }
 
 pub fn liquidate_tuna_lp_position_orca_jupiter_instruction(
@@ -2,7 +2,7 @@ use crate::accounts::{TunaConfig, TunaSpotPosition, Vault};
 use crate::instructions::{LiquidateTunaSpotPositionFusion, LiquidateTunaSpotPositionFusionInstructionArgs};
 use crate::types::{AccountsType, PoolToken, RemainingAccountsInfo, RemainingAccountsSlice};
 use crate::utils::fusion::get_swap_tick_arrays;
-use crate::{get_market_address, get_tuna_config_address, get_tuna_spot_position_address, HUNDRED_PERCENT};
+use crate::{get_market_address, get_tuna_config_address, get_tuna_spot_position_address, TxPlan, HUNDRED_PERCENT};
 use fusionamm_client::FusionPool;
 use solana_instruction::{AccountMeta, Instruction};
 use solana_pubkey::Pubkey;
@@ -22,7 +22,7 @@ pub fn liquidate_tuna_spot_position_fusion_instructions(
     token_program_a: &Pubkey,
     token_program_b: &Pubkey,
     decrease_percent: Option<u32>,
-) -> Vec<Instruction> {
+) -> TxPlan {
     let mut instructions = vec![];
 
     instructions.push(create_associated_token_account_idempotent(authority, &tuna_config.fee_recipient, &vault_a.mint, token_program_a));
@@ -63,7 +63,7 @@ pub fn liquidate_tuna_spot_position_fusion_instructions(
         decrease_percent,
     ));
 
-    instructions
+    instructions.into()
 }
 
 pub fn liquidate_tuna_spot_position_fusion_instruction(
@@ -1,7 +1,7 @@
 use crate::accounts::{TunaConfig, TunaSpotPosition, Vault};
 use crate::instructions::{LiquidateTunaSpotPositionJupiter, LiquidateTunaSpotPositionJupiterInstructionArgs};
 use crate::types::{AccountsType, RemainingAccountsInfo, RemainingAccountsSlice};
-use crate::{get_market_address, get_tuna_config_address, get_tuna_spot_position_address, JUPITER_PROGRAM_ID};
+use crate::{get_market_address, get_tuna_config_address, get_tuna_spot_position_address, TxPlan, JUPITER_PROGRAM_ID};
 use solana_instruction::{AccountMeta, Instruction};
 use solana_pubkey::Pubkey;
 use solana_sdk_ids::system_program;
@@ -26,7 +26,7 @@ pub fn liquidate_tuna_spot_position_jupiter_instructions(
     jupiter_route_accounts: Vec<AccountMeta>,
     jupiter_intermediate_token_accounts_and_programs: Vec<AccountMeta>,
     args: LiquidateTunaSpotPositionJupiterArgs,
-) -> Vec<Instruction> {
+) -> TxPlan {
     let mut instructions = vec![];
 
     instructions.push(create_associated_token_account_idempotent(authority, &tuna_config.fee_recipient, &vault_a.mint, token_program_a));
@@ -60,7 +60,7 @@ pub fn liquidate_tuna_spot_position_jupiter_instructions(
         args,
     ));
 
-    instructions
+    instructions.into()
 }
 
 pub fn liquidate_tuna_spot_position_jupiter_instruction(
@@ -7,7 +7,9 @@ pub mod collect_and_compound_fees_fusion;
 pub mod collect_and_compound_fees_orca;
 pub mod collect_fees_fusion;
 pub mod collect_fees_orca;
+pub mod collect_reward_orca;
 pub mod create_market;
+pub mod create_market_lookup_table;
 pub mod create_market_permissionless;
 pub mod create_price_update;
 pub mod create_tuna_config;
@@ -26,9 +28,11 @@ pub mod liquidate_tuna_spot_position_fusion;
 pub mod liquidate_tuna_spot_position_jupiter;
 pub mod liquidate_tuna_spot_position_orca;
 pub mod modify_tuna_spot_position_fusion;
+pub mod modify_tuna_spot_position_jupiter;
 pub mod modify_tuna_spot_position_orca;
 pub mod open_and_increase_tuna_lp_position_fusion;
 pub mod open_and_increase_tuna_lp_position_orca;
+pub mod open_and_increase_tuna_spot_position_jupiter;
 pub mod open_lending_position;
 pub mod open_lending_position_and_deposit;
 pub mod open_lending_position_v2;
@@ -37,6 +41,12 @@ pub mod open_tuna_lp_position_orca;
 pub mod open_tuna_spot_position;
 pub mod rebalance_tuna_lp_position_fusion;
 pub mod rebalance_tuna_lp_position_orca;
+pub mod repay_tuna_lp_position_debt;
+pub mod set_tuna_lp_position_flags;
+pub mod set_tuna_lp_position_limit_orders;
+pub mod set_tuna_lp_position_rebalance_threshold;
+pub mod set_tuna_spot_position_limit_orders;
+pub mod tx_plan;
 pub mod update_market;
 pub mod update_oracle_price;
 pub mod withdraw;
@@ -50,7 +60,9 @@ pub use collect_and_compound_fees_fusion::*;
 pub use collect_and_compound_fees_orca::*;
 pub use collect_fees_fusion::*;
 pub use collect_fees_orca::*;
+pub use collect_reward_orca::*;
 pub use create_market::*;
+pub use create_market_lookup_table::*;
 pub use create_market_permissionless::*;
 pub use create_price_update::*;
 pub use create_tuna_config::*;
@@ -69,6 +81,7 @@ pub use liquidate_tuna_spot_position_fusion::*;
 pub use liquidate_tuna_spot_position_orca::*;
 pub use open_and_increase_tuna_lp_position_fusion::*;
 pub use open_and_increase_tuna_lp_position_orca::*;
+pub use open_and_increase_tuna_spot_position_jupiter::*;
 pub use open_lending_position::*;
 pub use open_lending_position_and_deposit::*;
 pub use open_tuna_lp_position_fusion::*;
@@ -76,6 +89,12 @@ pub use open_tuna_lp_position_orca::*;
 pub use open_tuna_spot_position::*;
 pub use rebalance_tuna_lp_position_fusion::*;
 pub use rebalance_tuna_lp_position_orca::*;
+pub use repay_tuna_lp_position_debt::*;
+pub use set_tuna_lp_position_flags::*;
+pub use set_tuna_lp_position_limit_orders::*;
+pub use set_tuna_lp_position_rebalance_threshold::*;
+pub use set_tuna_spot_position_limit_orders::*;
+pub use tx_plan::*;
 pub use update_market::*;
 pub use update_oracle_price::*;
 pub use withdraw::*;
@@ -0,0 +1,251 @@
+use crate::accounts::{TunaConfig, Vault};
+use crate::fetcher::{block_on, fetch_all_vault, fetch_market, fetch_tuna_config, fetch_tuna_spot_position, AccountFetcher, SyncAccountFetcher};
+use crate::instructions::{ModifyTunaSpotPositionJupiter, ModifyTunaSpotPositionJupiterInstructionArgs};
+use crate::types::{AccountsType, PoolToken, RemainingAccountsInfo, RemainingAccountsSlice};
+use crate::utils::{
+    get_create_ata_instructions, get_increase_spot_position_swap_input_amount, get_jupiter_route_accounts, validate_jupiter_route_accounts,
+    JupiterRoute,
+};
+use crate::{get_market_address, get_tuna_config_address, get_tuna_spot_position_address, TxPlan, HUNDRED_PERCENT, JUPITER_PROGRAM_ID};
+use anyhow::{anyhow, Result};
+use defituna_core::fixed::{mul_div_64, Rounding};
+use solana_instruction::{AccountMeta, Instruction};
+use solana_pubkey::Pubkey;
+use solana_sdk_ids::system_program;
+use spl_associated_token_account::get_associated_token_address_with_program_id;
+use spl_associated_token_account::instruction::create_associated_token_account_idempotent;
+
+pub struct ModifyTunaSpotPositionJupiterArgs {
+    pub decrease_percent: u32,
+    pub collateral_amount: u64,
+    pub borrow_amount: u64,
+}
+
+pub fn modify_tuna_spot_position_jupiter_instructions(
+    rpc: &impl SyncAccountFetcher,
+    authority: &Pubkey,
+    pool_address: &Pubkey,
+    jupiter_route: &JupiterRoute,
+    jupiter_intermediate_token_accounts_and_programs: Vec<AccountMeta>,
+    args: ModifyTunaSpotPositionJupiterArgs,
+) -> Result<TxPlan> {
+    block_on(modify_tuna_spot_position_jupiter_instructions_async(
+        rpc,
+        authority,
+        pool_address,
+        jupiter_route,
+        jupiter_intermediate_token_accounts_and_programs,
+        args,
+    ))
+}
+
+pub async fn modify_tuna_spot_position_jupiter_instructions_async(
+    rpc: &impl AccountFetcher,
+    authority: &Pubkey,
+    pool_address: &Pubkey,
+    jupiter_route: &JupiterRoute,
+    jupiter_intermediate_token_accounts_and_programs: Vec<AccountMeta>,
+    args: ModifyTunaSpotPositionJupiterArgs,
+) -> Result<TxPlan> {
+    let tuna_position_address = get_tuna_spot_position_address(authority, pool_address).0;
+    let tuna_position = fetch_tuna_spot_position(rpc, &tuna_position_address).await?;
+
+    let tuna_config = fetch_tuna_config(rpc, &get_tuna_config_address().0).await?;
+
+    let market_address = get_market_address(pool_address).0;
+    let market = fetch_market(rpc, &market_address).await?;
+
+    let vaults = fetch_all_vault(rpc, &[market.data.vault_a, market.data.vault_b]).await?;
+    let (vault_a, vault_b) = (&vaults[0], &vaults[1]);
+    let mint_a_address = vault_a.data.mint;
+    let mint_b_address = vault_b.data.mint;
+
+    let mint_accounts = rpc.get_multiple_accounts(&[mint_a_address, mint_b_address]).await?;
+    let mint_a_account = mint_accounts[0].as_ref().ok_or(anyhow!("Token A mint account not found"))?;
+    let mint_b_account = mint_accounts[1].as_ref().ok_or(anyhow!("Token B mint account not found"))?;
+
+    // The position token is swapped to the other token when the position is decreased, and vice versa.
+    let swap_to_token_a = (tuna_position.data.position_token == PoolToken::A) == (args.decrease_percent == 0);
+    let (source_mint, source_token_program, destination_mint, destination_token_program) = if swap_to_token_a {
+        (&mint_b_address, &mint_b_account.owner, &mint_a_address, &mint_a_account.owner)
+    } else {
+        (&mint_a_address, &mint_a_account.owner, &mint_b_address, &mint_b_account.owner)
+    };
+    let jupiter_route_accounts = get_jupiter_route_accounts(
+        &jupiter_route.instruction,
+        &tuna_position_address,
+        source_mint,
+        source_token_program,
+        destination_mint,
+        destination_token_program,
+    )?;
+
+    let (max_swap_input_amount, partial_swap_input) = if args.decrease_percent > 0 {
+        let remaining_amount = mul_div_64(
+            tuna_position.data.amount,
+            (HUNDRED_PERCENT - args.decrease_percent.min(HUNDRED_PERCENT)) as u64,
+            HUNDRED_PERCENT as u64,
+            Rounding::Down,
+        )
+        .map_err(|e| anyhow!(e))?;
+        // The removed amount is swapped entirely, unless the collateral is the position token.
+        // In this case only the funds required to repay the debt are swapped.
+        (tuna_position.data.amount - remaining_amount, tuna_position.data.collateral_token == tuna_position.data.position_token)
+    } else {
+        let swap_input_amount = get_increase_spot_position_swap_input_amount(
+            &market.data,
+            tuna_position.data.position_token,
+            tuna_position.data.collateral_token,
+            args.collateral_amount,
+            args.borrow_amount,
+        )?;
+        (swap_input_amount, false)
+    };
+    validate_jupiter_route_accounts(
+        &jupiter_route.instruction,
+        &jupiter_route_accounts,
+        &tuna_position_address,
+        &get_associated_token_address_with_program_id(&tuna_position_address, source_mint, source_token_program),
+        &get_associated_token_address_with_program_id(&tuna_position_address, destination_mint, destination_token_program),
+        max_swap_input_amount,
+        partial_swap_input,
+    )?;
+
+    let (collateral_amount_a, collateral_amount_b) = if tuna_position.data.collateral_token == PoolToken::A {
+        (args.collateral_amount, 0)
+    } else {
+        (0, args.collateral_amount)
+    };
+
+    let mut instructions = vec![];
+
+    let authority_ata_a_instructions = get_create_ata_instructions(&mint_a_address, authority, authority, &mint_a_account.owner, collateral_amount_a);
+    let authority_ata_b_instructions = get_create_ata_instructions(&mint_b_address, authority, authority, &mint_b_account.owner, collateral_amount_b);
+    instructions.extend(authority_ata_a_instructions.create);
+    instructions.extend(authority_ata_b_instructions.create);
+
+    instructions.push(create_associated_token_account_idempotent(
+        authority,
+        &tuna_config.data.fee_recipient,
+        &mint_a_address,
+        &mint_a_account.owner,
+    ));
+
+    instructions.push(create_associated_token_account_idempotent(
+        authority,
+        &tuna_config.data.fee_recipient,
+        &mint_b_address,
+        &mint_b_account.owner,
+    ));
+
+    instructions.push(modify_tuna_spot_position_jupiter_instruction(
+        authority,
+        &tuna_config.data,
+        &vault_a.address,
+        &vault_a.data,
+        &vault_b.address,
+        &vault_b.data,
+        pool_address,
+        &mint_a_account.owner,
+        &mint_b_account.owner,
+        jupiter_route_accounts,
+        jupiter_intermediate_token_accounts_and_programs,
+        jupiter_route.instruction.data.clone(),
+        args,
+    ));
+
+    instructions.extend(authority_ata_a_instructions.cleanup);
+    instructions.extend(authority_ata_b_instructions.cleanup);
+
+    Ok(TxPlan {
+        address_lookup_table_addresses: jupiter_route.address_lookup_table_addresses.clone(),
+        ..TxPlan::from(instructions)
+    }
+    .with_market_lookup_table(&market.data))
+}
+
+pub fn modify_tuna_spot_position_jupiter_instruction(
+    authority: &Pubkey,
+    tuna_config: &TunaConfig,
+    vault_a_address: &Pubkey,
+    vault_a: &Vault,
+    vault_b_address: &Pubkey,
+    vault_b: &Vault,
+    pool_address: &Pubkey,
+    token_program_a: &Pubkey,
+    token_program_b: &Pubkey,
+    jupiter_route_accounts: Vec<AccountMeta>,
+    jupiter_intermediate_token_accounts_and_programs: Vec<AccountMeta>,
+    jupiter_route_data: Vec<u8>,
+    args: ModifyTunaSpotPositionJupiterArgs,
+) -> Instruction {
+    let mint_a = vault_a.mint;
+    let mint_b = vault_b.mint;
+
+    let tuna_config_address = get_tuna_config_address().0;
+    let market_address = get_market_address(pool_address).0;
+    let tuna_position_address = get_tuna_spot_position_address(authority, pool_address).0;
+
+    let ix_builder = ModifyTunaSpotPositionJupiter {
+        authority: *authority,
+        tuna_config: tuna_config_address,
+        mint_a,
+        mint_b,
+        token_program_a: *token_program_a,
+        token_program_b: *token_program_b,
+        market: market_address,
+        vault_a: *vault_a_address,
+        vault_b: *vault_b_address,
+        vault_a_ata: get_associated_token_address_with_program_id(vault_a_address, &mint_a, token_program_a),
+        vault_b_ata: get_associated_token_address_with_program_id(vault_b_address, &mint_b, token_program_b),
+        tuna_position: tuna_position_address,
+        tuna_position_ata_a: get_associated_token_address_with_program_id(&tuna_position_address, &mint_a, token_program_a),
+        tuna_position_ata_b: get_associated_token_address_with_program_id(&tuna_position_address, &mint_b, token_program_b),
+        tuna_position_owner_ata_a: get_associated_token_address_with_program_id(authority, &mint_a, token_program_a),
+        tuna_position_owner_ata_b: get_associated_token_address_with_program_id(authority, &mint_b, token_program_b),
+        fee_recipient_ata_a: get_associated_token_address_with_program_id(&tuna_config.fee_recipient, &mint_a, token_program_a),
+        fee_recipient_ata_b: get_associated_token_address_with_program_id(&tuna_config.fee_recipient, &mint_b, token_program_b),
+        oracle_price_update_a: vault_a.oracle_price_update,
+        oracle_price_update_b: vault_b.oracle_price_update,
+        pool: *pool_address,
+        jupiter_program: JUPITER_PROGRAM_ID,
+        memo_program: spl_memo::ID,
+        system_program: system_program::ID,
+    };
+
+    let mut remaining_accounts_slices = vec![];
+    let mut remaining_accounts = vec![];
+
+    remaining_accounts_slices.push(RemainingAccountsSlice {
+        accounts_type: AccountsType::JupiterRoute,
+        length: jupiter_route_accounts.len() as u8,
+    });
+
+    if !jupiter_intermediate_token_accounts_and_programs.is_empty() {
+        remaining_accounts_slices.push(RemainingAccountsSlice {
+            accounts_type: AccountsType::JupiterIntermediateTokenAccounts,
+            length: jupiter_intermediate_token_accounts_and_programs.len() as u8,
+        });
+    }
+
+    for account in jupiter_route_accounts {
+        remaining_accounts.push(account);
+    }
+
+    for account in jupiter_intermediate_token_accounts_and_programs {
+        remaining_accounts.push(account);
+    }
+
+    ix_builder.instruction_with_remaining_accounts(
+        ModifyTunaSpotPositionJupiterInstructionArgs {
+            decrease_percent: args.decrease_percent,
+            collateral_amount: args.collateral_amount,
+            borrow_amount: args.borrow_amount,
+            jupiter_route_data,
+            remaining_accounts_info: RemainingAccountsInfo {
+                slices: remaining_accounts_slices,
+            },
+        },
+        &remaining_accounts,
+    )
+}
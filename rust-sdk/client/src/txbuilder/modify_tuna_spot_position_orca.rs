@@ -1,13 +1,15 @@
-use crate::accounts::{fetch_all_vault, fetch_market, fetch_maybe_tuna_spot_position, fetch_tuna_config, TunaConfig, Vault};
+use crate::accounts::{TunaConfig, Vault};
+use crate::fetcher::{
+    block_on, fetch_all_vault, fetch_market, fetch_maybe_tuna_spot_position, fetch_tuna_config, fetch_whirlpool, AccountFetcher, SyncAccountFetcher,
+};
 use crate::instructions::{ModifyTunaSpotPositionOrca, ModifyTunaSpotPositionOrcaInstructionArgs};
 use crate::modify_tuna_spot_position_fusion::ModifyTunaSpotPositionArgs;
 use crate::types::{AccountsType, PoolToken, RemainingAccountsInfo, RemainingAccountsSlice};
 use crate::utils::get_create_ata_instructions;
 use crate::utils::orca::get_swap_tick_arrays;
-use crate::{get_market_address, get_tuna_config_address, get_tuna_spot_position_address, MaybeAccount};
+use crate::{get_market_address, get_tuna_config_address, get_tuna_spot_position_address, MaybeAccount, TxPlan};
 use anyhow::{anyhow, Result};
-use orca_whirlpools_client::{fetch_whirlpool, get_oracle_address, Whirlpool};
-use solana_client::rpc_client::RpcClient;
+use orca_whirlpools_client::{get_oracle_address, Whirlpool};
 use solana_instruction::{AccountMeta, Instruction};
 use solana_pubkey::Pubkey;
 use solana_sdk_ids::system_program;
@@ -15,17 +17,27 @@ use spl_associated_token_account::get_associated_token_address_with_program_id;
 use spl_associated_token_account::instruction::create_associated_token_account_idempotent;
 
 pub fn modify_tuna_spot_position_orca_instructions(
-    rpc: &RpcClient,
+    rpc: &impl SyncAccountFetcher,
     authority: &Pubkey,
     whirlpool_address: &Pubkey,
     collateral_token: Option<PoolToken>,
     args: ModifyTunaSpotPositionArgs,
-) -> Result<Vec<Instruction>> {
+) -> Result<TxPlan> {
+    block_on(modify_tuna_spot_position_orca_instructions_async(rpc, authority, whirlpool_address, collateral_token, args))
+}
+
+pub async fn modify_tuna_spot_position_orca_instructions_async(
+    rpc: &impl AccountFetcher,
+    authority: &Pubkey,
+    whirlpool_address: &Pubkey,
+    collateral_token: Option<PoolToken>,
+    args: ModifyTunaSpotPositionArgs,
+) -> Result<TxPlan> {
     let tuna_position_address = get_tuna_spot_position_address(authority, whirlpool_address).0;
 
     let collateral_token: PoolToken = match collateral_token {
         None => {
-            let maybe_tuna_position = fetch_maybe_tuna_spot_position(&rpc, &tuna_position_address)?;
+            let maybe_tuna_position = fetch_maybe_tuna_spot_position(rpc, &tuna_position_address).await?;
             match maybe_tuna_position {
                 MaybeAccount::Exists(tuna_position) => tuna_position.data.collateral_token,
                 MaybeAccount::NotFound(_) => return Err(anyhow!("Tuna position account doesn't exist. Collateral token must be provided!")),
@@ -34,19 +46,19 @@ pub fn modify_tuna_spot_position_orca_instructions(
         Some(v) => v,
     };
 
-    let whirlpool = fetch_whirlpool(rpc, whirlpool_address)?;
+    let whirlpool = fetch_whirlpool(rpc, whirlpool_address).await?;
     let mint_a_address = whirlpool.data.token_mint_a;
     let mint_b_address = whirlpool.data.token_mint_b;
 
-    let tuna_config = fetch_tuna_config(rpc, &get_tuna_config_address().0)?;
+    let tuna_config = fetch_tuna_config(rpc, &get_tuna_config_address().0).await?;
 
     let market_address = get_market_address(whirlpool_address).0;
-    let market = fetch_market(&rpc, &market_address)?;
+    let market = fetch_market(rpc, &market_address).await?;
 
-    let vaults = fetch_all_vault(&rpc, &[market.data.vault_a, market.data.vault_b])?;
+    let vaults = fetch_all_vault(rpc, &[market.data.vault_a, market.data.vault_b]).await?;
     let (vault_a, vault_b) = (&vaults[0], &vaults[1]);
 
-    let mint_accounts = rpc.get_multiple_accounts(&[mint_a_address.into(), mint_b_address.into()])?;
+    let mint_accounts = rpc.get_multiple_accounts(&[mint_a_address.into(), mint_b_address.into()]).await?;
     let mint_a_account = mint_accounts[0].as_ref().ok_or(anyhow!("Token A mint account not found"))?;
     let mint_b_account = mint_accounts[1].as_ref().ok_or(anyhow!("Token B mint account not found"))?;
 
@@ -94,7 +106,7 @@ pub fn modify_tuna_spot_position_orca_instructions(
 
     instructions.extend(authority_ata_instructions.cleanup);
 
-    Ok(instructions)
+    Ok(TxPlan::from(instructions).with_market_lookup_table(&market.data))
 }
 
 pub fn modify_tuna_spot_position_orca_instruction(
@@ -1,48 +1,31 @@
-use crate::accounts::{fetch_all_vault, fetch_market, fetch_tuna_config, TunaConfig, Vault};
+use crate::accounts::{TunaConfig, TunaLpPosition, Vault};
+use crate::fetcher::{block_on, fetch_all_vault, fetch_fusion_pool, fetch_market, fetch_tuna_config, AccountFetcher, SyncAccountFetcher};
 use crate::instructions::{OpenAndIncreaseTunaLpPositionFusion, OpenAndIncreaseTunaLpPositionFusionInstructionArgs};
 use crate::types::{AccountsType, RemainingAccountsInfo, RemainingAccountsSlice};
 use crate::utils::fusion::get_swap_tick_arrays;
-use crate::utils::get_create_ata_instructions;
-use crate::{get_market_address, get_tuna_config_address, get_tuna_liquidity_position_address};
+use crate::utils::{get_associated_token_account_len, get_create_ata_instructions, POSITION_NFT_TOKEN_ACCOUNT_LEN};
+use crate::{get_market_address, get_tuna_config_address, get_tuna_liquidity_position_address, TunaPositionFlags, TxPlan};
 use anyhow::{anyhow, Result};
 use fusionamm_client::{
-    fetch_fusion_pool, get_position_address, get_tick_array_address, FusionPool, InitializeTickArray, InitializeTickArrayInstructionArgs, TickArray,
+    get_position_address, get_tick_array_address, FusionPool, InitializeTickArray, InitializeTickArrayInstructionArgs, Position, TickArray,
     FP_NFT_UPDATE_AUTH,
 };
 use fusionamm_core::get_tick_array_start_tick_index;
-use solana_client::rpc_client::RpcClient;
 use solana_instruction::{AccountMeta, Instruction};
 use solana_keypair::Keypair;
 use solana_pubkey::Pubkey;
 use solana_sdk_ids::system_program;
 use solana_signer::Signer;
-use solana_sysvar::rent::Rent;
-use solana_sysvar::slot_hashes::SysvarId;
 use spl_associated_token_account::get_associated_token_address_with_program_id;
 use spl_associated_token_account::instruction::create_associated_token_account_idempotent;
 
-#[derive(Debug)]
-pub struct OpenAndIncreaseTunaLpPositionInstruction {
-    /// The public key of the position NFT that represents ownership of the newly opened position.
-    pub position_mint: Pubkey,
-
-    /// A vector of `Instruction` objects required to execute the position opening.
-    pub instructions: Vec<Instruction>,
-
-    /// A vector of `Keypair` objects representing additional signers required for the instructions.
-    pub additional_signers: Vec<Keypair>,
-
-    /// The cost of initializing the position, measured in lamports.
-    pub initialization_cost: u64,
-}
-
 #[derive(Default)]
 pub struct OpenAndIncreaseTunaLpPositionArgs {
     pub tick_lower_index: i32,
     pub tick_upper_index: i32,
     pub lower_limit_order_sqrt_price: u128,
     pub upper_limit_order_sqrt_price: u128,
-    pub flags: u32,
+    pub flags: TunaPositionFlags,
     pub collateral_a: u64,
     pub collateral_b: u64,
     pub borrow_a: u64,
@@ -52,28 +35,39 @@ pub struct OpenAndIncreaseTunaLpPositionArgs {
     pub max_swap_slippage: u32,
 }
 
+/// The mint of the new position is returned in `position_mint`, its keypair is returned as an additional signer.
 pub fn open_and_increase_tuna_lp_position_fusion_instructions(
-    rpc: &RpcClient,
+    rpc: &impl SyncAccountFetcher,
     authority: &Pubkey,
     fusion_pool_address: &Pubkey,
     args: OpenAndIncreaseTunaLpPositionArgs,
-) -> Result<OpenAndIncreaseTunaLpPositionInstruction> {
-    let rent = rpc.get_account(&Rent::id())?;
-    let rent: Rent = bincode::deserialize(&rent.data)?;
+) -> Result<TxPlan> {
+    block_on(open_and_increase_tuna_lp_position_fusion_instructions_async(rpc, authority, fusion_pool_address, args))
+}
 
-    let whirlpool = fetch_fusion_pool(rpc, fusion_pool_address)?;
+pub async fn open_and_increase_tuna_lp_position_fusion_instructions_async(
+    rpc: &impl AccountFetcher,
+    authority: &Pubkey,
+    fusion_pool_address: &Pubkey,
+    args: OpenAndIncreaseTunaLpPositionArgs,
+) -> Result<TxPlan> {
+    args.flags.validate(args.borrow_a > 0 || args.borrow_b > 0)?;
+
+    let rent = rpc.get_rent().await?;
+
+    let whirlpool = fetch_fusion_pool(rpc, fusion_pool_address).await?;
     let mint_a_address = whirlpool.data.token_mint_a;
     let mint_b_address = whirlpool.data.token_mint_b;
 
-    let tuna_config = fetch_tuna_config(rpc, &get_tuna_config_address().0)?;
+    let tuna_config = fetch_tuna_config(rpc, &get_tuna_config_address().0).await?;
 
     let market_address = get_market_address(fusion_pool_address).0;
-    let market = fetch_market(&rpc, &market_address)?;
+    let market = fetch_market(rpc, &market_address).await?;
 
-    let vaults = fetch_all_vault(&rpc, &[market.data.vault_a, market.data.vault_b])?;
+    let vaults = fetch_all_vault(rpc, &[market.data.vault_a, market.data.vault_b]).await?;
     let (vault_a, vault_b) = (&vaults[0], &vaults[1]);
 
-    let mint_accounts = rpc.get_multiple_accounts(&[mint_a_address.into(), mint_b_address.into()])?;
+    let mint_accounts = rpc.get_multiple_accounts(&[mint_a_address.into(), mint_b_address.into()]).await?;
     let mint_a_account = mint_accounts[0].as_ref().ok_or(anyhow!("Token A mint account not found"))?;
     let mint_b_account = mint_accounts[1].as_ref().ok_or(anyhow!("Token B mint account not found"))?;
 
@@ -87,6 +81,23 @@ pub fn open_and_increase_tuna_lp_position_fusion_instructions(
     additional_signers.push(Keypair::new());
     let position_mint = additional_signers[0].pubkey();
 
+    let tuna_position_address = get_tuna_liquidity_position_address(&position_mint).0;
+    let mut created_accounts = vec![
+        position_mint,
+        tuna_position_address,
+        get_position_address(&position_mint)?.0,
+        get_associated_token_address_with_program_id(&tuna_position_address, &position_mint, &spl_token_2022::ID),
+        get_associated_token_address_with_program_id(&tuna_position_address, &mint_a_address, &mint_a_account.owner),
+        get_associated_token_address_with_program_id(&tuna_position_address, &mint_b_address, &mint_b_account.owner),
+    ];
+
+    // The position token accounts are closed together with the position.
+    let refundable_rent = rent.minimum_balance(TunaLpPosition::LEN)
+        + rent.minimum_balance(Position::LEN)
+        + rent.minimum_balance(POSITION_NFT_TOKEN_ACCOUNT_LEN)
+        + rent.minimum_balance(get_associated_token_account_len(mint_a_account)?)
+        + rent.minimum_balance(get_associated_token_account_len(mint_b_account)?);
+
     instructions.extend(authority_ata_a_instructions.create);
     instructions.extend(authority_ata_b_instructions.create);
     instructions.push(create_associated_token_account_idempotent(
@@ -109,7 +120,9 @@ pub fn open_and_increase_tuna_lp_position_fusion_instructions(
     let lower_tick_array_address = get_tick_array_address(&fusion_pool_address, lower_tick_array_start_index)?.0;
     let upper_tick_array_address = get_tick_array_address(&fusion_pool_address, upper_tick_array_start_index)?.0;
 
-    let tick_array_infos = rpc.get_multiple_accounts(&[lower_tick_array_address.into(), upper_tick_array_address.into()])?;
+    let tick_array_infos = rpc
+        .get_multiple_accounts(&[lower_tick_array_address.into(), upper_tick_array_address.into()])
+        .await?;
 
     if tick_array_infos[0].is_none() {
         instructions.push(
@@ -123,6 +136,7 @@ pub fn open_and_increase_tuna_lp_position_fusion_instructions(
                 start_tick_index: lower_tick_array_start_index,
             }),
         );
+        created_accounts.push(lower_tick_array_address);
         non_refundable_rent += rent.minimum_balance(TickArray::LEN);
     }
 
@@ -138,6 +152,7 @@ pub fn open_and_increase_tuna_lp_position_fusion_instructions(
                 start_tick_index: upper_tick_array_start_index,
             }),
         );
+        created_accounts.push(upper_tick_array_address);
         non_refundable_rent += rent.minimum_balance(TickArray::LEN);
     }
 
@@ -158,12 +173,15 @@ pub fn open_and_increase_tuna_lp_position_fusion_instructions(
     instructions.extend(authority_ata_a_instructions.cleanup);
     instructions.extend(authority_ata_b_instructions.cleanup);
 
-    Ok(OpenAndIncreaseTunaLpPositionInstruction {
-        position_mint,
-        instructions,
+    Ok(TxPlan {
         additional_signers,
-        initialization_cost: non_refundable_rent,
-    })
+        position_mint: Some(position_mint),
+        non_refundable_rent,
+        refundable_rent,
+        ..TxPlan::from(instructions)
+    }
+    .with_accounts(created_accounts, vec![])
+    .with_market_lookup_table(&market.data))
 }
 
 pub fn open_and_increase_tuna_lp_position_fusion_instruction(
@@ -241,7 +259,7 @@ pub fn open_and_increase_tuna_lp_position_fusion_instruction(
             tick_upper_index: args.tick_upper_index,
             lower_limit_order_sqrt_price: args.lower_limit_order_sqrt_price,
             upper_limit_order_sqrt_price: args.upper_limit_order_sqrt_price,
-            flags: args.flags,
+            flags: args.flags.bits(),
             collateral_a: args.collateral_a,
             collateral_b: args.collateral_b,
             borrow_a: args.borrow_a,
@@ -0,0 +1,269 @@
+use crate::accounts::{TunaConfig, TunaSpotPosition, Vault};
+use crate::fetcher::{block_on, fetch_all_vault, fetch_market, fetch_tuna_config, AccountFetcher, SyncAccountFetcher};
+use crate::instructions::{OpenAndIncreaseTunaSpotPositionJupiter, OpenAndIncreaseTunaSpotPositionJupiterInstructionArgs};
+use crate::types::{AccountsType, PoolToken, RemainingAccountsInfo, RemainingAccountsSlice};
+use crate::utils::{
+    get_associated_token_account_len, get_create_ata_instructions, get_increase_spot_position_swap_input_amount, get_jupiter_route_accounts,
+    validate_jupiter_route_accounts, JupiterRoute,
+};
+use crate::{get_market_address, get_tuna_config_address, get_tuna_spot_position_address, TxPlan, JUPITER_PROGRAM_ID};
+use anyhow::{anyhow, Result};
+use solana_instruction::{AccountMeta, Instruction};
+use solana_pubkey::Pubkey;
+use solana_sdk_ids::system_program;
+use spl_associated_token_account::get_associated_token_address_with_program_id;
+use spl_associated_token_account::instruction::create_associated_token_account_idempotent;
+
+pub struct OpenAndIncreaseTunaSpotPositionJupiterArgs {
+    pub position_token: PoolToken,
+    pub collateral_token: PoolToken,
+    pub collateral_amount: u64,
+    pub borrow_amount: u64,
+}
+
+pub fn open_and_increase_tuna_spot_position_jupiter_instructions(
+    rpc: &impl SyncAccountFetcher,
+    authority: &Pubkey,
+    pool_address: &Pubkey,
+    jupiter_route: &JupiterRoute,
+    jupiter_intermediate_token_accounts_and_programs: Vec<AccountMeta>,
+    args: OpenAndIncreaseTunaSpotPositionJupiterArgs,
+) -> Result<TxPlan> {
+    block_on(open_and_increase_tuna_spot_position_jupiter_instructions_async(
+        rpc,
+        authority,
+        pool_address,
+        jupiter_route,
+        jupiter_intermediate_token_accounts_and_programs,
+        args,
+    ))
+}
+
+pub async fn open_and_increase_tuna_spot_position_jupiter_instructions_async(
+    rpc: &impl AccountFetcher,
+    authority: &Pubkey,
+    pool_address: &Pubkey,
+    jupiter_route: &JupiterRoute,
+    jupiter_intermediate_token_accounts_and_programs: Vec<AccountMeta>,
+    args: OpenAndIncreaseTunaSpotPositionJupiterArgs,
+) -> Result<TxPlan> {
+    let rent = rpc.get_rent().await?;
+
+    let tuna_position_address = get_tuna_spot_position_address(authority, pool_address).0;
+
+    let tuna_config = fetch_tuna_config(rpc, &get_tuna_config_address().0).await?;
+
+    let market_address = get_market_address(pool_address).0;
+    let market = fetch_market(rpc, &market_address).await?;
+
+    let vaults = fetch_all_vault(rpc, &[market.data.vault_a, market.data.vault_b]).await?;
+    let (vault_a, vault_b) = (&vaults[0], &vaults[1]);
+    let mint_a_address = vault_a.data.mint;
+    let mint_b_address = vault_b.data.mint;
+
+    let mint_accounts = rpc.get_multiple_accounts(&[mint_a_address, mint_b_address]).await?;
+    let mint_a_account = mint_accounts[0].as_ref().ok_or(anyhow!("Token A mint account not found"))?;
+    let mint_b_account = mint_accounts[1].as_ref().ok_or(anyhow!("Token B mint account not found"))?;
+
+    let (collateral_token_mint_address, collateral_token_mint_account) = if args.collateral_token == PoolToken::A {
+        (mint_a_address, mint_a_account)
+    } else {
+        (mint_b_address, mint_b_account)
+    };
+
+    // The route swaps the other token to the position token.
+    let (source_mint, source_token_program, destination_mint, destination_token_program) = if args.position_token == PoolToken::A {
+        (&mint_b_address, &mint_b_account.owner, &mint_a_address, &mint_a_account.owner)
+    } else {
+        (&mint_a_address, &mint_a_account.owner, &mint_b_address, &mint_b_account.owner)
+    };
+    let jupiter_route_accounts = get_jupiter_route_accounts(
+        &jupiter_route.instruction,
+        &tuna_position_address,
+        source_mint,
+        source_token_program,
+        destination_mint,
+        destination_token_program,
+    )?;
+
+    let swap_input_amount = get_increase_spot_position_swap_input_amount(
+        &market.data,
+        args.position_token,
+        args.collateral_token,
+        args.collateral_amount,
+        args.borrow_amount,
+    )?;
+    validate_jupiter_route_accounts(
+        &jupiter_route.instruction,
+        &jupiter_route_accounts,
+        &tuna_position_address,
+        &get_associated_token_address_with_program_id(&tuna_position_address, source_mint, source_token_program),
+        &get_associated_token_address_with_program_id(&tuna_position_address, destination_mint, destination_token_program),
+        swap_input_amount,
+        false,
+    )?;
+
+    let mut instructions = vec![];
+
+    let authority_ata_instructions = get_create_ata_instructions(
+        &collateral_token_mint_address,
+        authority,
+        authority,
+        &collateral_token_mint_account.owner,
+        args.collateral_amount,
+    );
+    instructions.extend(authority_ata_instructions.create);
+
+    instructions.push(create_associated_token_account_idempotent(
+        authority,
+        &tuna_config.data.fee_recipient,
+        &mint_a_address,
+        &mint_a_account.owner,
+    ));
+
+    instructions.push(create_associated_token_account_idempotent(
+        authority,
+        &tuna_config.data.fee_recipient,
+        &mint_b_address,
+        &mint_b_account.owner,
+    ));
+
+    instructions.push(open_and_increase_tuna_spot_position_jupiter_instruction(
+        authority,
+        &tuna_config.data,
+        &vault_a.address,
+        &vault_a.data,
+        &vault_b.address,
+        &vault_b.data,
+        pool_address,
+        &mint_a_account.owner,
+        &mint_b_account.owner,
+        args.collateral_token == PoolToken::A,
+        args.collateral_token == PoolToken::B,
+        jupiter_route_accounts,
+        jupiter_intermediate_token_accounts_and_programs,
+        jupiter_route.instruction.data.clone(),
+        args,
+    ));
+
+    instructions.extend(authority_ata_instructions.cleanup);
+
+    let created_accounts = vec![
+        tuna_position_address,
+        get_associated_token_address_with_program_id(&tuna_position_address, &mint_a_address, &mint_a_account.owner),
+        get_associated_token_address_with_program_id(&tuna_position_address, &mint_b_address, &mint_b_account.owner),
+    ];
+
+    // The position token accounts are closed together with the position.
+    let refundable_rent = rent.minimum_balance(TunaSpotPosition::LEN)
+        + rent.minimum_balance(get_associated_token_account_len(mint_a_account)?)
+        + rent.minimum_balance(get_associated_token_account_len(mint_b_account)?);
+
+    Ok(TxPlan {
+        address_lookup_table_addresses: jupiter_route.address_lookup_table_addresses.clone(),
+        refundable_rent,
+        ..TxPlan::from(instructions)
+    }
+    .with_accounts(created_accounts, vec![])
+    .with_market_lookup_table(&market.data))
+}
+
+pub fn open_and_increase_tuna_spot_position_jupiter_instruction(
+    authority: &Pubkey,
+    tuna_config: &TunaConfig,
+    vault_a_address: &Pubkey,
+    vault_a: &Vault,
+    vault_b_address: &Pubkey,
+    vault_b: &Vault,
+    pool_address: &Pubkey,
+    token_program_a: &Pubkey,
+    token_program_b: &Pubkey,
+    set_tuna_position_owner_ata_a: bool,
+    set_tuna_position_owner_ata_b: bool,
+    jupiter_route_accounts: Vec<AccountMeta>,
+    jupiter_intermediate_token_accounts_and_programs: Vec<AccountMeta>,
+    jupiter_route_data: Vec<u8>,
+    args: OpenAndIncreaseTunaSpotPositionJupiterArgs,
+) -> Instruction {
+    let mint_a = vault_a.mint;
+    let mint_b = vault_b.mint;
+
+    let tuna_config_address = get_tuna_config_address().0;
+    let market_address = get_market_address(pool_address).0;
+    let tuna_position_address = get_tuna_spot_position_address(authority, pool_address).0;
+    let tuna_position_owner_ata_a = get_associated_token_address_with_program_id(authority, &mint_a, token_program_a);
+    let tuna_position_owner_ata_b = get_associated_token_address_with_program_id(authority, &mint_b, token_program_b);
+
+    let ix_builder = OpenAndIncreaseTunaSpotPositionJupiter {
+        authority: *authority,
+        tuna_config: tuna_config_address,
+        mint_a,
+        mint_b,
+        token_program_a: *token_program_a,
+        token_program_b: *token_program_b,
+        market: market_address,
+        vault_a: *vault_a_address,
+        vault_b: *vault_b_address,
+        vault_a_ata: get_associated_token_address_with_program_id(vault_a_address, &mint_a, token_program_a),
+        vault_b_ata: get_associated_token_address_with_program_id(vault_b_address, &mint_b, token_program_b),
+        tuna_position: tuna_position_address,
+        tuna_position_ata_a: get_associated_token_address_with_program_id(&tuna_position_address, &mint_a, token_program_a),
+        tuna_position_ata_b: get_associated_token_address_with_program_id(&tuna_position_address, &mint_b, token_program_b),
+        tuna_position_owner_ata_a: if set_tuna_position_owner_ata_a {
+            Some(tuna_position_owner_ata_a)
+        } else {
+            None
+        },
+        tuna_position_owner_ata_b: if set_tuna_position_owner_ata_b {
+            Some(tuna_position_owner_ata_b)
+        } else {
+            None
+        },
+        fee_recipient_ata_a: get_associated_token_address_with_program_id(&tuna_config.fee_recipient, &mint_a, token_program_a),
+        fee_recipient_ata_b: get_associated_token_address_with_program_id(&tuna_config.fee_recipient, &mint_b, token_program_b),
+        oracle_price_update_a: vault_a.oracle_price_update,
+        oracle_price_update_b: vault_b.oracle_price_update,
+        pool: *pool_address,
+        jupiter_program: JUPITER_PROGRAM_ID,
+        memo_program: spl_memo::ID,
+        system_program: system_program::ID,
+        associated_token_program: spl_associated_token_account::ID,
+    };
+
+    let mut remaining_accounts_slices = vec![];
+    let mut remaining_accounts = vec![];
+
+    remaining_accounts_slices.push(RemainingAccountsSlice {
+        accounts_type: AccountsType::JupiterRoute,
+        length: jupiter_route_accounts.len() as u8,
+    });
+
+    if !jupiter_intermediate_token_accounts_and_programs.is_empty() {
+        remaining_accounts_slices.push(RemainingAccountsSlice {
+            accounts_type: AccountsType::JupiterIntermediateTokenAccounts,
+            length: jupiter_intermediate_token_accounts_and_programs.len() as u8,
+        });
+    }
+
+    for account in jupiter_route_accounts {
+        remaining_accounts.push(account);
+    }
+
+    for account in jupiter_intermediate_token_accounts_and_programs {
+        remaining_accounts.push(account);
+    }
+
+    ix_builder.instruction_with_remaining_accounts(
+        OpenAndIncreaseTunaSpotPositionJupiterInstructionArgs {
+            position_token: args.position_token,
+            collateral_token: args.collateral_token,
+            collateral_amount: args.collateral_amount,
+            borrow_amount: args.borrow_amount,
+            jupiter_route_data,
+            remaining_accounts_info: RemainingAccountsInfo {
+                slices: remaining_accounts_slices,
+            },
+        },
+        &remaining_accounts,
+    )
+}
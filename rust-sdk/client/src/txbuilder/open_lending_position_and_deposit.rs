@@ -1,30 +1,46 @@
-use crate::accounts::fetch_maybe_lending_position;
+use crate::accounts::LendingPosition;
+use crate::fetcher::{block_on, fetch_maybe_lending_position, AccountFetcher, SyncAccountFetcher};
 use crate::open_lending_position_v2::open_lending_position_v2_instruction;
-use crate::{deposit_instructions, get_lending_position_address, open_lending_position_instruction, MaybeAccount};
+use crate::{deposit_instructions_async, get_lending_position_address, open_lending_position_instruction, MaybeAccount, TxPlan};
 use anyhow::Result;
-use solana_client::rpc_client::RpcClient;
-use solana_instruction::Instruction;
 use solana_pubkey::Pubkey;
 
 pub fn open_lending_position_and_deposit_instructions(
-    rpc: &RpcClient,
+    rpc: &impl SyncAccountFetcher,
     authority: &Pubkey,
     mint: &Pubkey,
     vault: Option<&Pubkey>,
     amount: u64,
-) -> Result<Vec<Instruction>> {
+) -> Result<TxPlan> {
+    block_on(open_lending_position_and_deposit_instructions_async(rpc, authority, mint, vault, amount))
+}
+
+pub async fn open_lending_position_and_deposit_instructions_async(
+    rpc: &impl AccountFetcher,
+    authority: &Pubkey,
+    mint: &Pubkey,
+    vault: Option<&Pubkey>,
+    amount: u64,
+) -> Result<TxPlan> {
     let lending_position_address = get_lending_position_address(authority, vault.unwrap_or(mint)).0;
 
-    match fetch_maybe_lending_position(rpc, &lending_position_address)? {
-        MaybeAccount::Exists(_) => Ok(deposit_instructions(rpc, authority, mint, vault, amount)?),
+    match fetch_maybe_lending_position(rpc, &lending_position_address).await? {
+        MaybeAccount::Exists(_) => deposit_instructions_async(rpc, authority, mint, vault, amount).await,
         MaybeAccount::NotFound(_) => {
+            let rent = rpc.get_rent().await?;
+
             let mut instructions = vec![if vault.is_some() {
                 open_lending_position_v2_instruction(authority, mint, vault.unwrap())
             } else {
                 open_lending_position_instruction(authority, mint)
             }];
-            instructions.extend(deposit_instructions(rpc, authority, mint, vault, amount)?);
-            Ok(instructions)
+            instructions.extend(deposit_instructions_async(rpc, authority, mint, vault, amount).await?.instructions);
+
+            Ok(TxPlan {
+                refundable_rent: rent.minimum_balance(LendingPosition::LEN),
+                ..TxPlan::from(instructions)
+            }
+            .with_accounts(vec![lending_position_address], vec![]))
         }
     }
 }
@@ -1,25 +1,37 @@
+use crate::fetcher::{block_on, fetch_whirlpool, AccountFetcher, SyncAccountFetcher};
 use crate::instructions::{OpenTunaLpPositionOrca, OpenTunaLpPositionOrcaInstructionArgs};
-use crate::{get_market_address, get_tuna_liquidity_position_address, WP_NFT_UPDATE_AUTH};
+use crate::{get_market_address, get_tuna_liquidity_position_address, TunaPositionFlags, WP_NFT_UPDATE_AUTH};
 use anyhow::{anyhow, Result};
-use orca_whirlpools_client::{fetch_whirlpool, get_position_address};
-use solana_client::rpc_client::RpcClient;
+use orca_whirlpools_client::get_position_address;
 use solana_instruction::Instruction;
 use solana_pubkey::Pubkey;
 use solana_sdk_ids::system_program;
 use spl_associated_token_account::get_associated_token_address_with_program_id;
 
 pub fn open_tuna_lp_position_orca_instruction(
-    rpc: &RpcClient,
+    rpc: &impl SyncAccountFetcher,
     authority: &Pubkey,
     position_mint: &Pubkey,
     whirlpool: &Pubkey,
     args: OpenTunaLpPositionOrcaInstructionArgs,
 ) -> Result<Instruction> {
-    let whirlpool = fetch_whirlpool(rpc, &whirlpool)?;
+    block_on(open_tuna_lp_position_orca_instruction_async(rpc, authority, position_mint, whirlpool, args))
+}
+
+pub async fn open_tuna_lp_position_orca_instruction_async(
+    rpc: &impl AccountFetcher,
+    authority: &Pubkey,
+    position_mint: &Pubkey,
+    whirlpool: &Pubkey,
+    args: OpenTunaLpPositionOrcaInstructionArgs,
+) -> Result<Instruction> {
+    TunaPositionFlags::from_bits(args.flags)?.validate(false)?;
+
+    let whirlpool = fetch_whirlpool(rpc, &whirlpool).await?;
     let mint_a_address = whirlpool.data.token_mint_a;
     let mint_b_address = whirlpool.data.token_mint_b;
 
-    let mint_accounts = rpc.get_multiple_accounts(&[mint_a_address.into(), mint_b_address.into()])?;
+    let mint_accounts = rpc.get_multiple_accounts(&[mint_a_address.into(), mint_b_address.into()]).await?;
     let mint_a_account = mint_accounts[0].as_ref().ok_or(anyhow!("Token A mint account not found"))?;
     let mint_b_account = mint_accounts[1].as_ref().ok_or(anyhow!("Token B mint account not found"))?;
 
@@ -1,10 +1,12 @@
-use crate::get_tuna_spot_position_address;
+use crate::accounts::TunaSpotPosition;
+use crate::fetcher::{block_on, AccountFetcher, SyncAccountFetcher};
 use crate::instructions::{OpenTunaSpotPosition, OpenTunaSpotPositionInstructionArgs};
 use crate::types::PoolToken;
+use crate::utils::get_associated_token_account_len;
+use crate::{get_tuna_spot_position_address, TxPlan};
 use anyhow::{anyhow, Result};
 use fusionamm_client::{FusionPool, FUSIONAMM_ID};
 use orca_whirlpools_client::{Whirlpool, WHIRLPOOL_ID};
-use solana_client::rpc_client::RpcClient;
 use solana_instruction::Instruction;
 use solana_pubkey::Pubkey;
 use solana_sdk_ids::system_program;
@@ -20,12 +22,23 @@ impl Default for OpenTunaSpotPositionInstructionArgs {
 }
 
 pub fn open_tuna_spot_position_instructions(
-    rpc: &RpcClient,
+    rpc: &impl SyncAccountFetcher,
     authority: &Pubkey,
     pool_address: &Pubkey,
     args: OpenTunaSpotPositionInstructionArgs,
-) -> Result<Vec<Instruction>> {
-    let pool_account = rpc.get_account(pool_address)?;
+) -> Result<TxPlan> {
+    block_on(open_tuna_spot_position_instructions_async(rpc, authority, pool_address, args))
+}
+
+pub async fn open_tuna_spot_position_instructions_async(
+    rpc: &impl AccountFetcher,
+    authority: &Pubkey,
+    pool_address: &Pubkey,
+    args: OpenTunaSpotPositionInstructionArgs,
+) -> Result<TxPlan> {
+    let rent = rpc.get_rent().await?;
+
+    let pool_account = rpc.get_account(pool_address).await?;
 
     let (mint_a_address, mint_b_address) = if pool_account.owner == FUSIONAMM_ID {
         let pool = FusionPool::from_bytes(&pool_account.data)?;
@@ -37,7 +50,7 @@ pub fn open_tuna_spot_position_instructions(
         return Err(anyhow!("Incorrect fusion or orca pool"));
     };
 
-    let mint_accounts = rpc.get_multiple_accounts(&[mint_a_address.into(), mint_b_address.into()])?;
+    let mint_accounts = rpc.get_multiple_accounts(&[mint_a_address.into(), mint_b_address.into()]).await?;
     let mint_a_account = mint_accounts[0].as_ref().ok_or(anyhow!("Token A mint account not found"))?;
     let mint_b_account = mint_accounts[1].as_ref().ok_or(anyhow!("Token B mint account not found"))?;
 
@@ -53,7 +66,23 @@ pub fn open_tuna_spot_position_instructions(
         args,
     ));
 
-    Ok(instructions)
+    let tuna_position_address = get_tuna_spot_position_address(authority, pool_address).0;
+    let created_accounts = vec![
+        tuna_position_address,
+        get_associated_token_address_with_program_id(&tuna_position_address, &mint_a_address, &mint_a_account.owner),
+        get_associated_token_address_with_program_id(&tuna_position_address, &mint_b_address, &mint_b_account.owner),
+    ];
+
+    // The position token accounts are closed together with the position.
+    let refundable_rent = rent.minimum_balance(TunaSpotPosition::LEN)
+        + rent.minimum_balance(get_associated_token_account_len(mint_a_account)?)
+        + rent.minimum_balance(get_associated_token_account_len(mint_b_account)?);
+
+    Ok(TxPlan {
+        refundable_rent,
+        ..TxPlan::from(instructions)
+    }
+    .with_accounts(created_accounts, vec![]))
 }
 
 pub fn open_tuna_spot_position_instruction(
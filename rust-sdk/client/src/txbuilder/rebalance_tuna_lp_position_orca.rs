@@ -1,50 +1,54 @@
-use crate::accounts::{fetch_all_vault, fetch_market, fetch_tuna_config, fetch_tuna_lp_position, TunaConfig, TunaLpPosition, Vault};
+use crate::accounts::{TunaConfig, TunaLpPosition, Vault};
+use crate::fetcher::{
+    block_on, fetch_all_vault, fetch_market, fetch_tuna_config, fetch_tuna_lp_position, fetch_whirlpool, AccountFetcher, SyncAccountFetcher,
+};
 use crate::instructions::{RebalanceTunaLpPositionOrca, RebalanceTunaLpPositionOrcaInstructionArgs};
 use crate::types::{AccountsType, RemainingAccountsInfo, RemainingAccountsSlice};
 use crate::utils::orca::{get_swap_tick_arrays, get_tick_arrays_for_rebalanced_position};
-use crate::{get_market_address, get_tuna_config_address, get_tuna_liquidity_position_address, RebalancePositionInstruction};
+use crate::{get_market_address, get_tuna_config_address, get_tuna_liquidity_position_address, TxPlan};
 use anyhow::{anyhow, Result};
 use orca_whirlpools_client::{
-    fetch_whirlpool, get_oracle_address, get_position_address, get_tick_array_address, DynamicTickArray, InitializeDynamicTickArray,
+    get_oracle_address, get_position_address, get_tick_array_address, DynamicTickArray, InitializeDynamicTickArray,
     InitializeDynamicTickArrayInstructionArgs, Whirlpool,
 };
 use orca_whirlpools_core::get_tick_array_start_tick_index;
-use solana_client::rpc_client::RpcClient;
 use solana_instruction::{AccountMeta, Instruction};
 use solana_pubkey::Pubkey;
 use solana_sdk_ids::system_program;
-use solana_sysvar::rent::Rent;
-use solana_sysvar::slot_hashes::SysvarId;
 use spl_associated_token_account::get_associated_token_address_with_program_id;
 use spl_associated_token_account::instruction::create_associated_token_account_idempotent;
 
-pub fn rebalance_tuna_lp_position_orca_instructions(
-    rpc: &RpcClient,
+pub fn rebalance_tuna_lp_position_orca_instructions(rpc: &impl SyncAccountFetcher, authority: &Pubkey, position_mint: &Pubkey) -> Result<TxPlan> {
+    block_on(rebalance_tuna_lp_position_orca_instructions_async(rpc, authority, position_mint))
+}
+
+pub async fn rebalance_tuna_lp_position_orca_instructions_async(
+    rpc: &impl AccountFetcher,
     authority: &Pubkey,
     position_mint: &Pubkey,
-) -> Result<RebalancePositionInstruction> {
-    let rent = rpc.get_account(&Rent::id())?;
-    let rent: Rent = bincode::deserialize(&rent.data)?;
+) -> Result<TxPlan> {
+    let rent = rpc.get_rent().await?;
 
     let mut instructions = vec![];
     let mut non_refundable_rent: u64 = 0;
+    let mut created_accounts = vec![];
 
-    let tuna_config = fetch_tuna_config(rpc, &get_tuna_config_address().0)?;
-    let tuna_position = fetch_tuna_lp_position(&rpc, &get_tuna_liquidity_position_address(&position_mint).0)?;
+    let tuna_config = fetch_tuna_config(rpc, &get_tuna_config_address().0).await?;
+    let tuna_position = fetch_tuna_lp_position(rpc, &get_tuna_liquidity_position_address(&position_mint).0).await?;
 
-    let whirlpool = fetch_whirlpool(rpc, &tuna_position.data.pool)?;
+    let whirlpool = fetch_whirlpool(rpc, &tuna_position.data.pool).await?;
     let mint_a_address = whirlpool.data.token_mint_a;
     let mint_b_address = whirlpool.data.token_mint_b;
 
     let market_address = get_market_address(&tuna_position.data.pool).0;
-    let market = fetch_market(&rpc, &market_address)?;
+    let market = fetch_market(rpc, &market_address).await?;
 
-    let vaults = fetch_all_vault(&rpc, &[market.data.vault_a, market.data.vault_b])?;
+    let vaults = fetch_all_vault(rpc, &[market.data.vault_a, market.data.vault_b]).await?;
     let (vault_a, vault_b) = (&vaults[0], &vaults[1]);
 
     let all_mint_addresses = vec![mint_a_address, mint_b_address];
 
-    let mint_accounts = rpc.get_multiple_accounts(all_mint_addresses[0..all_mint_addresses.len()].into())?;
+    let mint_accounts = rpc.get_multiple_accounts(all_mint_addresses[0..all_mint_addresses.len()].into()).await?;
     let mint_a_account = mint_accounts[0].as_ref().ok_or(anyhow!("Token A mint account not found"))?;
     let mint_b_account = mint_accounts[1].as_ref().ok_or(anyhow!("Token B mint account not found"))?;
 
@@ -56,7 +60,9 @@ pub fn rebalance_tuna_lp_position_orca_instructions(
         tuna_position.data.tick_upper_index,
     );
 
-    let tick_array_infos = rpc.get_multiple_accounts(&[secondary_tick_arrays[0].0, secondary_tick_arrays[1].0])?;
+    let tick_array_infos = rpc
+        .get_multiple_accounts(&[secondary_tick_arrays[0].0, secondary_tick_arrays[1].0])
+        .await?;
 
     if tick_array_infos[0].is_none() {
         instructions.push(
@@ -71,6 +77,7 @@ pub fn rebalance_tuna_lp_position_orca_instructions(
                 idempotent: false,
             }),
         );
+        created_accounts.push(secondary_tick_arrays[0].0);
         non_refundable_rent += rent.minimum_balance(DynamicTickArray::MIN_LEN);
     }
 
@@ -87,6 +94,7 @@ pub fn rebalance_tuna_lp_position_orca_instructions(
                 idempotent: false,
             }),
         );
+        created_accounts.push(secondary_tick_arrays[1].0);
         non_refundable_rent += rent.minimum_balance(DynamicTickArray::MIN_LEN);
     }
 
@@ -117,10 +125,12 @@ pub fn rebalance_tuna_lp_position_orca_instructions(
         &mint_b_account.owner,
     ));
 
-    Ok(RebalancePositionInstruction {
-        instructions,
-        initialization_cost: non_refundable_rent,
-    })
+    Ok(TxPlan {
+        non_refundable_rent,
+        ..TxPlan::from(instructions)
+    }
+    .with_accounts(created_accounts, vec![])
+    .with_market_lookup_table(&market.data))
 }
 
 pub fn rebalance_tuna_lp_position_orca_instruction(
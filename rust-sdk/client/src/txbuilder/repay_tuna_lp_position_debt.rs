@@ -0,0 +1,148 @@
+use crate::accounts::{TunaLpPosition, Vault};
+use crate::fetcher::{block_on, fetch_all_vault, fetch_market, fetch_tuna_lp_position, AccountFetcher, SyncAccountFetcher};
+use crate::instructions::{RepayTunaLpPositionDebt, RepayTunaLpPositionDebtInstructionArgs};
+use crate::utils::get_create_ata_instructions;
+use crate::{get_market_address, get_tuna_liquidity_position_address, TxPlan};
+use anyhow::{anyhow, Result};
+use defituna_core::fixed::Rounding;
+use defituna_core::RepayLpPositionDebtQuoteResult;
+use solana_instruction::Instruction;
+use solana_pubkey::Pubkey;
+use solana_sdk_ids::system_program;
+use spl_associated_token_account::get_associated_token_address_with_program_id;
+
+#[derive(Default)]
+pub struct RepayTunaLpPositionDebtArgs {
+    /// The amount of token A to repay. Capped at the current position debt.
+    pub collateral_funds_a: u64,
+    /// The amount of token B to repay. Capped at the current position debt.
+    pub collateral_funds_b: u64,
+}
+
+impl From<RepayLpPositionDebtQuoteResult> for RepayTunaLpPositionDebtArgs {
+    fn from(quote: RepayLpPositionDebtQuoteResult) -> Self {
+        Self {
+            collateral_funds_a: quote.repay_a,
+            collateral_funds_b: quote.repay_b,
+        }
+    }
+}
+
+pub fn repay_tuna_lp_position_debt_instructions(
+    rpc: &impl SyncAccountFetcher,
+    authority: &Pubkey,
+    position_mint: &Pubkey,
+    args: impl Into<RepayTunaLpPositionDebtArgs>,
+) -> Result<TxPlan> {
+    block_on(repay_tuna_lp_position_debt_instructions_async(rpc, authority, position_mint, args))
+}
+
+pub async fn repay_tuna_lp_position_debt_instructions_async(
+    rpc: &impl AccountFetcher,
+    authority: &Pubkey,
+    position_mint: &Pubkey,
+    args: impl Into<RepayTunaLpPositionDebtArgs>,
+) -> Result<TxPlan> {
+    let args = args.into();
+
+    let tuna_position = fetch_tuna_lp_position(rpc, &get_tuna_liquidity_position_address(position_mint).0).await?;
+
+    let clock = rpc.get_clock().await?;
+
+    let market_address = get_market_address(&tuna_position.data.pool).0;
+    let market = fetch_market(rpc, &market_address).await?;
+
+    let vaults = fetch_all_vault(rpc, &[market.data.vault_a, market.data.vault_b]).await?;
+    let (mut vault_a, mut vault_b) = (vaults[0].clone(), vaults[1].clone());
+
+    // Repay amounts are capped at the debt with the interest accrued up to now.
+    vault_a.data.accrue_interest(clock.unix_timestamp as u64)?;
+    vault_b.data.accrue_interest(clock.unix_timestamp as u64)?;
+    let debt_a = vault_a.data.calculate_borrowed_funds(tuna_position.data.loan_shares_a, Rounding::Up)?;
+    let debt_b = vault_b.data.calculate_borrowed_funds(tuna_position.data.loan_shares_b, Rounding::Up)?;
+
+    let args = RepayTunaLpPositionDebtArgs {
+        collateral_funds_a: args.collateral_funds_a.min(debt_a),
+        collateral_funds_b: args.collateral_funds_b.min(debt_b),
+    };
+
+    if args.collateral_funds_a == 0 && args.collateral_funds_b == 0 {
+        return Err(anyhow!("The position has no debt to repay"));
+    }
+
+    let mint_a_address = tuna_position.data.mint_a;
+    let mint_b_address = tuna_position.data.mint_b;
+
+    let mint_accounts = rpc.get_multiple_accounts(&[mint_a_address, mint_b_address]).await?;
+    let mint_a_account = mint_accounts[0].as_ref().ok_or(anyhow!("Token A mint account not found"))?;
+    let mint_b_account = mint_accounts[1].as_ref().ok_or(anyhow!("Token B mint account not found"))?;
+
+    let authority_ata_a_instructions =
+        get_create_ata_instructions(&mint_a_address, authority, authority, &mint_a_account.owner, args.collateral_funds_a);
+    let authority_ata_b_instructions =
+        get_create_ata_instructions(&mint_b_address, authority, authority, &mint_b_account.owner, args.collateral_funds_b);
+
+    let mut instructions = vec![];
+    instructions.extend(authority_ata_a_instructions.create);
+    instructions.extend(authority_ata_b_instructions.create);
+    instructions.push(repay_tuna_lp_position_debt_instruction(
+        authority,
+        &tuna_position.data,
+        &vault_a.address,
+        &vault_a.data,
+        &vault_b.address,
+        &vault_b.data,
+        &mint_a_account.owner,
+        &mint_b_account.owner,
+        args,
+    ));
+    instructions.extend(authority_ata_a_instructions.cleanup);
+    instructions.extend(authority_ata_b_instructions.cleanup);
+
+    Ok(TxPlan::from(instructions).with_market_lookup_table(&market.data))
+}
+
+pub fn repay_tuna_lp_position_debt_instruction(
+    authority: &Pubkey,
+    tuna_position: &TunaLpPosition,
+    vault_a_address: &Pubkey,
+    vault_a: &Vault,
+    vault_b_address: &Pubkey,
+    vault_b: &Vault,
+    token_program_a: &Pubkey,
+    token_program_b: &Pubkey,
+    args: RepayTunaLpPositionDebtArgs,
+) -> Instruction {
+    let mint_a = tuna_position.mint_a;
+    let mint_b = tuna_position.mint_b;
+
+    assert_eq!(vault_a.mint, mint_a);
+    assert_eq!(vault_b.mint, mint_b);
+
+    let tuna_position_address = get_tuna_liquidity_position_address(&tuna_position.position_mint).0;
+
+    let ix_builder = RepayTunaLpPositionDebt {
+        authority: *authority,
+        mint_a,
+        mint_b,
+        market: get_market_address(&tuna_position.pool).0,
+        vault_a: *vault_a_address,
+        vault_b: *vault_b_address,
+        vault_a_ata: get_associated_token_address_with_program_id(vault_a_address, &mint_a, token_program_a),
+        vault_b_ata: get_associated_token_address_with_program_id(vault_b_address, &mint_b, token_program_b),
+        tuna_position: tuna_position_address,
+        tuna_position_ata_a: get_associated_token_address_with_program_id(&tuna_position_address, &mint_a, token_program_a),
+        tuna_position_ata_b: get_associated_token_address_with_program_id(&tuna_position_address, &mint_b, token_program_b),
+        tuna_position_owner_ata_a: get_associated_token_address_with_program_id(authority, &mint_a, token_program_a),
+        tuna_position_owner_ata_b: get_associated_token_address_with_program_id(authority, &mint_b, token_program_b),
+        token_program_a: *token_program_a,
+        token_program_b: *token_program_b,
+        memo_program: spl_memo::ID,
+        system_program: system_program::ID,
+    };
+
+    ix_builder.instruction(RepayTunaLpPositionDebtInstructionArgs {
+        collateral_funds_a: args.collateral_funds_a,
+        collateral_funds_b: args.collateral_funds_b,
+    })
+}
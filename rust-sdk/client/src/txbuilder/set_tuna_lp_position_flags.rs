@@ -0,0 +1,40 @@
+use crate::fetcher::{block_on, fetch_tuna_lp_position, AccountFetcher, SyncAccountFetcher};
+use crate::instructions::{SetTunaLpPositionFlags, SetTunaLpPositionFlagsInstructionArgs};
+use crate::{get_tuna_liquidity_position_address, TunaPositionFlags, TxPlan};
+use anyhow::Result;
+use solana_instruction::Instruction;
+use solana_pubkey::Pubkey;
+
+pub fn set_tuna_lp_position_flags_instructions(
+    rpc: &impl SyncAccountFetcher,
+    authority: &Pubkey,
+    position_mint: &Pubkey,
+    flags: TunaPositionFlags,
+) -> Result<TxPlan> {
+    block_on(set_tuna_lp_position_flags_instructions_async(rpc, authority, position_mint, flags))
+}
+
+pub async fn set_tuna_lp_position_flags_instructions_async(
+    rpc: &impl AccountFetcher,
+    authority: &Pubkey,
+    position_mint: &Pubkey,
+    flags: TunaPositionFlags,
+) -> Result<TxPlan> {
+    let tuna_position_address = get_tuna_liquidity_position_address(position_mint).0;
+    let tuna_position = fetch_tuna_lp_position(rpc, &tuna_position_address).await?;
+
+    flags.validate(tuna_position.data.loan_shares_a > 0 || tuna_position.data.loan_shares_b > 0)?;
+
+    Ok(vec![set_tuna_lp_position_flags_instruction(authority, position_mint, flags)].into())
+}
+
+pub fn set_tuna_lp_position_flags_instruction(authority: &Pubkey, position_mint: &Pubkey, flags: TunaPositionFlags) -> Instruction {
+    let tuna_position_address = get_tuna_liquidity_position_address(position_mint).0;
+
+    let ix_builder = SetTunaLpPositionFlags {
+        authority: *authority,
+        tuna_position: tuna_position_address,
+    };
+
+    ix_builder.instruction(SetTunaLpPositionFlagsInstructionArgs { flags: flags.bits() })
+}
@@ -0,0 +1,76 @@
+use crate::fetcher::{block_on, fetch_tuna_lp_position, AccountFetcher, SyncAccountFetcher};
+use crate::instructions::{SetTunaLpPositionLimitOrders, SetTunaLpPositionLimitOrdersInstructionArgs};
+use crate::utils::{fetch_mint_decimals_async, fetch_pool_sqrt_price_async, get_limit_order_sqrt_prices};
+use crate::{get_tuna_liquidity_position_address, TunaPositionFlags, TxPlan};
+use anyhow::Result;
+use solana_instruction::Instruction;
+use solana_pubkey::Pubkey;
+
+#[derive(Default)]
+pub struct SetTunaLpPositionLimitOrdersArgs {
+    /// The lower limit order (stop loss) price. `None` disables the order.
+    pub lower_limit_order_price: Option<f64>,
+    /// The upper limit order (take profit) price. `None` disables the order.
+    pub upper_limit_order_price: Option<f64>,
+    /// The token the position is swapped to when the lower limit order is executed.
+    pub lower_limit_order_swap_to_token: Option<u8>,
+    /// The token the position is swapped to when the upper limit order is executed.
+    pub upper_limit_order_swap_to_token: Option<u8>,
+}
+
+pub fn set_tuna_lp_position_limit_orders_instructions(
+    rpc: &impl SyncAccountFetcher,
+    authority: &Pubkey,
+    position_mint: &Pubkey,
+    args: SetTunaLpPositionLimitOrdersArgs,
+) -> Result<TxPlan> {
+    block_on(set_tuna_lp_position_limit_orders_instructions_async(rpc, authority, position_mint, args))
+}
+
+pub async fn set_tuna_lp_position_limit_orders_instructions_async(
+    rpc: &impl AccountFetcher,
+    authority: &Pubkey,
+    position_mint: &Pubkey,
+    args: SetTunaLpPositionLimitOrdersArgs,
+) -> Result<TxPlan> {
+    let tuna_position_address = get_tuna_liquidity_position_address(position_mint).0;
+    let tuna_position = fetch_tuna_lp_position(rpc, &tuna_position_address).await?;
+
+    let sqrt_price = fetch_pool_sqrt_price_async(rpc, &tuna_position.data.pool, tuna_position.data.market_maker).await?;
+    let (decimals_a, decimals_b) = fetch_mint_decimals_async(rpc, &tuna_position.data.mint_a, &tuna_position.data.mint_b).await?;
+
+    let (lower_limit_order_sqrt_price, upper_limit_order_sqrt_price) =
+        get_limit_order_sqrt_prices(args.lower_limit_order_price, args.upper_limit_order_price, sqrt_price, decimals_a, decimals_b)?;
+
+    // Only the limit order bits of the position flags are passed to the instruction.
+    let flags = TunaPositionFlags::default()
+        .with_lower_limit_order_swap_to_token(args.lower_limit_order_swap_to_token)
+        .with_upper_limit_order_swap_to_token(args.upper_limit_order_swap_to_token);
+    flags.validate(false)?;
+
+    Ok(vec![set_tuna_lp_position_limit_orders_instruction(
+        authority,
+        position_mint,
+        SetTunaLpPositionLimitOrdersInstructionArgs {
+            lower_limit_order_sqrt_price,
+            upper_limit_order_sqrt_price,
+            swap_to_token_on_limit_order: flags.bits() as u8,
+        },
+    )]
+    .into())
+}
+
+pub fn set_tuna_lp_position_limit_orders_instruction(
+    authority: &Pubkey,
+    position_mint: &Pubkey,
+    args: SetTunaLpPositionLimitOrdersInstructionArgs,
+) -> Instruction {
+    let tuna_position_address = get_tuna_liquidity_position_address(position_mint).0;
+
+    let ix_builder = SetTunaLpPositionLimitOrders {
+        authority: *authority,
+        tuna_position: tuna_position_address,
+    };
+
+    ix_builder.instruction(args)
+}
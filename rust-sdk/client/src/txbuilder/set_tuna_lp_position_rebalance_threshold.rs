@@ -0,0 +1,19 @@
+use crate::get_tuna_liquidity_position_address;
+use crate::instructions::{SetTunaLpPositionRebalanceThreshold, SetTunaLpPositionRebalanceThresholdInstructionArgs};
+use solana_instruction::Instruction;
+use solana_pubkey::Pubkey;
+
+pub fn set_tuna_lp_position_rebalance_threshold_instruction(
+    authority: &Pubkey,
+    position_mint: &Pubkey,
+    rebalance_threshold_ticks: u32,
+) -> Instruction {
+    let tuna_position_address = get_tuna_liquidity_position_address(position_mint).0;
+
+    let ix_builder = SetTunaLpPositionRebalanceThreshold {
+        authority: *authority,
+        tuna_position: tuna_position_address,
+    };
+
+    ix_builder.instruction(SetTunaLpPositionRebalanceThresholdInstructionArgs { rebalance_threshold_ticks })
+}
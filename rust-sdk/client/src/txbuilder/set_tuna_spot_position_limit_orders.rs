@@ -0,0 +1,66 @@
+use crate::fetcher::{block_on, fetch_tuna_spot_position, AccountFetcher, SyncAccountFetcher};
+use crate::get_tuna_spot_position_address;
+use crate::instructions::{SetTunaSpotPositionLimitOrders, SetTunaSpotPositionLimitOrdersInstructionArgs};
+use crate::utils::{fetch_mint_decimals_async, fetch_pool_sqrt_price_async, get_limit_order_sqrt_prices};
+use crate::TxPlan;
+use anyhow::Result;
+use solana_instruction::Instruction;
+use solana_pubkey::Pubkey;
+
+#[derive(Default)]
+pub struct SetTunaSpotPositionLimitOrdersArgs {
+    /// The lower limit order price. `None` disables the order.
+    pub lower_limit_order_price: Option<f64>,
+    /// The upper limit order price. `None` disables the order.
+    pub upper_limit_order_price: Option<f64>,
+}
+
+pub fn set_tuna_spot_position_limit_orders_instructions(
+    rpc: &impl SyncAccountFetcher,
+    authority: &Pubkey,
+    pool_address: &Pubkey,
+    args: SetTunaSpotPositionLimitOrdersArgs,
+) -> Result<TxPlan> {
+    block_on(set_tuna_spot_position_limit_orders_instructions_async(rpc, authority, pool_address, args))
+}
+
+pub async fn set_tuna_spot_position_limit_orders_instructions_async(
+    rpc: &impl AccountFetcher,
+    authority: &Pubkey,
+    pool_address: &Pubkey,
+    args: SetTunaSpotPositionLimitOrdersArgs,
+) -> Result<TxPlan> {
+    let tuna_position_address = get_tuna_spot_position_address(authority, pool_address).0;
+    let tuna_position = fetch_tuna_spot_position(rpc, &tuna_position_address).await?;
+
+    let sqrt_price = fetch_pool_sqrt_price_async(rpc, pool_address, tuna_position.data.market_maker).await?;
+    let (decimals_a, decimals_b) = fetch_mint_decimals_async(rpc, &tuna_position.data.mint_a, &tuna_position.data.mint_b).await?;
+
+    let (lower_limit_order_sqrt_price, upper_limit_order_sqrt_price) =
+        get_limit_order_sqrt_prices(args.lower_limit_order_price, args.upper_limit_order_price, sqrt_price, decimals_a, decimals_b)?;
+
+    Ok(vec![set_tuna_spot_position_limit_orders_instruction(
+        authority,
+        pool_address,
+        SetTunaSpotPositionLimitOrdersInstructionArgs {
+            lower_limit_order_sqrt_price,
+            upper_limit_order_sqrt_price,
+        },
+    )]
+    .into())
+}
+
+pub fn set_tuna_spot_position_limit_orders_instruction(
+    authority: &Pubkey,
+    pool_address: &Pubkey,
+    args: SetTunaSpotPositionLimitOrdersInstructionArgs,
+) -> Instruction {
+    let tuna_position_address = get_tuna_spot_position_address(authority, pool_address).0;
+
+    let ix_builder = SetTunaSpotPositionLimitOrders {
+        authority: *authority,
+        tuna_position: tuna_position_address,
+    };
+
+    ix_builder.instruction(args)
+}
@@ -0,0 +1,113 @@
+use crate::accounts::Market;
+use solana_instruction::Instruction;
+use solana_keypair::Keypair;
+use solana_pubkey::Pubkey;
+
+/// The discriminator of the token program `CloseAccount` instruction. It's the same in Token-2022.
+const CLOSE_TOKEN_ACCOUNT_DISCRIMINATOR: u8 = 9;
+
+/// The result of a transaction builder.
+#[derive(Debug, Default)]
+pub struct TxPlan {
+    /// A vector of `Instruction` objects required to execute the operation.
+    pub instructions: Vec<Instruction>,
+
+    /// A vector of `Keypair` objects representing additional signers required for the instructions.
+    pub additional_signers: Vec<Keypair>,
+
+    /// The mint of the position opened by the operation. Its keypair is one of the additional signers.
+    pub position_mint: Option<Pubkey>,
+
+    /// Address lookup tables required to fit the instructions into a transaction, e.g. the one of the market and the ones
+    /// of a Jupiter route.
+    pub address_lookup_table_addresses: Vec<Pubkey>,
+
+    /// Rent of the accounts created by the operation that is never returned, e.g. initialized tick arrays. Measured in lamports.
+    pub non_refundable_rent: u64,
+
+    /// Rent of the position accounts created by the operation, including the position token accounts. It is returned when the
+    /// position is closed. Measured in lamports.
+    ///
+    /// It doesn't include the rent of the associated token accounts created idempotently, since they may already exist,
+    /// nor the rent of the position NFT mint, whose size depends on the metadata set by the AMM program.
+    pub refundable_rent: u64,
+
+    /// Accounts created by the operation.
+    ///
+    /// Associated token accounts created idempotently are listed even if they already exist.
+    /// Temporary token accounts, e.g. the WSOL ones, are listed both as created and closed.
+    pub created_accounts: Vec<Pubkey>,
+
+    /// Accounts closed by the operation.
+    pub closed_accounts: Vec<Pubkey>,
+}
+
+impl TxPlan {
+    /// Adds the accounts created or closed by the tuna and AMM programs, which aren't detected from the instructions.
+    pub(crate) fn with_accounts(mut self, created_accounts: Vec<Pubkey>, closed_accounts: Vec<Pubkey>) -> Self {
+        self.created_accounts = merge_accounts(created_accounts, self.created_accounts);
+        self.closed_accounts = merge_accounts(closed_accounts, self.closed_accounts);
+        self
+    }
+
+    /// Adds the address lookup table of the market, if the market has one. It's listed before the other lookup tables.
+    pub(crate) fn with_market_lookup_table(mut self, market: &Market) -> Self {
+        if market.address_lookup_table != Pubkey::default() {
+            self.address_lookup_table_addresses = merge_accounts(vec![market.address_lookup_table], self.address_lookup_table_addresses);
+        }
+        self
+    }
+}
+
+impl From<Vec<Instruction>> for TxPlan {
+    /// Token accounts created by the associated token account program and closed by the token programs are reported
+    /// as created and closed accounts.
+    fn from(instructions: Vec<Instruction>) -> Self {
+        let mut created_accounts = vec![];
+        let mut closed_accounts = vec![];
+
+        for instruction in &instructions {
+            if let Some(token_account) = get_created_associated_token_account(instruction) {
+                created_accounts = merge_accounts(created_accounts, vec![token_account]);
+            }
+            if let Some(token_account) = get_closed_token_account(instruction) {
+                closed_accounts = merge_accounts(closed_accounts, vec![token_account]);
+            }
+        }
+
+        Self {
+            instructions,
+            created_accounts,
+            closed_accounts,
+            ..Default::default()
+        }
+    }
+}
+
+/// Returns the account created by the associated token account program `Create` and `CreateIdempotent` instructions.
+fn get_created_associated_token_account(instruction: &Instruction) -> Option<Pubkey> {
+    if instruction.program_id != spl_associated_token_account::ID || instruction.data.first().is_some_and(|&discriminator| discriminator > 1) {
+        return None;
+    }
+    instruction.accounts.get(1).map(|account| account.pubkey)
+}
+
+/// Returns the account closed by the token program `CloseAccount` instruction.
+fn get_closed_token_account(instruction: &Instruction) -> Option<Pubkey> {
+    if instruction.program_id != spl_token::ID && instruction.program_id != spl_token_2022::ID {
+        return None;
+    }
+    if instruction.data.first() != Some(&CLOSE_TOKEN_ACCOUNT_DISCRIMINATOR) {
+        return None;
+    }
+    instruction.accounts.first().map(|account| account.pubkey)
+}
+
+fn merge_accounts(mut accounts: Vec<Pubkey>, other: Vec<Pubkey>) -> Vec<Pubkey> {
+    for account in other {
+        if !accounts.contains(&account) {
+            accounts.push(account);
+        }
+    }
+    accounts
+}
@@ -1,21 +1,32 @@
+use crate::fetcher::{block_on, AccountFetcher, SyncAccountFetcher};
 use crate::instructions::{Withdraw, WithdrawInstructionArgs};
 use crate::utils::get_create_ata_instructions;
-use crate::{get_lending_position_address, get_tuna_config_address, get_vault_address};
+use crate::{get_lending_position_address, get_tuna_config_address, get_vault_address, TxPlan};
 use anyhow::Result;
-use solana_client::rpc_client::RpcClient;
 use solana_instruction::Instruction;
 use solana_pubkey::Pubkey;
 use spl_associated_token_account::get_associated_token_address_with_program_id;
 
 pub fn withdraw_instructions(
-    rpc: &RpcClient,
+    rpc: &impl SyncAccountFetcher,
     authority: &Pubkey,
     mint: &Pubkey,
     vault: Option<&Pubkey>,
     funds: u64,
     shares: u64,
-) -> Result<Vec<Instruction>> {
-    let mint_account = rpc.get_account(mint.into())?;
+) -> Result<TxPlan> {
+    block_on(withdraw_instructions_async(rpc, authority, mint, vault, funds, shares))
+}
+
+pub async fn withdraw_instructions_async(
+    rpc: &impl AccountFetcher,
+    authority: &Pubkey,
+    mint: &Pubkey,
+    vault: Option<&Pubkey>,
+    funds: u64,
+    shares: u64,
+) -> Result<TxPlan> {
+    let mint_account = rpc.get_account(mint.into()).await?;
 
     let authority_ata_instructions = get_create_ata_instructions(&mint, authority, authority, &mint_account.owner, 0);
 
@@ -24,7 +35,7 @@ pub fn withdraw_instructions(
     instructions.push(withdraw_instruction(authority, mint, &mint_account.owner, vault, funds, shares));
     instructions.extend(authority_ata_instructions.cleanup);
 
-    Ok(instructions)
+    Ok(instructions.into())
 }
 
 pub fn withdraw_instruction(
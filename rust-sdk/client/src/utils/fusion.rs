@@ -1,3 +1,4 @@
+use defituna_core::get_rebalanced_position_tick_range;
 use fusionamm_client::get_tick_array_address;
 use fusionamm_core::{get_tick_array_start_tick_index, TICK_ARRAY_SIZE};
 use solana_pubkey::Pubkey;
@@ -22,16 +23,8 @@ pub fn get_tick_arrays_for_rebalanced_position(
     position_tick_lower_index: i32,
     position_tick_upper_index: i32,
 ) -> [(Pubkey, i32); 2] {
-    // Calculate the new position's range.
-    let position_range = position_tick_upper_index - position_tick_lower_index;
-    let half_range = position_range / (2 * tick_spacing as i32) * tick_spacing as i32;
-    let position_center = tick_current_index.div_euclid(tick_spacing as i32) * tick_spacing as i32;
-
-    let mut new_tick_lower_index = (position_center - half_range).max(orca_whirlpools_core::MIN_TICK_INDEX);
-    let new_tick_upper_index = (new_tick_lower_index + position_range).min(orca_whirlpools_core::MAX_TICK_INDEX);
-    if new_tick_upper_index == orca_whirlpools_core::MAX_TICK_INDEX {
-        new_tick_lower_index = orca_whirlpools_core::MAX_TICK_INDEX - position_range;
-    }
+    let (new_tick_lower_index, new_tick_upper_index) =
+        get_rebalanced_position_tick_range(tick_current_index, tick_spacing, position_tick_lower_index, position_tick_upper_index);
 
     let lower_tick_array_start_index = orca_whirlpools_core::get_tick_array_start_tick_index(new_tick_lower_index, tick_spacing);
     let lower_tick_array_address = get_tick_array_address(&whirlpool_address, lower_tick_array_start_index).unwrap().0;
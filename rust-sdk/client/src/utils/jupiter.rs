@@ -0,0 +1,120 @@
+use crate::accounts::Market;
+use crate::types::PoolToken;
+use crate::JUPITER_PROGRAM_ID;
+use anyhow::{anyhow, Result};
+use defituna_core::{calculate_tuna_protocol_fee, decode_jupiter_route_instruction_data, validate_jupiter_route_instruction, JupiterRouteKind};
+use solana_instruction::{AccountMeta, Instruction};
+use solana_pubkey::Pubkey;
+use spl_associated_token_account::get_associated_token_address_with_program_id;
+
+/// A swap instruction returned by the Jupiter API and the address lookup tables required to fit it into a transaction.
+pub struct JupiterRoute {
+    pub instruction: Instruction,
+    pub address_lookup_table_addresses: Vec<Pubkey>,
+}
+
+/// Returns the accounts of the Jupiter route instruction prepared to be passed to the tuna program.
+///
+/// The user transfer authority is replaced with the tuna position, and the source and destination token accounts,
+/// which are not necessarily ATAs, with the ATAs of the tuna position. The swap steps may reference the same accounts,
+/// so all their occurrences are replaced. The tuna position signs the swap via CPI, therefore all signer flags are removed.
+pub fn get_jupiter_route_accounts(
+    route_instruction: &Instruction,
+    tuna_position_address: &Pubkey,
+    source_mint: &Pubkey,
+    source_token_program: &Pubkey,
+    destination_mint: &Pubkey,
+    destination_token_program: &Pubkey,
+) -> Result<Vec<AccountMeta>> {
+    if route_instruction.program_id != JUPITER_PROGRAM_ID {
+        return Err(anyhow!("Not a Jupiter instruction"));
+    }
+
+    let route_kind = route_instruction
+        .data
+        .get(0..8)
+        .and_then(JupiterRouteKind::from_discriminator)
+        .ok_or(anyhow!("Unsupported Jupiter route instruction"))?;
+
+    let (authority_index, source_index, destination_index) = route_kind.user_account_indexes();
+    if route_instruction.accounts.len() <= authority_index.max(source_index).max(destination_index) {
+        return Err(anyhow!("Invalid Jupiter route instruction accounts"));
+    }
+
+    let user_transfer_authority = route_instruction.accounts[authority_index].pubkey;
+    let user_source_token_account = route_instruction.accounts[source_index].pubkey;
+    let user_destination_token_account = route_instruction.accounts[destination_index].pubkey;
+
+    let tuna_position_source_token_account = get_associated_token_address_with_program_id(tuna_position_address, source_mint, source_token_program);
+    let tuna_position_destination_token_account =
+        get_associated_token_address_with_program_id(tuna_position_address, destination_mint, destination_token_program);
+
+    let mut accounts = route_instruction.accounts.clone();
+
+    for account in &mut accounts {
+        if account.pubkey == user_transfer_authority {
+            account.pubkey = *tuna_position_address;
+        } else if account.pubkey == user_source_token_account {
+            account.pubkey = tuna_position_source_token_account;
+        } else if account.pubkey == user_destination_token_account {
+            account.pubkey = tuna_position_destination_token_account;
+        }
+
+        account.is_signer = false;
+    }
+
+    Ok(accounts)
+}
+
+/// Returns the amount of tokens the program swaps to the position token when a spot position is increased:
+/// the borrowed funds and the collateral provided in the other token, less the protocol fee.
+pub(crate) fn get_increase_spot_position_swap_input_amount(
+    market: &Market,
+    position_token: PoolToken,
+    collateral_token: PoolToken,
+    collateral_amount: u64,
+    borrow_amount: u64,
+) -> Result<u64> {
+    let swapped_collateral = if collateral_token == position_token { 0 } else { collateral_amount };
+    let protocol_fee = calculate_tuna_protocol_fee(swapped_collateral, borrow_amount, market.protocol_fee_on_collateral, market.protocol_fee);
+    let swap_input_amount = swapped_collateral.checked_add(borrow_amount).ok_or(anyhow!("Arithmetic overflow"))?;
+    Ok(swap_input_amount.saturating_sub(protocol_fee))
+}
+
+/// Checks the Jupiter route accounts returned by [`get_jupiter_route_accounts`] and the route data the same way the tuna program does.
+///
+/// `max_input_amount` is the amount of source tokens available for the swap. Exact input routes must swap exactly this amount,
+/// unless `partial_input` is set, in which case they may swap less.
+pub(crate) fn validate_jupiter_route_accounts(
+    route_instruction: &Instruction,
+    route_accounts: &[AccountMeta],
+    tuna_position_address: &Pubkey,
+    tuna_position_source_token_account: &Pubkey,
+    tuna_position_destination_token_account: &Pubkey,
+    max_input_amount: u64,
+    partial_input: bool,
+) -> Result<()> {
+    let route_data = decode_jupiter_route_instruction_data(&route_instruction.data).map_err(|e| anyhow!(e))?;
+    let input_amount = if partial_input && !route_data.kind.is_exact_out() {
+        route_data.in_amount.min(max_input_amount)
+    } else {
+        max_input_amount
+    };
+
+    let route_instruction = Instruction {
+        program_id: route_instruction.program_id,
+        accounts: route_accounts.to_vec(),
+        data: route_instruction.data.clone(),
+    };
+
+    validate_jupiter_route_instruction(
+        &route_instruction,
+        tuna_position_address,
+        tuna_position_source_token_account,
+        tuna_position_destination_token_account,
+        input_amount,
+    )
+    .map_err(|e| anyhow!(e))?;
+
+    Ok(())
+}
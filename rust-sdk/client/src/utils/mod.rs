@@ -1,5 +1,11 @@
 pub mod fusion;
+pub mod jupiter;
 pub mod orca;
+pub mod pool;
 pub mod token;
+pub mod transaction;
 
+pub use jupiter::*;
+pub use pool::*;
 pub use token::*;
+pub use transaction::*;
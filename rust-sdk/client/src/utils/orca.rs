@@ -1,7 +1,21 @@
-use orca_whirlpools_client::get_tick_array_address;
-use orca_whirlpools_core::{get_tick_array_start_tick_index, TICK_ARRAY_SIZE};
-use orca_whirlpools_core::{MAX_TICK_INDEX, MIN_TICK_INDEX};
+use crate::fetcher::{block_on, fetch_position, AccountFetcher, SyncAccountFetcher};
+use crate::utils::{get_create_ata_instructions, CreateATAInstructions};
+use anyhow::{anyhow, Result};
+use defituna_core::get_rebalanced_position_tick_range;
+use orca_whirlpools_client::{get_position_address, get_tick_array_address, FixedTickArray, TickArray, Whirlpool};
+use orca_whirlpools_core::{collect_rewards_quote, get_tick_array_start_tick_index, get_tick_index_in_array, TickFacade, TICK_ARRAY_SIZE};
+use solana_instruction::AccountMeta;
 use solana_pubkey::Pubkey;
+use spl_associated_token_account::get_associated_token_address_with_program_id;
+
+/// A whirlpool reward collected by the position instructions.
+#[derive(Debug, Clone, Copy)]
+pub struct RewardToCollect {
+    /// The index of the reward in the whirlpool reward infos.
+    pub reward_index: u8,
+    /// The token program of the reward mint.
+    pub reward_token_program: Pubkey,
+}
 
 pub fn get_swap_tick_arrays(tick_current_index: i32, tick_spacing: u16, whirlpool_address: &Pubkey) -> [Pubkey; 5] {
     let mut tick_array_addresses = [Pubkey::default(); 5];
@@ -23,16 +37,8 @@ pub fn get_tick_arrays_for_rebalanced_position(
     position_tick_lower_index: i32,
     position_tick_upper_index: i32,
 ) -> [(Pubkey, i32); 2] {
-    // Calculate the new position's range.
-    let position_range = position_tick_upper_index - position_tick_lower_index;
-    let half_range = position_range / (2 * tick_spacing as i32) * tick_spacing as i32;
-    let position_center = tick_current_index.div_euclid(tick_spacing as i32) * tick_spacing as i32;
-
-    let mut new_tick_lower_index = (position_center - half_range).max(MIN_TICK_INDEX);
-    let new_tick_upper_index = (new_tick_lower_index + position_range).min(MAX_TICK_INDEX);
-    if new_tick_upper_index == MAX_TICK_INDEX {
-        new_tick_lower_index = MAX_TICK_INDEX - position_range;
-    }
+    let (new_tick_lower_index, new_tick_upper_index) =
+        get_rebalanced_position_tick_range(tick_current_index, tick_spacing, position_tick_lower_index, position_tick_upper_index);
 
     let lower_tick_array_start_index = get_tick_array_start_tick_index(new_tick_lower_index, tick_spacing);
     let lower_tick_array_address = get_tick_array_address(&whirlpool_address, lower_tick_array_start_index).unwrap().0;
@@ -45,3 +51,103 @@ pub fn get_tick_arrays_for_rebalanced_position(
         (upper_tick_array_address, upper_tick_array_start_index),
     ]
 }
+
+/// Returns the indices of the whirlpool rewards owed to the position.
+pub fn fetch_owed_reward_indices(rpc: &impl SyncAccountFetcher, whirlpool: &Whirlpool, position_mint: &Pubkey) -> Result<Vec<u8>> {
+    block_on(fetch_owed_reward_indices_async(rpc, whirlpool, position_mint))
+}
+
+/// Async version of [`fetch_owed_reward_indices`].
+pub async fn fetch_owed_reward_indices_async(rpc: &impl AccountFetcher, whirlpool: &Whirlpool, position_mint: &Pubkey) -> Result<Vec<u8>> {
+    let position = fetch_position(rpc, &get_position_address(position_mint)?.0).await?;
+
+    let fetch_tick = |tick_index: i32, tick_array_info: Option<&solana_account::Account>| -> Result<TickFacade> {
+        let tick_array_info = tick_array_info.ok_or(anyhow!("Tick array account not found"))?;
+        let tick_array: FixedTickArray = TickArray::from_bytes(&tick_array_info.data)?.into();
+        let tick_array_start_index = get_tick_array_start_tick_index(tick_index, whirlpool.tick_spacing);
+        let tick_index_in_array = get_tick_index_in_array(tick_index, tick_array_start_index, whirlpool.tick_spacing).map_err(|e| anyhow!(e))?;
+        Ok(tick_array.ticks[tick_index_in_array as usize].clone().into())
+    };
+
+    let lower_tick_array_start_index = get_tick_array_start_tick_index(position.data.tick_lower_index, whirlpool.tick_spacing);
+    let upper_tick_array_start_index = get_tick_array_start_tick_index(position.data.tick_upper_index, whirlpool.tick_spacing);
+    let tick_array_infos = rpc
+        .get_multiple_accounts(&[
+            get_tick_array_address(&position.data.whirlpool, lower_tick_array_start_index)?.0,
+            get_tick_array_address(&position.data.whirlpool, upper_tick_array_start_index)?.0,
+        ])
+        .await?;
+    let lower_tick = fetch_tick(position.data.tick_lower_index, tick_array_infos[0].as_ref())?;
+    let upper_tick = fetch_tick(position.data.tick_upper_index, tick_array_infos[1].as_ref())?;
+
+    let clock = rpc.get_clock().await?;
+    // The cluster time may lag behind the last reward update.
+    let timestamp = (clock.unix_timestamp as u64).max(whirlpool.reward_last_updated_timestamp);
+
+    let rewards_quote = collect_rewards_quote(whirlpool.clone().into(), position.data.into(), lower_tick, upper_tick, timestamp, None, None, None)
+        .map_err(|e| anyhow!(e))?;
+
+    Ok((0..whirlpool.reward_infos.len())
+        .filter(|&i| whirlpool.reward_infos[i].mint != Pubkey::default() && rewards_quote.rewards[i].rewards_owed > 0)
+        .map(|i| i as u8)
+        .collect())
+}
+
+/// Returns the rewards owed to the position and the instructions that create the authority reward token accounts.
+pub fn prepare_rewards_to_collect(
+    rpc: &impl SyncAccountFetcher,
+    authority: &Pubkey,
+    whirlpool: &Whirlpool,
+    position_mint: &Pubkey,
+) -> Result<(Vec<RewardToCollect>, CreateATAInstructions)> {
+    block_on(prepare_rewards_to_collect_async(rpc, authority, whirlpool, position_mint))
+}
+
+/// Async version of [`prepare_rewards_to_collect`].
+pub async fn prepare_rewards_to_collect_async(
+    rpc: &impl AccountFetcher,
+    authority: &Pubkey,
+    whirlpool: &Whirlpool,
+    position_mint: &Pubkey,
+) -> Result<(Vec<RewardToCollect>, CreateATAInstructions)> {
+    let reward_indices = fetch_owed_reward_indices_async(rpc, whirlpool, position_mint).await?;
+    let reward_mint_addresses: Vec<Pubkey> = reward_indices.iter().map(|&i| whirlpool.reward_infos[i as usize].mint).collect();
+    let mint_accounts = rpc.get_multiple_accounts(&reward_mint_addresses).await?;
+
+    let mut rewards = vec![];
+    let mut ata_instructions = CreateATAInstructions {
+        create: vec![],
+        cleanup: vec![],
+    };
+
+    for (i, reward_index) in reward_indices.into_iter().enumerate() {
+        let reward_mint_account = mint_accounts[i].as_ref().ok_or(anyhow!("Reward mint account not found"))?;
+        let authority_ata_instructions = get_create_ata_instructions(&reward_mint_addresses[i], authority, authority, &reward_mint_account.owner, 0);
+        ata_instructions.create.extend(authority_ata_instructions.create);
+        ata_instructions.cleanup.extend(authority_ata_instructions.cleanup);
+        rewards.push(RewardToCollect {
+            reward_index,
+            reward_token_program: reward_mint_account.owner,
+        });
+    }
+
+    Ok((rewards, ata_instructions))
+}
+
+/// Returns the remaining accounts of the `AccountsType::Rewards` slice. Each reward takes four accounts.
+pub fn get_rewards_remaining_accounts(authority: &Pubkey, whirlpool: &Whirlpool, rewards: &[RewardToCollect]) -> Vec<AccountMeta> {
+    let mut accounts = Vec::with_capacity(4 * rewards.len());
+
+    for reward in rewards {
+        let reward_info = &whirlpool.reward_infos[reward.reward_index as usize];
+        accounts.push(AccountMeta::new_readonly(reward_info.mint, false));
+        accounts.push(AccountMeta::new_readonly(reward.reward_token_program, false));
+        accounts.push(AccountMeta::new(
+            get_associated_token_address_with_program_id(authority, &reward_info.mint, &reward.reward_token_program),
+            false,
+        ));
+        accounts.push(AccountMeta::new(reward_info.vault, false));
+    }
+
+    accounts
+}
@@ -0,0 +1,71 @@
+use crate::fetcher::{block_on, fetch_fusion_pool, fetch_whirlpool, AccountFetcher, SyncAccountFetcher};
+use crate::types::MarketMaker;
+use anyhow::{anyhow, Result};
+use fusionamm_core::{price_to_sqrt_price, MAX_SQRT_PRICE, MIN_SQRT_PRICE};
+use solana_pubkey::Pubkey;
+use spl_token_2022::extension::StateWithExtensions;
+use spl_token_2022::state::Mint;
+
+/// Returns the current sqrt price of an Orca or Fusion pool.
+pub fn fetch_pool_sqrt_price(rpc: &impl SyncAccountFetcher, pool_address: &Pubkey, market_maker: MarketMaker) -> Result<u128> {
+    block_on(fetch_pool_sqrt_price_async(rpc, pool_address, market_maker))
+}
+
+/// Async version of [`fetch_pool_sqrt_price`].
+pub async fn fetch_pool_sqrt_price_async(rpc: &impl AccountFetcher, pool_address: &Pubkey, market_maker: MarketMaker) -> Result<u128> {
+    let sqrt_price = match market_maker {
+        MarketMaker::Orca => fetch_whirlpool(rpc, pool_address).await?.data.sqrt_price,
+        MarketMaker::Fusion => fetch_fusion_pool(rpc, pool_address).await?.data.sqrt_price,
+    };
+    Ok(sqrt_price)
+}
+
+/// Returns the decimals of token A and token B mints.
+pub fn fetch_mint_decimals(rpc: &impl SyncAccountFetcher, mint_a: &Pubkey, mint_b: &Pubkey) -> Result<(u8, u8)> {
+    block_on(fetch_mint_decimals_async(rpc, mint_a, mint_b))
+}
+
+/// Async version of [`fetch_mint_decimals`].
+pub async fn fetch_mint_decimals_async(rpc: &impl AccountFetcher, mint_a: &Pubkey, mint_b: &Pubkey) -> Result<(u8, u8)> {
+    let mint_accounts = rpc.get_multiple_accounts(&[*mint_a, *mint_b]).await?;
+    let mint_a_account = mint_accounts[0].as_ref().ok_or(anyhow!("Token A mint account not found"))?;
+    let mint_b_account = mint_accounts[1].as_ref().ok_or(anyhow!("Token B mint account not found"))?;
+
+    let decimals_a = StateWithExtensions::<Mint>::unpack(&mint_a_account.data)?.base.decimals;
+    let decimals_b = StateWithExtensions::<Mint>::unpack(&mint_b_account.data)?.base.decimals;
+    Ok((decimals_a, decimals_b))
+}
+
+/// Converts the limit order prices to sqrt prices and checks them against the current pool price.
+/// The lower limit order must be below the current price and the upper one must be above it. `None` disables the order.
+pub fn get_limit_order_sqrt_prices(
+    lower_limit_order_price: Option<f64>,
+    upper_limit_order_price: Option<f64>,
+    sqrt_price: u128,
+    decimals_a: u8,
+    decimals_b: u8,
+) -> Result<(u128, u128)> {
+    let lower_limit_order_sqrt_price = match lower_limit_order_price {
+        Some(price) => {
+            let lower_sqrt_price = price_to_sqrt_price(price, decimals_a, decimals_b);
+            if lower_sqrt_price < MIN_SQRT_PRICE || lower_sqrt_price >= sqrt_price {
+                return Err(anyhow!("The lower limit order price must be below the current pool price"));
+            }
+            lower_sqrt_price
+        }
+        None => MIN_SQRT_PRICE,
+    };
+
+    let upper_limit_order_sqrt_price = match upper_limit_order_price {
+        Some(price) => {
+            let upper_sqrt_price = price_to_sqrt_price(price, decimals_a, decimals_b);
+            if upper_sqrt_price > MAX_SQRT_PRICE || upper_sqrt_price <= sqrt_price {
+                return Err(anyhow!("The upper limit order price must be above the current pool price"));
+            }
+            upper_sqrt_price
+        }
+        None => MAX_SQRT_PRICE,
+    };
+
+    Ok((lower_limit_order_sqrt_price, upper_limit_order_sqrt_price))
+}
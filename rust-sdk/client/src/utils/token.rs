@@ -1,9 +1,17 @@
+use anyhow::Result;
+use solana_account::Account;
 use solana_instruction::Instruction;
 use solana_pubkey::Pubkey;
 use solana_system_interface::instruction::transfer;
 use spl_associated_token_account::get_associated_token_address_with_program_id;
 use spl_associated_token_account::instruction::create_associated_token_account_idempotent;
+use spl_token::solana_program::program_pack::Pack;
+use spl_token_2022::extension::{BaseStateWithExtensions, ExtensionType, StateWithExtensions};
 use spl_token_2022::instruction::{close_account, sync_native};
+use spl_token_2022::state::Mint;
+
+/// The size of the position NFT token account. Position mints don't have extensions that require token account extensions.
+pub const POSITION_NFT_TOKEN_ACCOUNT_LEN: usize = 170;
 
 pub struct CreateATAInstructions {
     pub create: Vec<Instruction>,
@@ -35,3 +43,15 @@ pub fn get_create_ata_instructions(mint: &Pubkey, owner: &Pubkey, payer: &Pubkey
         cleanup: cleanup_instructions,
     }
 }
+
+/// Returns the size of a new associated token account of the mint. Token-2022 accounts include the extensions required by the mint.
+pub fn get_associated_token_account_len(mint_account: &Account) -> Result<usize> {
+    if mint_account.owner != spl_token_2022::ID {
+        return Ok(spl_token::state::Account::LEN);
+    }
+
+    let mint = StateWithExtensions::<Mint>::unpack(&mint_account.data)?;
+    let mut extension_types = ExtensionType::get_required_init_account_extensions(&mint.get_extension_types()?);
+    extension_types.push(ExtensionType::ImmutableOwner);
+    Ok(ExtensionType::try_calculate_account_len::<spl_token_2022::state::Account>(&extension_types)?)
+}
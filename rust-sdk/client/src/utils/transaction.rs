@@ -0,0 +1,108 @@
+use crate::fetcher::{block_on, AccountFetcher, SyncAccountFetcher};
+use crate::TxPlan;
+use anyhow::{anyhow, Result};
+use solana_address_lookup_table_interface::state::AddressLookupTable;
+use solana_compute_budget_interface::ComputeBudgetInstruction;
+use solana_hash::Hash;
+use solana_message::{v0, AddressLookupTableAccount, VersionedMessage};
+use solana_pubkey::Pubkey;
+use solana_signature::Signature;
+use solana_signer::Signer;
+use solana_transaction::versioned::VersionedTransaction;
+
+/// Options of the versioned transaction assembled from the builder output.
+#[derive(Debug, Default, Clone)]
+pub struct VersionedTransactionConfig {
+    /// Additional address lookup tables.
+    pub address_lookup_table_addresses: Vec<Pubkey>,
+
+    /// Adds the `SetComputeUnitLimit` instruction.
+    pub compute_unit_limit: Option<u32>,
+
+    /// Adds the `SetComputeUnitPrice` instruction. The price is in micro-lamports per compute unit.
+    pub compute_unit_price: Option<u64>,
+}
+
+/// Assembles a v0 transaction from the result of a builder.
+///
+/// The transaction uses the lookup tables of the builder, including the one of the market, and the ones from the config.
+/// It is signed by the additional signers of the builder, e.g. the position mint keypair returned by
+/// `open_and_increase_tuna_lp_position_orca_instructions`. The signature of the payer and of any other
+/// required signer is left empty to be added later.
+pub fn create_versioned_transaction(
+    rpc: &impl SyncAccountFetcher,
+    payer: &Pubkey,
+    tx_plan: &TxPlan,
+    recent_blockhash: Hash,
+    config: &VersionedTransactionConfig,
+) -> Result<VersionedTransaction> {
+    block_on(create_versioned_transaction_async(rpc, payer, tx_plan, recent_blockhash, config))
+}
+
+/// Async version of [`create_versioned_transaction`].
+pub async fn create_versioned_transaction_async(
+    rpc: &impl AccountFetcher,
+    payer: &Pubkey,
+    tx_plan: &TxPlan,
+    recent_blockhash: Hash,
+    config: &VersionedTransactionConfig,
+) -> Result<VersionedTransaction> {
+    let mut address_lookup_table_addresses = vec![];
+    for address in tx_plan
+        .address_lookup_table_addresses
+        .iter()
+        .chain(&config.address_lookup_table_addresses)
+    {
+        if !address_lookup_table_addresses.contains(address) {
+            address_lookup_table_addresses.push(*address);
+        }
+    }
+
+    let address_lookup_table_accounts = fetch_address_lookup_table_accounts(rpc, &address_lookup_table_addresses).await?;
+
+    let mut all_instructions = vec![];
+    if let Some(compute_unit_limit) = config.compute_unit_limit {
+        all_instructions.push(ComputeBudgetInstruction::set_compute_unit_limit(compute_unit_limit));
+    }
+    if let Some(compute_unit_price) = config.compute_unit_price {
+        all_instructions.push(ComputeBudgetInstruction::set_compute_unit_price(compute_unit_price));
+    }
+    all_instructions.extend_from_slice(&tx_plan.instructions);
+
+    let message = VersionedMessage::V0(v0::Message::try_compile(payer, &all_instructions, &address_lookup_table_accounts, recent_blockhash)?);
+
+    let num_required_signatures = message.header().num_required_signatures as usize;
+    let signer_addresses = &message.static_account_keys()[..num_required_signatures];
+    let mut signatures = vec![Signature::default(); num_required_signatures];
+
+    let message_data = message.serialize();
+    for signer in &tx_plan.additional_signers {
+        let index = signer_addresses
+            .iter()
+            .position(|address| *address == signer.pubkey())
+            .ok_or(anyhow!("{} is not a signer of the transaction", signer.pubkey()))?;
+        signatures[index] = signer.sign_message(&message_data);
+    }
+
+    Ok(VersionedTransaction { signatures, message })
+}
+
+async fn fetch_address_lookup_table_accounts(rpc: &impl AccountFetcher, addresses: &[Pubkey]) -> Result<Vec<AddressLookupTableAccount>> {
+    if addresses.is_empty() {
+        return Ok(vec![]);
+    }
+
+    let accounts = rpc.get_multiple_accounts(addresses).await?;
+
+    let mut address_lookup_table_accounts = Vec::with_capacity(addresses.len());
+    for (address, account) in addresses.iter().zip(accounts) {
+        let account = account.ok_or(anyhow!("Address lookup table not found: {}", address))?;
+        let table = AddressLookupTable::deserialize(&account.data).map_err(|_| anyhow!("Invalid address lookup table: {}", address))?;
+        address_lookup_table_accounts.push(AddressLookupTableAccount {
+            key: *address,
+            addresses: table.addresses.to_vec(),
+        });
+    }
+
+    Ok(address_lookup_table_accounts)
+}
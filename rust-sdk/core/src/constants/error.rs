@@ -13,3 +13,12 @@ pub const JUPITER_QUOTE_REQUEST_ERROR: CoreError = "Jupiter quote request failed
 
 #[cfg_attr(feature = "wasm", wasm_expose)]
 pub const JUPITER_SWAP_INSTRUCTIONS_REQUEST_ERROR: CoreError = "Jupiter swap instructions request failed";
+
+#[cfg_attr(feature = "wasm", wasm_expose)]
+pub const INVALID_JUPITER_ROUTE_INSTRUCTION_DATA: CoreError = "Invalid jupiter route instruction data";
+
+#[cfg_attr(feature = "wasm", wasm_expose)]
+pub const INVALID_JUPITER_ROUTE_USER_ACCOUNT: CoreError = "Invalid jupiter route instruction user account";
+
+#[cfg_attr(feature = "wasm", wasm_expose)]
+pub const INCORRECT_JUPITER_SWAP_INPUT_AMOUNT: CoreError = "Incorrect swap input amount";
@@ -8,3 +8,18 @@ pub const HUNDRED_PERCENT: u32 = 1000000;
 
 #[cfg_attr(feature = "wasm", wasm_expose)]
 pub const COMPUTED_AMOUNT: u64 = 18446744073709551615;
+
+#[cfg_attr(feature = "wasm", wasm_expose)]
+pub const SECONDS_PER_YEAR: u64 = 31536000;
+
+#[cfg_attr(feature = "wasm", wasm_expose)]
+pub const TUNA_POSITION_FLAGS_LOWER_LIMIT_ORDER_SWAP_TO_TOKEN_A: u32 = 1;
+
+#[cfg_attr(feature = "wasm", wasm_expose)]
+pub const TUNA_POSITION_FLAGS_LOWER_LIMIT_ORDER_SWAP_TO_TOKEN_B: u32 = 2;
+
+#[cfg_attr(feature = "wasm", wasm_expose)]
+pub const TUNA_POSITION_FLAGS_UPPER_LIMIT_ORDER_SWAP_TO_TOKEN_A: u32 = 4;
+
+#[cfg_attr(feature = "wasm", wasm_expose)]
+pub const TUNA_POSITION_FLAGS_UPPER_LIMIT_ORDER_SWAP_TO_TOKEN_B: u32 = 8;
@@ -1,4 +1,50 @@
-/// Returns a borrow rate multiplier according to the provided utilization of a market. The returned value will be equal to:
+/// A borrow rate model. Returns a multiplier of the vault base interest rate for the provided utilization.
+pub trait BorrowCurve {
+    /// Returns a borrow rate multiplier according to the provided utilization of a market.
+    /// # Parameters
+    /// - `utilization` Current utilization (1.0 is equal to 100%)
+    /// # Returns
+    /// Borrow rate multiplier.
+    fn sample(&self, utilization: f64) -> f64;
+}
+
+/// The default borrow curve: the rate multiplier grows linearly from 1/k to 1.0 up to the target utilization,
+/// and from 1.0 to k above it.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct KinkedBorrowCurve {
+    /// The utilization at which the multiplier is equal to 1.0 (1.0 is equal to 100%).
+    pub target_utilization: f64,
+    /// The multiplier at 100% utilization.
+    pub k: f64,
+}
+
+impl Default for KinkedBorrowCurve {
+    fn default() -> Self {
+        Self {
+            target_utilization: 0.9,
+            k: 4.0,
+        }
+    }
+}
+
+impl BorrowCurve for KinkedBorrowCurve {
+    fn sample(&self, utilization: f64) -> f64 {
+        let target_utilization = self.target_utilization;
+        let k = self.k;
+
+        if utilization > 1.0 {
+            k
+        } else if utilization <= 0.0 {
+            1.0 / k
+        } else if utilization > target_utilization {
+            (utilization - target_utilization) * (k - 1.0) / (1.0 - target_utilization) + 1.0
+        } else {
+            1.0 - (target_utilization - utilization) * (1.0 - 1.0 / k) / target_utilization
+        }
+    }
+}
+
+/// Returns a borrow rate multiplier of the default curve according to the provided utilization of a market. The returned value will be equal to:
 ///   * 0.25 at utilization 0%
 ///   * 1.0  at utilization 90%
 ///   * 4.0  at utilization 100%
@@ -7,16 +53,5 @@
 /// # Returns
 /// Borrow rate multiplier.
 pub fn sample(utilization: f64) -> f64 {
-    let target_utilization = 0.9;
-    let k = 4.0;
-
-    if utilization > 1.0 {
-        k
-    } else if utilization <= 0.0 {
-        1.0 / k
-    } else if utilization > target_utilization {
-        (utilization - target_utilization) * (k - 1.0) / (1.0 - target_utilization) + 1.0
-    } else {
-        1.0 - (target_utilization - utilization) * (1.0 - 1.0 / k) / target_utilization
-    }
+    KinkedBorrowCurve::default().sample(utilization)
 }
@@ -1,5 +1,8 @@
+mod swap_pool;
 pub mod tuna_lp_position;
 pub mod tuna_spot_position;
+pub mod vault;
 
 pub use tuna_lp_position::*;
 pub use tuna_spot_position::*;
+pub use vault::*;
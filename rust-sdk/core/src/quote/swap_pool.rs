@@ -0,0 +1,210 @@
+use fusionamm_core::{swap_quote_by_input_token, swap_quote_by_output_token, CoreError, FusionPoolFacade, TickArrays};
+use orca_whirlpools_core::{
+    compute_swap, tick_index_to_sqrt_price, OracleFacade, TickArrayFacade, TickArraySequence, WhirlpoolFacade,
+    ADAPTIVE_FEE_CONTROL_FACTOR_DENOMINATOR, FEE_RATE_HARD_LIMIT, MAX_TICK_INDEX, MIN_TICK_INDEX, TICK_ARRAY_SIZE,
+    VOLATILITY_ACCUMULATOR_SCALE_FACTOR,
+};
+
+pub(crate) enum SwapPoolFacade {
+    Fusion {
+        fusion_pool: FusionPoolFacade,
+        tick_arrays: TickArrays,
+    },
+    Orca {
+        whirlpool: WhirlpoolFacade,
+        oracle: Option<OracleFacade>,
+        tick_arrays: orca_whirlpools_core::TickArrays,
+        timestamp: u64,
+    },
+}
+
+pub(crate) struct SwapPool {
+    pub facade: SwapPoolFacade,
+    pub slippage_bps: Option<u16>,
+}
+
+impl SwapPool {
+    /// Returns the current sqrt price of the pool.
+    pub fn sqrt_price(&self) -> u128 {
+        match &self.facade {
+            SwapPoolFacade::Fusion { fusion_pool, .. } => fusion_pool.sqrt_price,
+            SwapPoolFacade::Orca { whirlpool, .. } => whirlpool.sqrt_price,
+        }
+    }
+
+    /// Returns the swap fee rate charged at the current pool price, including the adaptive part of the Orca fee.
+    /// The adaptive fee grows while a swap crosses tick groups, so for large swaps the rate is a lower bound of the effective fee.
+    pub fn fee_rate(&self) -> Result<u16, CoreError> {
+        match &self.facade {
+            SwapPoolFacade::Fusion { fusion_pool, .. } => Ok(fusion_pool.fee_rate),
+            SwapPoolFacade::Orca {
+                whirlpool,
+                oracle,
+                timestamp,
+                ..
+            } => get_orca_fee_rate(whirlpool, oracle.as_ref(), *timestamp),
+        }
+    }
+
+    /// Returns the estimated output amount of the exact input swap.
+    pub fn swap_exact_in(self, token_in: u64, specified_token_a: bool) -> Result<u64, CoreError> {
+        match self.facade {
+            SwapPoolFacade::Fusion { fusion_pool, tick_arrays } => {
+                let quote = swap_quote_by_input_token(token_in, specified_token_a, 0, fusion_pool, tick_arrays, None, None)?;
+                Ok(quote.token_est_out)
+            }
+            SwapPoolFacade::Orca {
+                whirlpool,
+                oracle,
+                tick_arrays,
+                timestamp,
+            } => {
+                let (_, token_out) = compute_orca_swap_input(token_in, 0, specified_token_a, whirlpool, oracle, tick_arrays.into(), timestamp)?;
+                Ok(token_out)
+            }
+        }
+    }
+
+    /// Simulates the exact input swap tick by tick.
+    /// Returns the estimated output amount and the sqrt price of the pool after the swap.
+    /// Finding the Orca price takes a binary search over the swap, so use `swap_exact_in` if the price isn't needed.
+    pub fn simulate_swap_exact_in(self, token_in: u64, specified_token_a: bool) -> Result<(u64, u128), CoreError> {
+        match self.facade {
+            SwapPoolFacade::Fusion { fusion_pool, tick_arrays } => {
+                let quote = swap_quote_by_input_token(token_in, specified_token_a, 0, fusion_pool, tick_arrays, None, None)?;
+                Ok((quote.token_est_out, quote.next_sqrt_price))
+            }
+            SwapPoolFacade::Orca {
+                whirlpool,
+                oracle,
+                tick_arrays,
+                timestamp,
+            } => {
+                let tick_arrays: [Option<TickArrayFacade>; 6] = tick_arrays.into();
+                let (_, token_out) = compute_orca_swap_input(token_in, 0, specified_token_a, whirlpool, oracle, tick_arrays, timestamp)?;
+                let next_sqrt_price = get_orca_next_sqrt_price(token_in, specified_token_a, whirlpool, oracle, tick_arrays, timestamp)?;
+                Ok((token_out, next_sqrt_price))
+            }
+        }
+    }
+
+    /// Returns the estimated input amount of the exact output swap.
+    pub fn swap_exact_out(self, token_out: u64, specified_token_a: bool) -> Result<u64, CoreError> {
+        match self.facade {
+            SwapPoolFacade::Fusion { fusion_pool, tick_arrays } => {
+                let quote = swap_quote_by_output_token(token_out, specified_token_a, 0, fusion_pool, tick_arrays, None, None)?;
+                Ok(quote.token_est_in)
+            }
+            SwapPoolFacade::Orca {
+                whirlpool,
+                oracle,
+                tick_arrays,
+                timestamp,
+            } => {
+                let quote = orca_whirlpools_core::swap_quote_by_output_token(
+                    token_out,
+                    specified_token_a,
+                    0,
+                    whirlpool,
+                    oracle,
+                    tick_arrays,
+                    timestamp,
+                    None,
+                    None,
+                )?;
+                Ok(quote.token_est_in)
+            }
+        }
+    }
+}
+
+/// Returns the Whirlpool fee rate for the first step of a swap, mirroring the fee rate manager of the Whirlpool program.
+/// The rate is saturated to u16 (6.5%), which is only reachable by the adaptive fee in extreme volatility.
+fn get_orca_fee_rate(whirlpool: &WhirlpoolFacade, oracle: Option<&OracleFacade>, timestamp: u64) -> Result<u16, CoreError> {
+    let oracle = match oracle {
+        Some(oracle) if whirlpool.is_initialized_with_adaptive_fee() => oracle,
+        _ => return Ok(whirlpool.fee_rate),
+    };
+
+    let constants = oracle.adaptive_fee_constants;
+    let mut variables = oracle.adaptive_fee_variables;
+    let tick_group_index = whirlpool.tick_current_index.div_euclid(constants.tick_group_size as i32);
+    variables.update_reference(tick_group_index, timestamp, &constants)?;
+    variables.update_volatility_accumulator(tick_group_index, &constants);
+
+    let crossed = variables.volatility_accumulator as u128 * constants.tick_group_size as u128;
+    let adaptive_fee_rate = (constants.adaptive_fee_control_factor as u128 * crossed * crossed).div_ceil(
+        ADAPTIVE_FEE_CONTROL_FACTOR_DENOMINATOR as u128 * VOLATILITY_ACCUMULATOR_SCALE_FACTOR as u128 * VOLATILITY_ACCUMULATOR_SCALE_FACTOR as u128,
+    );
+    let fee_rate = (whirlpool.fee_rate as u128 + adaptive_fee_rate.min(FEE_RATE_HARD_LIMIT as u128)).min(FEE_RATE_HARD_LIMIT as u128);
+
+    Ok(fee_rate.min(u16::MAX as u128) as u16)
+}
+
+/// Computes the exact input Whirlpool swap up to the price limit. Returns the consumed input and the output amounts.
+fn compute_orca_swap_input(
+    token_in: u64,
+    sqrt_price_limit: u128,
+    a_to_b: bool,
+    whirlpool: WhirlpoolFacade,
+    oracle: Option<OracleFacade>,
+    tick_arrays: [Option<TickArrayFacade>; 6],
+    timestamp: u64,
+) -> Result<(u64, u64), CoreError> {
+    let tick_sequence = TickArraySequence::new(tick_arrays, whirlpool.tick_spacing)?;
+    let swap = compute_swap(token_in, sqrt_price_limit, whirlpool, tick_sequence, a_to_b, true, timestamp, oracle.map(|oracle| oracle.into()))?;
+    Ok(if a_to_b {
+        (swap.token_a, swap.token_b)
+    } else {
+        (swap.token_b, swap.token_a)
+    })
+}
+
+/// The Whirlpool swap quote doesn't return the resulting price, so it's found by a binary search
+/// of the farthest price limit at which the whole input amount is still consumed.
+/// The result is accurate up to the rounding of the token amounts.
+///
+/// The search is bounded by the price range of the tick arrays, since the swap can't move the price beyond them.
+/// It still runs the swap computation about 60-70 times, once per halving of the range.
+fn get_orca_next_sqrt_price(
+    token_in: u64,
+    a_to_b: bool,
+    whirlpool: WhirlpoolFacade,
+    oracle: Option<OracleFacade>,
+    tick_arrays: [Option<TickArrayFacade>; 6],
+    timestamp: u64,
+) -> Result<u128, CoreError> {
+    if token_in == 0 {
+        return Ok(whirlpool.sqrt_price);
+    }
+
+    let is_input_consumed = |sqrt_price_limit: u128| -> Result<bool, CoreError> {
+        let (input, _) = compute_orca_swap_input(token_in, sqrt_price_limit, a_to_b, whirlpool, oracle, tick_arrays, timestamp)?;
+        Ok(input == token_in)
+    };
+
+    // The input is consumed at the `consumed` limit and isn't consumed at the `not_consumed` limit.
+    let start_tick_indexes = tick_arrays.iter().flatten().map(|tick_array| tick_array.start_tick_index);
+    let (mut consumed, mut not_consumed): (u128, u128) = if a_to_b {
+        let tick_index = start_tick_indexes.min().unwrap_or(MIN_TICK_INDEX).max(MIN_TICK_INDEX);
+        (tick_index_to_sqrt_price(tick_index).into(), whirlpool.sqrt_price)
+    } else {
+        let tick_array_range = TICK_ARRAY_SIZE as i32 * whirlpool.tick_spacing as i32;
+        let tick_index = start_tick_indexes
+            .max()
+            .map_or(MAX_TICK_INDEX, |index| index + tick_array_range - 1)
+            .min(MAX_TICK_INDEX);
+        (tick_index_to_sqrt_price(tick_index).into(), whirlpool.sqrt_price)
+    };
+
+    while consumed.abs_diff(not_consumed) > 1 {
+        let mid = consumed.min(not_consumed) + consumed.abs_diff(not_consumed) / 2;
+        if is_input_consumed(mid)? {
+            consumed = mid;
+        } else {
+            not_consumed = mid;
+        }
+    }
+
+    Ok(consumed)
+}
@@ -1,12 +1,21 @@
 #![allow(clippy::collapsible_else_if)]
 #![allow(clippy::too_many_arguments)]
 
-use crate::{calculate_tuna_protocol_fee, sqrt_price_x64_to_price_x64, COMPUTED_AMOUNT, HUNDRED_PERCENT, INVALID_ARGUMENTS};
+use crate::quote::swap_pool::{SwapPool, SwapPoolFacade};
+use crate::{
+    apply_swap_fee, calculate_tuna_protocol_fee, find_time_to_liquidation, get_vault_projected_debt, reverse_apply_swap_fee,
+    sqrt_price_x64_to_price_x64, COMPUTED_AMOUNT, HUNDRED_PERCENT, INVALID_ARGUMENTS, TOKEN_A, TOKEN_B,
+    TUNA_POSITION_FLAGS_LOWER_LIMIT_ORDER_SWAP_TO_TOKEN_A, TUNA_POSITION_FLAGS_LOWER_LIMIT_ORDER_SWAP_TO_TOKEN_B,
+    TUNA_POSITION_FLAGS_UPPER_LIMIT_ORDER_SWAP_TO_TOKEN_A, TUNA_POSITION_FLAGS_UPPER_LIMIT_ORDER_SWAP_TO_TOKEN_B,
+};
 use fixed::types::U64F64;
 use fusionamm_core::{
-    get_amount_a_from_liquidity, get_amount_b_from_liquidity, get_amounts_from_liquidity, get_liquidity_from_amount_a, get_liquidity_from_amount_b,
-    get_liquidity_from_amounts, position_ratio_x64, tick_index_to_sqrt_price, try_apply_swap_fee, CoreError, ARITHMETIC_OVERFLOW, Q64_RESOLUTION,
+    div_by_sqrt_price_squared, get_amount_a_from_liquidity, get_amount_b_from_liquidity, get_amounts_from_liquidity, get_liquidity_from_amount_a,
+    get_liquidity_from_amount_b, get_liquidity_from_amounts, mul_by_sqrt_price_squared, mul_u256, position_ratio_x64, sqrt_price_to_price,
+    tick_index_to_sqrt_price, try_apply_swap_fee, try_get_min_amount_with_slippage_tolerance, try_mul_div, CoreError, FusionPoolFacade,
+    PositionRatio, TickArrays, ARITHMETIC_OVERFLOW, MAX_TICK_INDEX, MIN_TICK_INDEX, Q64_RESOLUTION,
 };
+use orca_whirlpools_core::{OracleFacade, WhirlpoolFacade};
 
 #[cfg(feature = "wasm")]
 use fusionamm_macros::wasm_expose;
@@ -316,6 +325,24 @@ pub struct IncreaseLpPositionQuoteResult {
     pub liquidation_upper_price: f64,
 }
 
+/// Splits the total value of the provided amounts between tokens A and B according to the position ratio.
+/// All values are rounded down, so the resulting amounts never exceed what the program can deposit.
+fn split_by_position_ratio(amount_a: u64, amount_b: u64, position_ratio: PositionRatio, sqrt_price: u128) -> Result<(u64, u64), CoreError> {
+    // The total value of the provided amounts denominated in token B.
+    let total = mul_by_sqrt_price_squared(amount_a, sqrt_price, false)?
+        .checked_add(amount_b)
+        .ok_or(ARITHMETIC_OVERFLOW)?;
+
+    // total_a = total * ratio_a / price, where price = sqrt_price^2 / 2^128 and ratio_a is a Q64.64 number.
+    let (total_a, _) = mul_u256(total as u128, position_ratio.ratio_a)
+        .shift_word_left()
+        .div(mul_u256(sqrt_price, sqrt_price), false);
+    let total_a = u64::try_from(total_a.try_into_u128()?).map_err(|_| ARITHMETIC_OVERFLOW)?;
+    let total_b = try_mul_div(total, position_ratio.ratio_b, 1 << 64, false)?;
+
+    Ok((total_a, total_b))
+}
+
 #[cfg_attr(feature = "wasm", wasm_expose)]
 pub fn get_increase_lp_position_quote(args: IncreaseLpPositionQuoteArgs) -> Result<IncreaseLpPositionQuoteResult, CoreError> {
     let mut collateral_a = args.collateral_a;
@@ -375,15 +402,9 @@ pub fn get_increase_lp_position_quote(args: IncreaseLpPositionQuoteArgs) -> Resu
 
     if args.collateral_a != COMPUTED_AMOUNT && args.collateral_b != COMPUTED_AMOUNT {
         let position_ratio = position_ratio_x64(sqrt_price.into(), args.tick_lower_index, args.tick_upper_index);
-        let ratio_a = position_ratio.ratio_a as f64 / Q64_RESOLUTION;
-        let ratio_b = position_ratio.ratio_b as f64 / Q64_RESOLUTION;
-
-        let price = (sqrt_price as f64 / Q64_RESOLUTION).powf(2.0);
 
         // Estimated total position size.
-        let mut total = (provided_a as f64 * price + provided_b as f64) as u64;
-        total_a = (total as f64 * ratio_a / price) as u64;
-        total_b = (total as f64 * ratio_b) as u64;
+        (total_a, total_b) = split_by_position_ratio(provided_a, provided_b, position_ratio, sqrt_price)?;
 
         let mut fee_a = 0;
         let mut fee_b = 0;
@@ -391,19 +412,17 @@ pub fn get_increase_lp_position_quote(args: IncreaseLpPositionQuoteArgs) -> Resu
         if total_a < provided_a {
             swap_input = provided_a - total_a;
             fee_a = swap_input - try_apply_swap_fee(swap_input, args.swap_fee_rate)?;
-            swap_output = ((swap_input - fee_a) as f64 * price) as u64;
+            swap_output = mul_by_sqrt_price_squared(swap_input - fee_a, sqrt_price, false)?;
             swap_a_to_b = true;
         } else if total_b < provided_b {
             swap_input = provided_b - total_b;
             fee_b = swap_input - try_apply_swap_fee(swap_input, args.swap_fee_rate)?;
-            swap_output = ((swap_input - fee_b) as f64 / price) as u64;
+            swap_output = div_by_sqrt_price_squared(swap_input - fee_b, sqrt_price, false)?;
             swap_a_to_b = false;
         }
 
         // Recompute totals with applied swap fee.
-        total = ((provided_a - fee_a) as f64 * price) as u64 + provided_b - fee_b;
-        total_a = ((total as f64 * ratio_a) / price) as u64;
-        total_b = (total as f64 * ratio_b) as u64;
+        (total_a, total_b) = split_by_position_ratio(provided_a - fee_a, provided_b - fee_b, position_ratio, sqrt_price)?;
     }
 
     let liquidity = get_liquidity_from_amounts(sqrt_price, lower_sqrt_price, upper_sqrt_price, total_a, total_b)?;
@@ -439,6 +458,475 @@ pub fn get_increase_lp_position_quote(args: IncreaseLpPositionQuoteArgs) -> Resu
     })
 }
 
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "wasm", wasm_expose)]
+pub struct IncreaseLpPositionPriceImpactQuoteResult {
+    pub collateral_a: u64,
+    pub collateral_b: u64,
+    pub borrow_a: u64,
+    pub borrow_b: u64,
+    /** The amount of token A added to the liquidity of the position. */
+    pub total_a: u64,
+    /** The amount of token B added to the liquidity of the position. */
+    pub total_b: u64,
+    pub swap_input: u64,
+    /** The swap output simulated across the pool ticks. */
+    pub swap_output: u64,
+    pub swap_a_to_b: bool,
+    /** Price impact of the swap including the swap fee (100% = 1.0). */
+    pub price_impact: f64,
+    /** The sqrt price of the pool after the swap. */
+    pub next_sqrt_price: u128,
+    pub protocol_fee_a: u64,
+    pub protocol_fee_b: u64,
+    /** The amount of token A that can't be added to the liquidity and stays in the position. */
+    pub leftovers_a: u64,
+    /** The amount of token B that can't be added to the liquidity and stays in the position. */
+    pub leftovers_b: u64,
+    /** The value of leftovers relative to the position value (HUNDRED_PERCENT = 100%). */
+    pub leftovers_percentage: u32,
+    /** True if the leftovers exceed the maximum allowed percentage and the transaction is expected to fail. */
+    pub leftovers_exceeded: bool,
+    pub liquidity: u128,
+    pub leverage: f64,
+    pub liquidation_lower_price: f64,
+    pub liquidation_upper_price: f64,
+}
+
+fn get_increase_lp_position_quote_with_pool(
+    args: IncreaseLpPositionQuoteArgs,
+    max_percentage_of_leftovers: u32,
+    pool: SwapPool,
+) -> Result<IncreaseLpPositionPriceImpactQuoteResult, CoreError> {
+    let sqrt_price = pool.sqrt_price();
+    let quote = get_increase_lp_position_quote(IncreaseLpPositionQuoteArgs {
+        sqrt_price,
+        swap_fee_rate: pool.fee_rate()?,
+        ..args
+    })?;
+
+    let provided_a = quote.collateral_a + quote.borrow_a - quote.protocol_fee_a;
+    let provided_b = quote.collateral_b + quote.borrow_b - quote.protocol_fee_b;
+
+    let mut swap_output = 0;
+    let mut next_sqrt_price = sqrt_price;
+    let mut price_impact = 0.0;
+    let (available_a, available_b) = if quote.swap_input > 0 {
+        (swap_output, next_sqrt_price) = pool.simulate_swap_exact_in(quote.swap_input, quote.swap_a_to_b)?;
+
+        let price = sqrt_price_to_price(sqrt_price.into(), 1, 1);
+        if quote.swap_a_to_b {
+            price_impact = (quote.swap_input as f64 - swap_output as f64 / price) / quote.swap_input as f64;
+            (provided_a - quote.swap_input, provided_b + swap_output)
+        } else {
+            price_impact = (quote.swap_input as f64 - swap_output as f64 * price) / quote.swap_input as f64;
+            (provided_a + swap_output, provided_b - quote.swap_input)
+        }
+    } else {
+        (provided_a, provided_b)
+    };
+
+    // The liquidity is added at the pool price after the swap.
+    let lower_sqrt_price = tick_index_to_sqrt_price(args.tick_lower_index);
+    let upper_sqrt_price = tick_index_to_sqrt_price(args.tick_upper_index);
+    let liquidity = get_liquidity_from_amounts(next_sqrt_price, lower_sqrt_price, upper_sqrt_price, available_a, available_b)?;
+    let deposit = get_amounts_from_liquidity(liquidity, next_sqrt_price, lower_sqrt_price, upper_sqrt_price, true)?;
+    let leftovers_a = available_a.saturating_sub(deposit.a);
+    let leftovers_b = available_b.saturating_sub(deposit.b);
+
+    let leftovers_value = mul_by_sqrt_price_squared(leftovers_a, next_sqrt_price, false)? as u128 + leftovers_b as u128;
+    let total_value = mul_by_sqrt_price_squared(available_a, next_sqrt_price, false)? as u128 + available_b as u128;
+    let leftovers_percentage = if total_value > 0 {
+        (leftovers_value * HUNDRED_PERCENT as u128).div_ceil(total_value) as u32
+    } else {
+        0
+    };
+
+    let liquidation_prices = get_lp_position_liquidation_prices(
+        args.tick_lower_index,
+        args.tick_upper_index,
+        liquidity,
+        leftovers_a,
+        leftovers_b,
+        quote.borrow_a,
+        quote.borrow_b,
+        args.liquidation_threshold,
+    )?;
+
+    let leverage = compute_leverage(available_a, available_b, quote.borrow_a, quote.borrow_b, next_sqrt_price)?;
+
+    Ok(IncreaseLpPositionPriceImpactQuoteResult {
+        collateral_a: quote.collateral_a,
+        collateral_b: quote.collateral_b,
+        borrow_a: quote.borrow_a,
+        borrow_b: quote.borrow_b,
+        total_a: deposit.a.min(available_a),
+        total_b: deposit.b.min(available_b),
+        swap_input: quote.swap_input,
+        swap_output,
+        swap_a_to_b: quote.swap_a_to_b,
+        price_impact,
+        next_sqrt_price,
+        protocol_fee_a: quote.protocol_fee_a,
+        protocol_fee_b: quote.protocol_fee_b,
+        leftovers_a,
+        leftovers_b,
+        leftovers_percentage,
+        leftovers_exceeded: leftovers_percentage > max_percentage_of_leftovers,
+        liquidity,
+        leverage,
+        liquidation_lower_price: liquidation_prices.lower,
+        liquidation_upper_price: liquidation_prices.upper,
+    })
+}
+
+/// LP position increase quote that simulates the swap across the pool ticks instead of executing it at the spot price.
+/// The pool price and the swap fee rate are taken from the pool, so `sqrt_price` and `swap_fee_rate` of the arguments are ignored.
+///
+/// # Parameters
+/// - `args`: The increase quote arguments.
+/// - `max_percentage_of_leftovers`: The maximum allowed percentage of leftovers from TunaConfig.
+/// - `fusion_pool`: Fusion pool.
+/// - `tick_arrays`: Five tick arrays around the current pool price.
+///
+/// # Returns
+/// - `IncreaseLpPositionPriceImpactQuoteResult`: quote result
+#[cfg_attr(feature = "wasm", wasm_expose)]
+pub fn get_increase_lp_position_quote_with_price_impact(
+    args: IncreaseLpPositionQuoteArgs,
+    max_percentage_of_leftovers: u32,
+    fusion_pool: FusionPoolFacade,
+    tick_arrays: TickArrays,
+) -> Result<IncreaseLpPositionPriceImpactQuoteResult, CoreError> {
+    get_increase_lp_position_quote_with_pool(
+        args,
+        max_percentage_of_leftovers,
+        SwapPool {
+            facade: SwapPoolFacade::Fusion { fusion_pool, tick_arrays },
+            slippage_bps: None,
+        },
+    )
+}
+
+/// LP position increase quote for a market on Orca that simulates the swap across the pool ticks including the adaptive fee.
+/// The pool price and the swap fee rate are taken from the pool, so `sqrt_price` and `swap_fee_rate` of the arguments are ignored.
+///
+/// The Whirlpool swap quote doesn't return the resulting pool price, so it's found by a binary search within the price range
+/// of the tick arrays. Each quote runs the swap simulation about 60-70 times, which is much slower than the Fusion quote.
+///
+/// # Parameters
+/// - `args`: The increase quote arguments.
+/// - `max_percentage_of_leftovers`: The maximum allowed percentage of leftovers from TunaConfig.
+/// - `whirlpool`: Orca whirlpool.
+/// - `oracle`: Whirlpool oracle. Required for pools with the adaptive fee.
+/// - `tick_arrays`: Five tick arrays around the current pool price.
+/// - `timestamp`: Current blockchain timestamp. Used to compute the adaptive fee.
+///
+/// # Returns
+/// - `IncreaseLpPositionPriceImpactQuoteResult`: quote result
+#[cfg_attr(feature = "wasm", wasm_expose)]
+pub fn get_increase_lp_position_orca_quote_with_price_impact(
+    args: IncreaseLpPositionQuoteArgs,
+    max_percentage_of_leftovers: u32,
+    whirlpool: WhirlpoolFacade,
+    oracle: Option<OracleFacade>,
+    tick_arrays: orca_whirlpools_core::TickArrays,
+    timestamp: u64,
+) -> Result<IncreaseLpPositionPriceImpactQuoteResult, CoreError> {
+    get_increase_lp_position_quote_with_pool(
+        args,
+        max_percentage_of_leftovers,
+        SwapPool {
+            facade: SwapPoolFacade::Orca {
+                whirlpool,
+                oracle,
+                tick_arrays,
+                timestamp,
+            },
+            slippage_bps: None,
+        },
+    )
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "wasm", wasm_expose)]
+pub struct IncreaseLpPositionLeverageArgs {
+    /** Collateral in token A. */
+    pub collateral_a: u64,
+    /** Collateral in token B. */
+    pub collateral_b: u64,
+    /** The target leverage of the position (2.5 = 2.5x). */
+    pub leverage: f64,
+    /** The maximum leverage of the market (LEVERAGE_ONE = 1.0x). */
+    pub max_leverage: u32,
+    /** The amount of token A that can still be borrowed in the market, or u64::MAX if it's not limited. */
+    pub borrow_limit_a: u64,
+    /** The amount of token B that can still be borrowed in the market, or u64::MAX if it's not limited. */
+    pub borrow_limit_b: u64,
+    /** Protocol fee rate from a market account represented as hundredths of a basis point (0.01% = 100). */
+    pub protocol_fee_rate: u16,
+    /** Protocol fee rate from a market account represented as hundredths of a basis point (0.01% = 100). */
+    pub protocol_fee_rate_on_collateral: u16,
+    /** The swap fee rate of a pool denominated in 1e6. */
+    pub swap_fee_rate: u16,
+    /** Current sqrt price. */
+    pub sqrt_price: u128,
+    /** Position lower tick index. */
+    pub tick_lower_index: i32,
+    /** Position upper tick index. */
+    pub tick_upper_index: i32,
+    /** The liquidation threshold of the market. */
+    pub liquidation_threshold: u32,
+}
+
+/// Computes the borrowed amounts required to open or increase an lp position with the target leverage.
+/// The borrowed value is split between the tokens according to the position ratio at the current price,
+/// and the protocol fees are taken into account so that the resulting leverage matches the target.
+///
+/// # Parameters
+/// - `args`: IncreaseLpPositionLeverageArgs
+///
+/// # Returns
+/// - `IncreaseLpPositionQuoteArgs`: the arguments for get_increase_lp_position_quote
+#[cfg_attr(feature = "wasm", wasm_expose)]
+pub fn get_increase_lp_position_args_for_leverage(args: IncreaseLpPositionLeverageArgs) -> Result<IncreaseLpPositionQuoteArgs, CoreError> {
+    let sqrt_price = args.sqrt_price;
+
+    if args.tick_lower_index >= args.tick_upper_index {
+        return Err("Incorrect position tick index order: the lower tick must be less than the upper tick.");
+    }
+
+    if args.collateral_a == COMPUTED_AMOUNT || args.collateral_b == COMPUTED_AMOUNT {
+        return Err(INVALID_ARGUMENTS);
+    }
+
+    if args.leverage.is_nan() || args.leverage < 1.0 {
+        return Err("Leverage can't be less than 1.0");
+    }
+
+    if args.leverage > args.max_leverage as f64 / HUNDRED_PERCENT as f64 {
+        return Err("Leverage exceeds the maximum allowed leverage of the market");
+    }
+
+    let (borrow_a, borrow_b) = compute_borrow_for_leverage(
+        args.collateral_a,
+        args.collateral_b,
+        args.leverage,
+        args.protocol_fee_rate,
+        args.protocol_fee_rate_on_collateral,
+        sqrt_price,
+        args.tick_lower_index,
+        args.tick_upper_index,
+    )?;
+
+    if borrow_a > args.borrow_limit_a {
+        return Err("The amount of token A to borrow exceeds the borrow limit of the market");
+    }
+
+    if borrow_b > args.borrow_limit_b {
+        return Err("The amount of token B to borrow exceeds the borrow limit of the market");
+    }
+
+    Ok(IncreaseLpPositionQuoteArgs {
+        collateral_a: args.collateral_a,
+        collateral_b: args.collateral_b,
+        borrow_a,
+        borrow_b,
+        protocol_fee_rate: args.protocol_fee_rate,
+        protocol_fee_rate_on_collateral: args.protocol_fee_rate_on_collateral,
+        swap_fee_rate: args.swap_fee_rate,
+        sqrt_price,
+        tick_lower_index: args.tick_lower_index,
+        tick_upper_index: args.tick_upper_index,
+        liquidation_threshold: args.liquidation_threshold,
+    })
+}
+
+/// The position size is collateral + borrow - protocol_fee, and the leverage is size / (size - borrow). Hence:
+/// borrow = (leverage - 1) * collateral * (1 - fee_on_collateral) / (1 - fee + leverage * fee).
+/// The borrowed value is split between the tokens according to the position ratio at the current price.
+fn compute_borrow_for_leverage(
+    collateral_a: u64,
+    collateral_b: u64,
+    leverage: f64,
+    protocol_fee_rate: u16,
+    protocol_fee_rate_on_collateral: u16,
+    sqrt_price: u128,
+    tick_lower_index: i32,
+    tick_upper_index: i32,
+) -> Result<(u64, u64), CoreError> {
+    let fee_rate = protocol_fee_rate as f64 / HUNDRED_PERCENT as f64;
+    let fee_rate_on_collateral = protocol_fee_rate_on_collateral as f64 / HUNDRED_PERCENT as f64;
+
+    let collateral = mul_by_sqrt_price_squared(collateral_a, sqrt_price, false)?
+        .checked_add(collateral_b)
+        .ok_or(ARITHMETIC_OVERFLOW)?;
+    let borrow = (leverage - 1.0) * collateral as f64 * (1.0 - fee_rate_on_collateral) / (1.0 - fee_rate + leverage * fee_rate);
+    if borrow >= u64::MAX as f64 {
+        return Err(ARITHMETIC_OVERFLOW);
+    }
+    let borrow = borrow as u64;
+
+    let position_ratio = position_ratio_x64(sqrt_price, tick_lower_index, tick_upper_index);
+    let borrow_b = try_mul_div(borrow, position_ratio.ratio_b, 1 << 64, false)?;
+    let borrow_a = div_by_sqrt_price_squared(borrow - borrow_b, sqrt_price, false)?;
+
+    Ok((borrow_a, borrow_b))
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "wasm", wasm_expose)]
+pub enum LpPositionSizeConstraint {
+    /** The maximum leverage of the market. */
+    MaxLeverage,
+    /** The available liquidity of the vault A. */
+    VaultLiquidityA,
+    /** The available liquidity of the vault B. */
+    VaultLiquidityB,
+    /** The borrow limit of the market in token A. */
+    BorrowLimitA,
+    /** The borrow limit of the market in token B. */
+    BorrowLimitB,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "wasm", wasm_expose)]
+pub struct MaxLpPositionArgs {
+    /** Available wallet balance in token A used as collateral. */
+    pub collateral_a: u64,
+    /** Available wallet balance in token B used as collateral. */
+    pub collateral_b: u64,
+    /** The maximum leverage of the market (LEVERAGE_ONE = 1.0x). */
+    pub max_leverage: u32,
+    /** The available liquidity of the vault A (deposited_funds - borrowed_funds). */
+    pub vault_available_a: u64,
+    /** The available liquidity of the vault B (deposited_funds - borrowed_funds). */
+    pub vault_available_b: u64,
+    /** The amount of token A that can still be borrowed in the market (borrow_limit_a - borrowed funds). */
+    pub borrow_limit_available_a: u64,
+    /** The amount of token B that can still be borrowed in the market (borrow_limit_b - borrowed funds). */
+    pub borrow_limit_available_b: u64,
+    /** Protocol fee rate from a market account represented as hundredths of a basis point (0.01% = 100). */
+    pub protocol_fee_rate: u16,
+    /** Protocol fee rate from a market account represented as hundredths of a basis point (0.01% = 100). */
+    pub protocol_fee_rate_on_collateral: u16,
+    /** The swap fee rate of a pool denominated in 1e6. */
+    pub swap_fee_rate: u16,
+    /** Current sqrt price. */
+    pub sqrt_price: u128,
+    /** Position lower tick index. */
+    pub tick_lower_index: i32,
+    /** Position upper tick index. */
+    pub tick_upper_index: i32,
+    /** The liquidation threshold of the market. */
+    pub liquidation_threshold: u32,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "wasm", wasm_expose)]
+pub struct MaxLpPositionResult {
+    /** The arguments for get_increase_lp_position_quote that open the largest possible position. */
+    pub quote_args: IncreaseLpPositionQuoteArgs,
+    /** The leverage of the largest possible position. */
+    pub leverage: f64,
+    /** The constraint that limits the position size. */
+    pub constraint: LpPositionSizeConstraint,
+}
+
+/// Calculates the largest lp position that can be opened in the range with the provided collateral.
+/// The position is opened with the maximum leverage of the market unless the borrowed amounts
+/// exceed the available vault liquidity or the market borrow limits, in which case the leverage is reduced.
+///
+/// # Parameters
+/// - `args`: MaxLpPositionArgs
+///
+/// # Returns
+/// - `MaxLpPositionResult`: the position arguments and the binding constraint
+#[cfg_attr(feature = "wasm", wasm_expose)]
+pub fn get_max_lp_position(args: MaxLpPositionArgs) -> Result<MaxLpPositionResult, CoreError> {
+    let sqrt_price = args.sqrt_price;
+
+    if args.tick_lower_index >= args.tick_upper_index {
+        return Err("Incorrect position tick index order: the lower tick must be less than the upper tick.");
+    }
+
+    if args.collateral_a == COMPUTED_AMOUNT || args.collateral_b == COMPUTED_AMOUNT || args.max_leverage < HUNDRED_PERCENT {
+        return Err(INVALID_ARGUMENTS);
+    }
+
+    let max_leverage = args.max_leverage as f64 / HUNDRED_PERCENT as f64;
+    let (mut borrow_a, mut borrow_b) = compute_borrow_for_leverage(
+        args.collateral_a,
+        args.collateral_b,
+        max_leverage,
+        args.protocol_fee_rate,
+        args.protocol_fee_rate_on_collateral,
+        sqrt_price,
+        args.tick_lower_index,
+        args.tick_upper_index,
+    )?;
+
+    // The borrowed amounts are proportional, so the whole borrow is scaled down by the most restrictive limit.
+    let limits = [
+        (borrow_a, args.vault_available_a, LpPositionSizeConstraint::VaultLiquidityA),
+        (borrow_a, args.borrow_limit_available_a, LpPositionSizeConstraint::BorrowLimitA),
+        (borrow_b, args.vault_available_b, LpPositionSizeConstraint::VaultLiquidityB),
+        (borrow_b, args.borrow_limit_available_b, LpPositionSizeConstraint::BorrowLimitB),
+    ];
+
+    let mut scale = 1.0;
+    let mut constraint = LpPositionSizeConstraint::MaxLeverage;
+    for (borrow, available, limit) in limits {
+        if borrow > available {
+            let limit_scale = available as f64 / borrow as f64;
+            if limit_scale < scale {
+                scale = limit_scale;
+                constraint = limit;
+            }
+        }
+    }
+
+    let mut leverage = max_leverage;
+    if constraint != LpPositionSizeConstraint::MaxLeverage {
+        borrow_a = ((borrow_a as f64 * scale) as u64)
+            .min(args.vault_available_a)
+            .min(args.borrow_limit_available_a);
+        borrow_b = ((borrow_b as f64 * scale) as u64)
+            .min(args.vault_available_b)
+            .min(args.borrow_limit_available_b);
+
+        // Invert the borrow formula of compute_borrow_for_leverage to get the reduced leverage.
+        let fee_rate = args.protocol_fee_rate as f64 / HUNDRED_PERCENT as f64;
+        let fee_rate_on_collateral = args.protocol_fee_rate_on_collateral as f64 / HUNDRED_PERCENT as f64;
+        let collateral = mul_by_sqrt_price_squared(args.collateral_a, sqrt_price, false)? as f64 + args.collateral_b as f64;
+        let borrow = mul_by_sqrt_price_squared(borrow_a, sqrt_price, false)? as f64 + borrow_b as f64;
+        let k = collateral * (1.0 - fee_rate_on_collateral);
+        leverage = if k > 0.0 {
+            (k + borrow * (1.0 - fee_rate)) / (k - borrow * fee_rate)
+        } else {
+            1.0
+        };
+    }
+
+    Ok(MaxLpPositionResult {
+        quote_args: IncreaseLpPositionQuoteArgs {
+            collateral_a: args.collateral_a,
+            collateral_b: args.collateral_b,
+            borrow_a,
+            borrow_b,
+            protocol_fee_rate: args.protocol_fee_rate,
+            protocol_fee_rate_on_collateral: args.protocol_fee_rate_on_collateral,
+            swap_fee_rate: args.swap_fee_rate,
+            sqrt_price,
+            tick_lower_index: args.tick_lower_index,
+            tick_upper_index: args.tick_upper_index,
+            liquidation_threshold: args.liquidation_threshold,
+        },
+        leverage,
+        constraint,
+    })
+}
+
 #[derive(Debug, Copy, Clone, PartialEq)]
 #[cfg_attr(feature = "wasm", wasm_expose)]
 pub struct RepayLpPositionDebtQuoteArgs {
@@ -462,65 +950,1175 @@ pub struct RepayLpPositionDebtQuoteArgs {
     pub repay_b: u64,
     /** Current sqrt price. */
     pub sqrt_price: u128,
-    /** The liquidation threshold of the market. */
-    pub liquidation_threshold: u32,
+    /** The liquidation threshold of the market. */
+    pub liquidation_threshold: u32,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "wasm", wasm_expose)]
+pub struct RepayLpPositionDebtQuoteResult {
+    /** The amount of token A to repay. */
+    pub repay_a: u64,
+    /** The amount of token B to repay. */
+    pub repay_b: u64,
+    pub debt_a: u64,
+    pub debt_b: u64,
+    pub leverage: f64,
+    pub liquidation_lower_price: f64,
+    pub liquidation_upper_price: f64,
+}
+
+#[cfg_attr(feature = "wasm", wasm_expose)]
+pub fn get_repay_lp_position_debt_quote(args: RepayLpPositionDebtQuoteArgs) -> Result<RepayLpPositionDebtQuoteResult, CoreError> {
+    let mut debt_a = args.debt_a;
+    let mut debt_b = args.debt_b;
+    let repay_a = args.repay_a;
+    let repay_b = args.repay_b;
+
+    if args.liquidity == 0 {
+        return Err("Position liquidity can't be zero.");
+    }
+
+    if debt_a < repay_a {
+        return Err("Position debt A is less than the repaid amount.");
+    }
+
+    if debt_b < repay_b {
+        return Err("Position debt b is less than the repaid amount.");
+    }
+
+    debt_a -= repay_a;
+    debt_b -= repay_b;
+
+    let liquidation_prices = get_lp_position_liquidation_prices(
+        args.tick_lower_index,
+        args.tick_upper_index,
+        args.liquidity,
+        args.leftovers_a,
+        args.leftovers_b,
+        debt_a,
+        debt_b,
+        args.liquidation_threshold,
+    )?;
+
+    let lower_sqrt_price = tick_index_to_sqrt_price(args.tick_lower_index);
+    let upper_sqrt_price = tick_index_to_sqrt_price(args.tick_upper_index);
+
+    let total = get_amounts_from_liquidity(args.liquidity, args.sqrt_price, lower_sqrt_price, upper_sqrt_price, false)?;
+    let leverage = compute_leverage(total.a + args.leftovers_a, total.b + args.leftovers_b, debt_a, debt_b, args.sqrt_price)?;
+
+    Ok(RepayLpPositionDebtQuoteResult {
+        repay_a,
+        repay_b,
+        debt_a,
+        debt_b,
+        leverage,
+        liquidation_lower_price: liquidation_prices.lower,
+        liquidation_upper_price: liquidation_prices.upper,
+    })
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "wasm", wasm_expose)]
+pub struct DecreaseLpPositionQuoteArgs {
+    /** The position liquidity. */
+    pub liquidity: u128,
+    /** Position lower tick index. */
+    pub tick_lower_index: i32,
+    /** Position upper tick index. */
+    pub tick_upper_index: i32,
+    /** The leftovers of a position in token A. */
+    pub leftovers_a: u64,
+    /** The leftovers of a position in token B. */
+    pub leftovers_b: u64,
+    /** The current debt of a position in token A. */
+    pub debt_a: u64,
+    /** The current debt of a position in token B. */
+    pub debt_b: u64,
+    /** Position decrease percentage (HUNDRED_PERCENT closes the position). */
+    pub decrease_percent: u32,
+    /** The token (TOKEN_A or TOKEN_B) all remaining funds are swapped to, or None to withdraw both tokens. */
+    pub swap_to_token: Option<u8>,
+    /** The swap fee rate of a pool denominated in 1e6. */
+    pub swap_fee_rate: u16,
+    /** Slippage tolerance in basis points applied to the amounts removed from the pool. */
+    pub slippage_tolerance_bps: u16,
+    /** Current sqrt price. */
+    pub sqrt_price: u128,
+    /** The liquidation threshold of the market. */
+    pub liquidation_threshold: u32,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "wasm", wasm_expose)]
+pub struct DecreaseLpPositionQuoteResult {
+    /** The amount of liquidity removed from the pool. */
+    pub removed_liquidity: u128,
+    /** The amount of token A removed from the pool. */
+    pub removed_a: u64,
+    /** The amount of token B removed from the pool. */
+    pub removed_b: u64,
+    /** The minimum amount of token A removed from the pool according to the slippage tolerance (min_removed_amount_a). */
+    pub min_removed_amount_a: u64,
+    /** The minimum amount of token B removed from the pool according to the slippage tolerance (min_removed_amount_b). */
+    pub min_removed_amount_b: u64,
+    pub swap_input: u64,
+    pub swap_output: u64,
+    pub swap_a_to_b: bool,
+    /** The amount of debt in token A that is repaid. */
+    pub repaid_debt_a: u64,
+    /** The amount of debt in token B that is repaid. */
+    pub repaid_debt_b: u64,
+    /** The amount of token A returned to the user. */
+    pub withdrawn_a: u64,
+    /** The amount of token B returned to the user. */
+    pub withdrawn_b: u64,
+    /** The position liquidity after the decrease. */
+    pub liquidity: u128,
+    /** The position debt in token A after the decrease. */
+    pub debt_a: u64,
+    /** The position debt in token B after the decrease. */
+    pub debt_b: u64,
+    pub leverage: f64,
+    pub liquidation_lower_price: f64,
+    pub liquidation_upper_price: f64,
+}
+
+/// Lp position decrease quote. The debt is repaid proportionally to the decrease percentage. If the removed amount of
+/// one token is not enough to repay its debt, the missing part is swapped from the other token.
+///
+/// # Parameters
+/// - `args`: DecreaseLpPositionQuoteArgs
+///
+/// # Returns
+/// - `DecreaseLpPositionQuoteResult`: quote result
+#[cfg_attr(feature = "wasm", wasm_expose)]
+pub fn get_decrease_lp_position_quote(args: DecreaseLpPositionQuoteArgs) -> Result<DecreaseLpPositionQuoteResult, CoreError> {
+    let sqrt_price = args.sqrt_price;
+
+    if args.tick_lower_index >= args.tick_upper_index {
+        return Err("Incorrect position tick index order: the lower tick must be less than the upper tick.");
+    }
+
+    if args.decrease_percent > HUNDRED_PERCENT {
+        return Err("Decrease percent can't be greater than HUNDRED_PERCENT.");
+    }
+
+    if let Some(swap_to_token) = args.swap_to_token {
+        if swap_to_token > TOKEN_B {
+            return Err(INVALID_ARGUMENTS);
+        }
+    }
+
+    let lower_sqrt_price = tick_index_to_sqrt_price(args.tick_lower_index);
+    let upper_sqrt_price = tick_index_to_sqrt_price(args.tick_upper_index);
+
+    let removed_liquidity = if args.decrease_percent == HUNDRED_PERCENT {
+        args.liquidity
+    } else {
+        args.liquidity.checked_mul(args.decrease_percent as u128).ok_or(ARITHMETIC_OVERFLOW)? / HUNDRED_PERCENT as u128
+    };
+    let removed = get_amounts_from_liquidity(removed_liquidity, sqrt_price, lower_sqrt_price, upper_sqrt_price, false)?;
+
+    let removed_leftovers_a = try_mul_div(args.leftovers_a, args.decrease_percent as u128, HUNDRED_PERCENT as u128, false)?;
+    let removed_leftovers_b = try_mul_div(args.leftovers_b, args.decrease_percent as u128, HUNDRED_PERCENT as u128, false)?;
+
+    let repaid_debt_a = try_mul_div(args.debt_a, args.decrease_percent as u128, HUNDRED_PERCENT as u128, true)?;
+    let repaid_debt_b = try_mul_div(args.debt_b, args.decrease_percent as u128, HUNDRED_PERCENT as u128, true)?;
+
+    let mut available_a = removed.a + removed_leftovers_a;
+    let mut available_b = removed.b + removed_leftovers_b;
+
+    let mut swap_input = 0;
+    let mut swap_output = 0;
+    let mut swap_a_to_b = false;
+
+    // Swap the missing part of the debt from the opposite token.
+    if available_a < repaid_debt_a && available_b < repaid_debt_b {
+        return Err("The removed amounts are not enough to repay the debt.");
+    } else if available_a < repaid_debt_a {
+        swap_output = repaid_debt_a - available_a;
+        swap_input = reverse_apply_swap_fee(mul_by_sqrt_price_squared(swap_output, sqrt_price, true)?, args.swap_fee_rate, true)?;
+        if swap_input > available_b - repaid_debt_b {
+            return Err("The removed amounts are not enough to repay the debt.");
+        }
+        available_a = 0;
+        available_b -= repaid_debt_b + swap_input;
+    } else if available_b < repaid_debt_b {
+        swap_output = repaid_debt_b - available_b;
+        swap_input = reverse_apply_swap_fee(div_by_sqrt_price_squared(swap_output, sqrt_price, true)?, args.swap_fee_rate, true)?;
+        if swap_input > available_a - repaid_debt_a {
+            return Err("The removed amounts are not enough to repay the debt.");
+        }
+        swap_a_to_b = true;
+        available_a -= repaid_debt_a + swap_input;
+        available_b = 0;
+    } else {
+        available_a -= repaid_debt_a;
+        available_b -= repaid_debt_b;
+    }
+
+    // Swap the remaining funds to the requested token.
+    if args.swap_to_token == Some(TOKEN_A) && available_b > 0 {
+        let output = div_by_sqrt_price_squared(apply_swap_fee(available_b, args.swap_fee_rate, false)?, sqrt_price, false)?;
+        // Both swaps are in the same direction (B->A) because the first one can only leave some token B.
+        swap_input += available_b;
+        swap_output += output;
+        swap_a_to_b = false;
+        available_a += output;
+        available_b = 0;
+    } else if args.swap_to_token == Some(TOKEN_B) && available_a > 0 {
+        let output = mul_by_sqrt_price_squared(apply_swap_fee(available_a, args.swap_fee_rate, false)?, sqrt_price, false)?;
+        swap_input += available_a;
+        swap_output += output;
+        swap_a_to_b = true;
+        available_a = 0;
+        available_b += output;
+    }
+
+    let liquidity = args.liquidity - removed_liquidity;
+    let leftovers_a = args.leftovers_a - removed_leftovers_a;
+    let leftovers_b = args.leftovers_b - removed_leftovers_b;
+    let debt_a = args.debt_a - repaid_debt_a;
+    let debt_b = args.debt_b - repaid_debt_b;
+
+    let liquidation_prices = get_lp_position_liquidation_prices(
+        args.tick_lower_index,
+        args.tick_upper_index,
+        liquidity,
+        leftovers_a,
+        leftovers_b,
+        debt_a,
+        debt_b,
+        args.liquidation_threshold,
+    )?;
+
+    let total = get_amounts_from_liquidity(liquidity, sqrt_price, lower_sqrt_price, upper_sqrt_price, false)?;
+    let leverage = compute_leverage(total.a + leftovers_a, total.b + leftovers_b, debt_a, debt_b, sqrt_price)?;
+
+    Ok(DecreaseLpPositionQuoteResult {
+        removed_liquidity,
+        removed_a: removed.a,
+        removed_b: removed.b,
+        min_removed_amount_a: try_get_min_amount_with_slippage_tolerance(removed.a, args.slippage_tolerance_bps)?,
+        min_removed_amount_b: try_get_min_amount_with_slippage_tolerance(removed.b, args.slippage_tolerance_bps)?,
+        swap_input,
+        swap_output,
+        swap_a_to_b,
+        repaid_debt_a,
+        repaid_debt_b,
+        withdrawn_a: available_a,
+        withdrawn_b: available_b,
+        liquidity,
+        debt_a,
+        debt_b,
+        leverage,
+        liquidation_lower_price: liquidation_prices.lower,
+        liquidation_upper_price: liquidation_prices.upper,
+    })
+}
+
+/// Returns the token the position funds are swapped to when the limit order is executed.
+///
+/// # Parameters
+/// - `flags`: Position flags
+/// - `upper_limit_order`: True for the upper limit order, false for the lower limit order
+///
+/// # Returns
+/// - `Option<u8>`: TOKEN_A, TOKEN_B or None if both tokens are withdrawn
+pub fn get_lp_position_limit_order_swap_to_token(flags: u32, upper_limit_order: bool) -> Result<Option<u8>, CoreError> {
+    let (swap_to_token_a, swap_to_token_b) = if upper_limit_order {
+        (TUNA_POSITION_FLAGS_UPPER_LIMIT_ORDER_SWAP_TO_TOKEN_A, TUNA_POSITION_FLAGS_UPPER_LIMIT_ORDER_SWAP_TO_TOKEN_B)
+    } else {
+        (TUNA_POSITION_FLAGS_LOWER_LIMIT_ORDER_SWAP_TO_TOKEN_A, TUNA_POSITION_FLAGS_LOWER_LIMIT_ORDER_SWAP_TO_TOKEN_B)
+    };
+
+    match (flags & swap_to_token_a != 0, flags & swap_to_token_b != 0) {
+        (true, true) => Err("The limit order can't swap to both token A and token B."),
+        (true, false) => Ok(Some(TOKEN_A)),
+        (false, true) => Ok(Some(TOKEN_B)),
+        (false, false) => Ok(None),
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "wasm", wasm_expose)]
+pub struct LpLimitOrderExecutionQuoteArgs {
+    /** The position liquidity. */
+    pub liquidity: u128,
+    /** Position lower tick index. */
+    pub tick_lower_index: i32,
+    /** Position upper tick index. */
+    pub tick_upper_index: i32,
+    /** The leftovers of a position in token A. */
+    pub leftovers_a: u64,
+    /** The leftovers of a position in token B. */
+    pub leftovers_b: u64,
+    /** The current debt of a position in token A. */
+    pub debt_a: u64,
+    /** The current debt of a position in token B. */
+    pub debt_b: u64,
+    /** Position flags. */
+    pub flags: u32,
+    /** True if the upper limit order is executed, false if the lower limit order is executed. */
+    pub upper_limit_order: bool,
+    /** The sqrt price at which the limit order is executed. */
+    pub trigger_sqrt_price: u128,
+    /** The swap fee rate of a pool denominated in 1e6. */
+    pub swap_fee_rate: u16,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "wasm", wasm_expose)]
+pub struct LpLimitOrderExecutionQuoteResult {
+    /** The amount of token A removed from the pool. */
+    pub removed_a: u64,
+    /** The amount of token B removed from the pool. */
+    pub removed_b: u64,
+    /** The amount of debt in token A that is repaid. */
+    pub repaid_debt_a: u64,
+    /** The amount of debt in token B that is repaid. */
+    pub repaid_debt_b: u64,
+    pub swap_input: u64,
+    pub swap_output: u64,
+    pub swap_a_to_b: bool,
+    /** The amount of token A paid out to the user. */
+    pub amount_a: u64,
+    /** The amount of token B paid out to the user. */
+    pub amount_b: u64,
+}
+
+/// Returns the outcome of the limit order execution. The position is closed at the trigger price: the liquidity is
+/// removed, the debt is repaid and the remaining funds are swapped to the token set by the position flags.
+///
+/// # Parameters
+/// - `args`: LpLimitOrderExecutionQuoteArgs
+///
+/// # Returns
+/// - `LpLimitOrderExecutionQuoteResult`: quote result
+#[cfg_attr(feature = "wasm", wasm_expose)]
+pub fn get_lp_limit_order_execution_quote(args: LpLimitOrderExecutionQuoteArgs) -> Result<LpLimitOrderExecutionQuoteResult, CoreError> {
+    // Conflicting flags of any limit order make the position flags invalid.
+    let lower_swap_to_token = get_lp_position_limit_order_swap_to_token(args.flags, false)?;
+    let upper_swap_to_token = get_lp_position_limit_order_swap_to_token(args.flags, true)?;
+
+    let quote = get_decrease_lp_position_quote(DecreaseLpPositionQuoteArgs {
+        liquidity: args.liquidity,
+        tick_lower_index: args.tick_lower_index,
+        tick_upper_index: args.tick_upper_index,
+        leftovers_a: args.leftovers_a,
+        leftovers_b: args.leftovers_b,
+        debt_a: args.debt_a,
+        debt_b: args.debt_b,
+        decrease_percent: HUNDRED_PERCENT,
+        swap_to_token: if args.upper_limit_order {
+            upper_swap_to_token
+        } else {
+            lower_swap_to_token
+        },
+        swap_fee_rate: args.swap_fee_rate,
+        slippage_tolerance_bps: 0,
+        sqrt_price: args.trigger_sqrt_price,
+        liquidation_threshold: 0,
+    })?;
+
+    Ok(LpLimitOrderExecutionQuoteResult {
+        removed_a: quote.removed_a,
+        removed_b: quote.removed_b,
+        repaid_debt_a: quote.repaid_debt_a,
+        repaid_debt_b: quote.repaid_debt_b,
+        swap_input: quote.swap_input,
+        swap_output: quote.swap_output,
+        swap_a_to_b: quote.swap_a_to_b,
+        amount_a: quote.withdrawn_a,
+        amount_b: quote.withdrawn_b,
+    })
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "wasm", wasm_expose)]
+pub struct LiquidateLpPositionQuoteArgs {
+    /** The position liquidity. */
+    pub liquidity: u128,
+    /** Position lower tick index. */
+    pub tick_lower_index: i32,
+    /** Position upper tick index. */
+    pub tick_upper_index: i32,
+    /** The leftovers of a position in token A. */
+    pub leftovers_a: u64,
+    /** The leftovers of a position in token B. */
+    pub leftovers_b: u64,
+    /** The current debt of a position in token A. */
+    pub debt_a: u64,
+    /** The current debt of a position in token B. */
+    pub debt_b: u64,
+    /** Liquidation percentage (HUNDRED_PERCENT liquidates the whole position). */
+    pub decrease_percent: u32,
+    /** The liquidation fee of the market represented as hundredths of a basis point. */
+    pub liquidation_fee: u32,
+    /** The swap fee rate of a pool denominated in 1e6. */
+    pub swap_fee_rate: u16,
+    /** Current sqrt price. */
+    pub sqrt_price: u128,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "wasm", wasm_expose)]
+pub struct LiquidateLpPositionQuoteResult {
+    /** The amount of liquidity removed from the pool. */
+    pub removed_liquidity: u128,
+    /** The amount of token A removed from the position including leftovers. */
+    pub removed_a: u64,
+    /** The amount of token B removed from the position including leftovers. */
+    pub removed_b: u64,
+    pub swap_input: u64,
+    pub swap_output: u64,
+    pub swap_a_to_b: bool,
+    /** The amount of debt in token A that is repaid to the vault. */
+    pub repaid_debt_a: u64,
+    /** The amount of debt in token B that is repaid to the vault. */
+    pub repaid_debt_b: u64,
+    /** The part of the liquidated debt in token A that can't be repaid and becomes a bad debt of the vault. */
+    pub bad_debt_a: u64,
+    /** The part of the liquidated debt in token B that can't be repaid and becomes a bad debt of the vault. */
+    pub bad_debt_b: u64,
+    /** The part of the liquidation fee in token A transferred to the protocol fee recipient. */
+    pub protocol_fee_a: u64,
+    /** The part of the liquidation fee in token B transferred to the protocol fee recipient. */
+    pub protocol_fee_b: u64,
+    /** The part of the liquidation fee in token A paid to the liquidator. The program doesn't reward the liquidator, so it's zero. */
+    pub liquidator_fee_a: u64,
+    /** The part of the liquidation fee in token B paid to the liquidator. The program doesn't reward the liquidator, so it's zero. */
+    pub liquidator_fee_b: u64,
+    /** The position liquidity after the liquidation. */
+    pub liquidity: u128,
+    /** The leftovers of the position in token A after the liquidation, including the funds remaining after the debt repayment.
+     *  They stay in the position and can be withdrawn by the position owner. */
+    pub leftovers_a: u64,
+    /** The leftovers of the position in token B after the liquidation, including the funds remaining after the debt repayment.
+     *  They stay in the position and can be withdrawn by the position owner. */
+    pub leftovers_b: u64,
+    /** The position debt in token A after the liquidation. */
+    pub debt_a: u64,
+    /** The position debt in token B after the liquidation. */
+    pub debt_b: u64,
+}
+
+/// Lp position liquidation quote. The liquidated part of the debt is repaid first, the missing part of one token is
+/// swapped from the other token. The liquidation fee is charged on the removed amounts, but it can't exceed the funds
+/// remaining after the debt repayment. The whole fee goes to the protocol fee recipient. The rest of the funds stays in
+/// the position as leftovers. If the removed funds are not enough to repay the debt, the unpaid part becomes a bad debt.
+///
+/// # Parameters
+/// - `args`: LiquidateLpPositionQuoteArgs
+///
+/// # Returns
+/// - `LiquidateLpPositionQuoteResult`: quote result
+#[cfg_attr(feature = "wasm", wasm_expose)]
+pub fn get_liquidate_lp_position_quote(args: LiquidateLpPositionQuoteArgs) -> Result<LiquidateLpPositionQuoteResult, CoreError> {
+    let sqrt_price = args.sqrt_price;
+
+    if args.tick_lower_index >= args.tick_upper_index {
+        return Err("Incorrect position tick index order: the lower tick must be less than the upper tick.");
+    }
+
+    if args.decrease_percent > HUNDRED_PERCENT || args.liquidation_fee > HUNDRED_PERCENT {
+        return Err(INVALID_ARGUMENTS);
+    }
+
+    let lower_sqrt_price = tick_index_to_sqrt_price(args.tick_lower_index);
+    let upper_sqrt_price = tick_index_to_sqrt_price(args.tick_upper_index);
+
+    let removed_liquidity = if args.decrease_percent == HUNDRED_PERCENT {
+        args.liquidity
+    } else {
+        args.liquidity.checked_mul(args.decrease_percent as u128).ok_or(ARITHMETIC_OVERFLOW)? / HUNDRED_PERCENT as u128
+    };
+    let removed = get_amounts_from_liquidity(removed_liquidity, sqrt_price, lower_sqrt_price, upper_sqrt_price, false)?;
+
+    let removed_leftovers_a = try_mul_div(args.leftovers_a, args.decrease_percent as u128, HUNDRED_PERCENT as u128, false)?;
+    let removed_leftovers_b = try_mul_div(args.leftovers_b, args.decrease_percent as u128, HUNDRED_PERCENT as u128, false)?;
+    let removed_a = removed.a.checked_add(removed_leftovers_a).ok_or(ARITHMETIC_OVERFLOW)?;
+    let removed_b = removed.b.checked_add(removed_leftovers_b).ok_or(ARITHMETIC_OVERFLOW)?;
+
+    let liquidated_debt_a = try_mul_div(args.debt_a, args.decrease_percent as u128, HUNDRED_PERCENT as u128, true)?;
+    let liquidated_debt_b = try_mul_div(args.debt_b, args.decrease_percent as u128, HUNDRED_PERCENT as u128, true)?;
+
+    let mut available_a = removed_a;
+    let mut available_b = removed_b;
+    let mut repaid_debt_a = liquidated_debt_a.min(available_a);
+    let mut repaid_debt_b = liquidated_debt_b.min(available_b);
+    available_a -= repaid_debt_a;
+    available_b -= repaid_debt_b;
+
+    let mut swap_input = 0;
+    let mut swap_output = 0;
+    let mut swap_a_to_b = false;
+
+    // Swap the missing part of the debt from the opposite token. If it's not enough, swap everything that is left.
+    if repaid_debt_a < liquidated_debt_a && available_b > 0 {
+        let missing_a = liquidated_debt_a - repaid_debt_a;
+        swap_input = reverse_apply_swap_fee(mul_by_sqrt_price_squared(missing_a, sqrt_price, true)?, args.swap_fee_rate, true)?;
+        if swap_input <= available_b {
+            swap_output = missing_a;
+        } else {
+            swap_input = available_b;
+            swap_output = div_by_sqrt_price_squared(apply_swap_fee(swap_input, args.swap_fee_rate, false)?, sqrt_price, false)?;
+        }
+        available_b -= swap_input;
+        repaid_debt_a += swap_output;
+    } else if repaid_debt_b < liquidated_debt_b && available_a > 0 {
+        let missing_b = liquidated_debt_b - repaid_debt_b;
+        swap_input = reverse_apply_swap_fee(div_by_sqrt_price_squared(missing_b, sqrt_price, true)?, args.swap_fee_rate, true)?;
+        if swap_input <= available_a {
+            swap_output = missing_b;
+        } else {
+            swap_input = available_a;
+            swap_output = mul_by_sqrt_price_squared(apply_swap_fee(swap_input, args.swap_fee_rate, false)?, sqrt_price, false)?;
+        }
+        swap_a_to_b = true;
+        available_a -= swap_input;
+        repaid_debt_b += swap_output;
+    }
+
+    let protocol_fee_a = try_mul_div(removed_a, args.liquidation_fee as u128, HUNDRED_PERCENT as u128, false)?.min(available_a);
+    let protocol_fee_b = try_mul_div(removed_b, args.liquidation_fee as u128, HUNDRED_PERCENT as u128, false)?.min(available_b);
+
+    Ok(LiquidateLpPositionQuoteResult {
+        removed_liquidity,
+        removed_a,
+        removed_b,
+        swap_input,
+        swap_output,
+        swap_a_to_b,
+        repaid_debt_a,
+        repaid_debt_b,
+        bad_debt_a: liquidated_debt_a - repaid_debt_a,
+        bad_debt_b: liquidated_debt_b - repaid_debt_b,
+        protocol_fee_a,
+        protocol_fee_b,
+        liquidator_fee_a: 0,
+        liquidator_fee_b: 0,
+        liquidity: args.liquidity - removed_liquidity,
+        leftovers_a: args.leftovers_a - removed_leftovers_a + available_a - protocol_fee_a,
+        leftovers_b: args.leftovers_b - removed_leftovers_b + available_b - protocol_fee_b,
+        debt_a: args.debt_a - liquidated_debt_a,
+        debt_b: args.debt_b - liquidated_debt_b,
+    })
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "wasm", wasm_expose)]
+pub struct RebalanceLpPositionQuoteArgs {
+    /** The position liquidity. */
+    pub liquidity: u128,
+    /** Position lower tick index. */
+    pub tick_lower_index: i32,
+    /** Position upper tick index. */
+    pub tick_upper_index: i32,
+    /** The leftovers of a position in token A. */
+    pub leftovers_a: u64,
+    /** The leftovers of a position in token B. */
+    pub leftovers_b: u64,
+    /** The current debt of a position in token A. */
+    pub debt_a: u64,
+    /** The current debt of a position in token B. */
+    pub debt_b: u64,
+    /** The rebalance threshold of a position in ticks. */
+    pub rebalance_threshold_ticks: u32,
+    /** The rebalance protocol fee of a market denominated in HUNDRED_PERCENT. */
+    pub rebalance_protocol_fee: u32,
+    /** The swap fee rate of a pool denominated in 1e6. */
+    pub swap_fee_rate: u16,
+    /** The tick spacing of a pool. */
+    pub tick_spacing: u16,
+    /** Current tick index of a pool. */
+    pub tick_current_index: i32,
+    /** Current sqrt price. */
+    pub sqrt_price: u128,
+    /** The liquidation threshold of the market. */
+    pub liquidation_threshold: u32,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "wasm", wasm_expose)]
+pub struct RebalanceLpPositionQuoteResult {
+    /** True if the current tick is out of the position range by at least the rebalance threshold. */
+    pub rebalance_conditions_met: bool,
+    /** The lower tick index of the rebalanced position. */
+    pub tick_lower_index: i32,
+    /** The upper tick index of the rebalanced position. */
+    pub tick_upper_index: i32,
+    pub swap_input: u64,
+    pub swap_output: u64,
+    pub swap_a_to_b: bool,
+    /** The rebalance protocol fee in token A. */
+    pub protocol_fee_a: u64,
+    /** The rebalance protocol fee in token B. */
+    pub protocol_fee_b: u64,
+    /** The position liquidity after the rebalance. */
+    pub liquidity: u128,
+    /** The position leftovers in token A after the rebalance. */
+    pub leftovers_a: u64,
+    /** The position leftovers in token B after the rebalance. */
+    pub leftovers_b: u64,
+    pub leverage: f64,
+    pub liquidation_lower_price: f64,
+    pub liquidation_upper_price: f64,
+}
+
+/// Computes the tick range of a rebalanced position. The new range has the same width as the old one
+/// and is centered around the current tick.
+///
+/// # Parameters
+/// - `tick_current_index`: current tick index of a pool
+/// - `tick_spacing`: tick spacing of a pool
+/// - `tick_lower_index`: position lower tick index
+/// - `tick_upper_index`: position upper tick index
+///
+/// # Returns
+/// - `(i32, i32)`: the new lower and upper tick indexes
+pub fn get_rebalanced_position_tick_range(tick_current_index: i32, tick_spacing: u16, tick_lower_index: i32, tick_upper_index: i32) -> (i32, i32) {
+    let position_range = tick_upper_index - tick_lower_index;
+    let half_range = position_range / (2 * tick_spacing as i32) * tick_spacing as i32;
+    let position_center = tick_current_index.div_euclid(tick_spacing as i32) * tick_spacing as i32;
+
+    let mut new_tick_lower_index = (position_center - half_range).max(MIN_TICK_INDEX);
+    let new_tick_upper_index = (new_tick_lower_index + position_range).min(MAX_TICK_INDEX);
+    if new_tick_upper_index == MAX_TICK_INDEX {
+        new_tick_lower_index = MAX_TICK_INDEX - position_range;
+    }
+
+    (new_tick_lower_index, new_tick_upper_index)
+}
+
+/// Lp position rebalance quote. The position is re-centered around the current tick, the rebalance protocol fee is
+/// charged from all position funds, and the remaining funds are swapped at the current price to match the ratio
+/// of the new range.
+///
+/// # Parameters
+/// - `args`: RebalanceLpPositionQuoteArgs
+///
+/// # Returns
+/// - `RebalanceLpPositionQuoteResult`: quote result
+#[cfg_attr(feature = "wasm", wasm_expose)]
+pub fn get_rebalance_lp_position_quote(args: RebalanceLpPositionQuoteArgs) -> Result<RebalanceLpPositionQuoteResult, CoreError> {
+    let sqrt_price = args.sqrt_price;
+
+    if args.tick_lower_index >= args.tick_upper_index {
+        return Err("Incorrect position tick index order: the lower tick must be less than the upper tick.");
+    }
+
+    if args.tick_spacing == 0 || args.rebalance_protocol_fee > HUNDRED_PERCENT {
+        return Err(INVALID_ARGUMENTS);
+    }
+
+    let threshold = args.rebalance_threshold_ticks as i64;
+    let tick_current_index = args.tick_current_index as i64;
+    let rebalance_conditions_met =
+        tick_current_index < args.tick_lower_index as i64 - threshold || tick_current_index >= args.tick_upper_index as i64 + threshold;
+
+    let (tick_lower_index, tick_upper_index) =
+        get_rebalanced_position_tick_range(args.tick_current_index, args.tick_spacing, args.tick_lower_index, args.tick_upper_index);
+
+    let removed = get_amounts_from_liquidity(
+        args.liquidity,
+        sqrt_price,
+        tick_index_to_sqrt_price(args.tick_lower_index),
+        tick_index_to_sqrt_price(args.tick_upper_index),
+        false,
+    )?;
+    let total_a = removed.a.checked_add(args.leftovers_a).ok_or(ARITHMETIC_OVERFLOW)?;
+    let total_b = removed.b.checked_add(args.leftovers_b).ok_or(ARITHMETIC_OVERFLOW)?;
+
+    let protocol_fee_a = try_mul_div(total_a, args.rebalance_protocol_fee as u128, HUNDRED_PERCENT as u128, false)?;
+    let protocol_fee_b = try_mul_div(total_b, args.rebalance_protocol_fee as u128, HUNDRED_PERCENT as u128, false)?;
+    let available_a = total_a - protocol_fee_a;
+    let available_b = total_b - protocol_fee_b;
+
+    let deposit = deposit_with_swap(available_a, available_b, sqrt_price, tick_lower_index, tick_upper_index, args.swap_fee_rate)?;
+    let liquidity = deposit.liquidity;
+    let leftovers_a = deposit.leftovers_a;
+    let leftovers_b = deposit.leftovers_b;
+
+    let liquidation_prices = get_lp_position_liquidation_prices(
+        tick_lower_index,
+        tick_upper_index,
+        liquidity,
+        leftovers_a,
+        leftovers_b,
+        args.debt_a,
+        args.debt_b,
+        args.liquidation_threshold,
+    )?;
+
+    let total = get_amounts_from_liquidity(
+        liquidity,
+        sqrt_price,
+        tick_index_to_sqrt_price(tick_lower_index),
+        tick_index_to_sqrt_price(tick_upper_index),
+        false,
+    )?;
+    let leverage = compute_leverage(total.a + leftovers_a, total.b + leftovers_b, args.debt_a, args.debt_b, sqrt_price)?;
+
+    Ok(RebalanceLpPositionQuoteResult {
+        rebalance_conditions_met,
+        tick_lower_index,
+        tick_upper_index,
+        swap_input: deposit.swap_input,
+        swap_output: deposit.swap_output,
+        swap_a_to_b: deposit.swap_a_to_b,
+        protocol_fee_a,
+        protocol_fee_b,
+        liquidity,
+        leftovers_a,
+        leftovers_b,
+        leverage,
+        liquidation_lower_price: liquidation_prices.lower,
+        liquidation_upper_price: liquidation_prices.upper,
+    })
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "wasm", wasm_expose)]
+pub struct CollectAndCompoundFeesQuoteArgs {
+    /** The position liquidity. */
+    pub liquidity: u128,
+    /** Position lower tick index. */
+    pub tick_lower_index: i32,
+    /** Position upper tick index. */
+    pub tick_upper_index: i32,
+    /** The leftovers of a position in token A. */
+    pub leftovers_a: u64,
+    /** The leftovers of a position in token B. */
+    pub leftovers_b: u64,
+    /** The current debt of a position in token A. */
+    pub debt_a: u64,
+    /** The current debt of a position in token B. */
+    pub debt_b: u64,
+    /** The uncollected fees of a position in token A. */
+    pub fees_a: u64,
+    /** The uncollected fees of a position in token B. */
+    pub fees_b: u64,
+    /** If true, additional funds are borrowed to keep the current leverage of the position. */
+    pub with_leverage: bool,
+    /** Protocol fee rate from a market account represented as hundredths of a basis point (0.01% = 100). */
+    pub protocol_fee_rate: u16,
+    /** Protocol fee rate from a market account represented as hundredths of a basis point (0.01% = 100). */
+    pub protocol_fee_rate_on_collateral: u16,
+    /** The swap fee rate of a pool denominated in 1e6. */
+    pub swap_fee_rate: u16,
+    /** Current sqrt price. */
+    pub sqrt_price: u128,
+    /** The liquidation threshold of the market. */
+    pub liquidation_threshold: u32,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "wasm", wasm_expose)]
+pub struct CollectAndCompoundFeesQuoteResult {
+    /** The amount of token A borrowed in addition to the collected fees. */
+    pub borrow_a: u64,
+    /** The amount of token B borrowed in addition to the collected fees. */
+    pub borrow_b: u64,
+    pub protocol_fee_a: u64,
+    pub protocol_fee_b: u64,
+    pub swap_input: u64,
+    pub swap_output: u64,
+    pub swap_a_to_b: bool,
+    /** The amount of liquidity added to the position. */
+    pub added_liquidity: u128,
+    /** The position liquidity after compounding. */
+    pub liquidity: u128,
+    /** The position leftovers in token A after compounding. */
+    pub leftovers_a: u64,
+    /** The position leftovers in token B after compounding. */
+    pub leftovers_b: u64,
+    /** The position debt in token A after compounding. */
+    pub debt_a: u64,
+    /** The position debt in token B after compounding. */
+    pub debt_b: u64,
+    pub leverage: f64,
+    pub liquidation_lower_price: f64,
+    pub liquidation_upper_price: f64,
+}
+
+/// Collect and compound fees quote. The collected fees and the position leftovers are added to the position
+/// liquidity. If `with_leverage` is set, the debt is increased proportionally to the collected fees, so the position
+/// leverage is kept unchanged, otherwise the fees are compounded as collateral and the leverage decreases.
+///
+/// # Parameters
+/// - `args`: CollectAndCompoundFeesQuoteArgs
+///
+/// # Returns
+/// - `CollectAndCompoundFeesQuoteResult`: quote result
+#[cfg_attr(feature = "wasm", wasm_expose)]
+pub fn get_collect_and_compound_fees_quote(args: CollectAndCompoundFeesQuoteArgs) -> Result<CollectAndCompoundFeesQuoteResult, CoreError> {
+    let sqrt_price = args.sqrt_price;
+
+    if args.tick_lower_index >= args.tick_upper_index {
+        return Err("Incorrect position tick index order: the lower tick must be less than the upper tick.");
+    }
+
+    let lower_sqrt_price = tick_index_to_sqrt_price(args.tick_lower_index);
+    let upper_sqrt_price = tick_index_to_sqrt_price(args.tick_upper_index);
+
+    let mut borrow_a = 0;
+    let mut borrow_b = 0;
+
+    if args.with_leverage && (args.debt_a > 0 || args.debt_b > 0) {
+        let position = get_amounts_from_liquidity(args.liquidity, sqrt_price, lower_sqrt_price, upper_sqrt_price, false)?;
+        let total_a = position.a.checked_add(args.leftovers_a).ok_or(ARITHMETIC_OVERFLOW)?;
+        let total_b = position.b.checked_add(args.leftovers_b).ok_or(ARITHMETIC_OVERFLOW)?;
+        let total = mul_by_sqrt_price_squared(total_a, sqrt_price, false)?
+            .checked_add(total_b)
+            .ok_or(ARITHMETIC_OVERFLOW)?;
+        let debt = mul_by_sqrt_price_squared(args.debt_a, sqrt_price, true)?
+            .checked_add(args.debt_b)
+            .ok_or(ARITHMETIC_OVERFLOW)?;
+        if debt >= total {
+            return Err("The debt is greater than the total size");
+        }
+
+        let fees = mul_by_sqrt_price_squared(args.fees_a, sqrt_price, false)?
+            .checked_add(args.fees_b)
+            .ok_or(ARITHMETIC_OVERFLOW)?;
+        borrow_a = try_mul_div(args.debt_a, fees as u128, (total - debt) as u128, false)?;
+        borrow_b = try_mul_div(args.debt_b, fees as u128, (total - debt) as u128, false)?;
+    }
+
+    let protocol_fee_a = calculate_tuna_protocol_fee(args.fees_a, borrow_a, args.protocol_fee_rate_on_collateral, args.protocol_fee_rate);
+    let protocol_fee_b = calculate_tuna_protocol_fee(args.fees_b, borrow_b, args.protocol_fee_rate_on_collateral, args.protocol_fee_rate);
+
+    let available_a = args
+        .fees_a
+        .checked_add(borrow_a)
+        .and_then(|amount| amount.checked_sub(protocol_fee_a))
+        .and_then(|amount| amount.checked_add(args.leftovers_a))
+        .ok_or(ARITHMETIC_OVERFLOW)?;
+    let available_b = args
+        .fees_b
+        .checked_add(borrow_b)
+        .and_then(|amount| amount.checked_sub(protocol_fee_b))
+        .and_then(|amount| amount.checked_add(args.leftovers_b))
+        .ok_or(ARITHMETIC_OVERFLOW)?;
+
+    let deposit = deposit_with_swap(available_a, available_b, sqrt_price, args.tick_lower_index, args.tick_upper_index, args.swap_fee_rate)?;
+
+    let liquidity = args.liquidity.checked_add(deposit.liquidity).ok_or(ARITHMETIC_OVERFLOW)?;
+    let debt_a = args.debt_a.checked_add(borrow_a).ok_or(ARITHMETIC_OVERFLOW)?;
+    let debt_b = args.debt_b.checked_add(borrow_b).ok_or(ARITHMETIC_OVERFLOW)?;
+
+    let liquidation_prices = get_lp_position_liquidation_prices(
+        args.tick_lower_index,
+        args.tick_upper_index,
+        liquidity,
+        deposit.leftovers_a,
+        deposit.leftovers_b,
+        debt_a,
+        debt_b,
+        args.liquidation_threshold,
+    )?;
+
+    let total = get_amounts_from_liquidity(liquidity, sqrt_price, lower_sqrt_price, upper_sqrt_price, false)?;
+    let total_a = total.a.checked_add(deposit.leftovers_a).ok_or(ARITHMETIC_OVERFLOW)?;
+    let total_b = total.b.checked_add(deposit.leftovers_b).ok_or(ARITHMETIC_OVERFLOW)?;
+    let leverage = compute_leverage(total_a, total_b, debt_a, debt_b, sqrt_price)?;
+
+    Ok(CollectAndCompoundFeesQuoteResult {
+        borrow_a,
+        borrow_b,
+        protocol_fee_a,
+        protocol_fee_b,
+        swap_input: deposit.swap_input,
+        swap_output: deposit.swap_output,
+        swap_a_to_b: deposit.swap_a_to_b,
+        added_liquidity: deposit.liquidity,
+        liquidity,
+        leftovers_a: deposit.leftovers_a,
+        leftovers_b: deposit.leftovers_b,
+        debt_a,
+        debt_b,
+        leverage,
+        liquidation_lower_price: liquidation_prices.lower,
+        liquidation_upper_price: liquidation_prices.upper,
+    })
+}
+
+struct PositionDeposit {
+    swap_input: u64,
+    swap_output: u64,
+    swap_a_to_b: bool,
+    liquidity: u128,
+    leftovers_a: u64,
+    leftovers_b: u64,
+}
+
+/// Swaps the excess of one token at the current price to match the ratio of the position range and computes
+/// the liquidity that can be added with the resulting amounts.
+fn deposit_with_swap(
+    mut available_a: u64,
+    mut available_b: u64,
+    sqrt_price: u128,
+    tick_lower_index: i32,
+    tick_upper_index: i32,
+    swap_fee_rate: u16,
+) -> Result<PositionDeposit, CoreError> {
+    let lower_sqrt_price = tick_index_to_sqrt_price(tick_lower_index);
+    let upper_sqrt_price = tick_index_to_sqrt_price(tick_upper_index);
+
+    // The swap fee reduces the output amount, so the swap input x (valued in token B) is solved from:
+    // b' = ratio_b * total', where total' = total - x * fee.
+    let position_ratio = position_ratio_x64(sqrt_price, tick_lower_index, tick_upper_index);
+    let total_value_b = mul_by_sqrt_price_squared(available_a, sqrt_price, false)?
+        .checked_add(available_b)
+        .ok_or(ARITHMETIC_OVERFLOW)?;
+    let target_b = try_mul_div(total_value_b, position_ratio.ratio_b, 1 << 64, false)?;
+
+    let one_x64 = (HUNDRED_PERCENT as u128) << 64;
+    let fee_rate = swap_fee_rate as u128;
+
+    let mut swap_input = 0;
+    let mut swap_output = 0;
+    let mut swap_a_to_b = false;
+
+    if target_b > available_b {
+        let swap_input_b = try_mul_div(target_b - available_b, one_x64, one_x64 - (fee_rate << 64) + position_ratio.ratio_b * fee_rate, false)?;
+        swap_input = div_by_sqrt_price_squared(swap_input_b, sqrt_price, false)?.min(available_a);
+        swap_output = mul_by_sqrt_price_squared(apply_swap_fee(swap_input, swap_fee_rate, false)?, sqrt_price, false)?;
+        swap_a_to_b = true;
+        available_a -= swap_input;
+        available_b += swap_output;
+    } else if available_b > target_b {
+        swap_input = try_mul_div(available_b - target_b, one_x64, one_x64 - position_ratio.ratio_b * fee_rate, false)?.min(available_b);
+        swap_output = div_by_sqrt_price_squared(apply_swap_fee(swap_input, swap_fee_rate, false)?, sqrt_price, false)?;
+        available_a += swap_output;
+        available_b -= swap_input;
+    }
+
+    let liquidity = get_liquidity_from_amounts(sqrt_price, lower_sqrt_price, upper_sqrt_price, available_a, available_b)?;
+    let deposited = get_amounts_from_liquidity(liquidity, sqrt_price, lower_sqrt_price, upper_sqrt_price, true)?;
+    let leftovers_a = available_a.saturating_sub(deposited.a);
+    let leftovers_b = available_b.saturating_sub(deposited.b);
+
+    Ok(PositionDeposit {
+        swap_input,
+        swap_output,
+        swap_a_to_b,
+        liquidity,
+        leftovers_a,
+        leftovers_b,
+    })
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "wasm", wasm_expose)]
+pub struct LpPositionInterestAccrualArgs {
+    /** The position liquidity. */
+    pub liquidity: u128,
+    /** Position lower tick index. */
+    pub tick_lower_index: i32,
+    /** Position upper tick index. */
+    pub tick_upper_index: i32,
+    /** The leftovers of a position in token A. */
+    pub leftovers_a: u64,
+    /** The leftovers of a position in token B. */
+    pub leftovers_b: u64,
+    /** The current debt of a position in token A including accrued interest. */
+    pub debt_a: u64,
+    /** The current debt of a position in token B including accrued interest. */
+    pub debt_b: u64,
+    /** The liquidation threshold of the market. */
+    pub liquidation_threshold: u32,
+    /** The interest rate of the vault A per second (Fixed128 bits). */
+    pub interest_rate_a: u64,
+    /** The utilization of the vault A (1.0 is equal to 100%). */
+    pub utilization_a: f64,
+    /** The interest rate of the vault B per second (Fixed128 bits). */
+    pub interest_rate_b: u64,
+    /** The utilization of the vault B (1.0 is equal to 100%). */
+    pub utilization_b: f64,
+}
+
+/// Returns the number of seconds until the position becomes unhealthy at the provided price due to accrued interest,
+/// assuming the vault utilizations don't change.
+///
+/// # Parameters
+/// - `args`: LpPositionInterestAccrualArgs
+/// - `sqrt_price`: Current sqrt price
+///
+/// # Returns
+/// - `u64`: seconds until liquidation, zero if the position is already unhealthy, or u64::MAX if it doesn't happen within 100 years
+#[cfg_attr(feature = "wasm", wasm_expose)]
+pub fn get_lp_position_time_to_liquidation(args: LpPositionInterestAccrualArgs, sqrt_price: u128) -> Result<u64, CoreError> {
+    if args.tick_lower_index >= args.tick_upper_index {
+        return Err("Incorrect position tick index order: the lower tick must be less than the upper tick.");
+    }
+
+    if args.liquidation_threshold >= HUNDRED_PERCENT {
+        return Err(INVALID_ARGUMENTS);
+    }
+
+    if args.debt_a == 0 && args.debt_b == 0 {
+        return Ok(u64::MAX);
+    }
+
+    let lower_sqrt_price = tick_index_to_sqrt_price(args.tick_lower_index);
+    let upper_sqrt_price = tick_index_to_sqrt_price(args.tick_upper_index);
+    let amounts = get_amounts_from_liquidity(args.liquidity, sqrt_price, lower_sqrt_price, upper_sqrt_price, false)?;
+
+    let total = mul_by_sqrt_price_squared(amounts.a + args.leftovers_a, sqrt_price, false)? as u128 + (amounts.b + args.leftovers_b) as u128;
+    let total = total * args.liquidation_threshold as u128 / HUNDRED_PERCENT as u128;
+
+    find_time_to_liquidation(|duration| {
+        let debt_a = get_vault_projected_debt(args.debt_a, args.interest_rate_a, args.utilization_a, duration)?;
+        let debt_b = get_vault_projected_debt(args.debt_b, args.interest_rate_b, args.utilization_b, duration)?;
+        let debt = mul_by_sqrt_price_squared(debt_a, sqrt_price, true)? as u128 + debt_b as u128;
+        Ok(debt > total)
+    })
+}
+
+/// Returns the liquidation prices of the position after the given period of time, taking into account
+/// the interest accrued on the debt and assuming the vault utilizations don't change.
+///
+/// # Parameters
+/// - `args`: LpPositionInterestAccrualArgs
+/// - `duration`: The period of time in seconds
+///
+/// # Returns
+/// - `LiquidationPrices`: the lower and upper liquidation prices
+#[cfg_attr(feature = "wasm", wasm_expose)]
+pub fn get_lp_position_future_liquidation_prices(args: LpPositionInterestAccrualArgs, duration: u64) -> Result<LiquidationPrices, CoreError> {
+    get_lp_position_liquidation_prices(
+        args.tick_lower_index,
+        args.tick_upper_index,
+        args.liquidity,
+        args.leftovers_a,
+        args.leftovers_b,
+        get_vault_projected_debt(args.debt_a, args.interest_rate_a, args.utilization_a, duration)?,
+        get_vault_projected_debt(args.debt_b, args.interest_rate_b, args.utilization_b, duration)?,
+        args.liquidation_threshold,
+    )
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "wasm", wasm_expose)]
+pub struct LpPositionPnlArgs {
+    /** The position liquidity. */
+    pub liquidity: u128,
+    /** Position lower tick index. */
+    pub tick_lower_index: i32,
+    /** Position upper tick index. */
+    pub tick_upper_index: i32,
+    /** The leftovers of a position in token A. */
+    pub leftovers_a: u64,
+    /** The leftovers of a position in token B. */
+    pub leftovers_b: u64,
+    /** The amount of borrowed funds in token A without accrued interest. */
+    pub loan_funds_a: u64,
+    /** The amount of borrowed funds in token B without accrued interest. */
+    pub loan_funds_b: u64,
+    /** The current debt of a position in token A including accrued interest. */
+    pub debt_a: u64,
+    /** The current debt of a position in token B including accrued interest. */
+    pub debt_b: u64,
+    /** The yield in token A compounded into the position. */
+    pub compounded_yield_a: u64,
+    /** The yield in token B compounded into the position. */
+    pub compounded_yield_b: u64,
+    /** Position entry sqrt price. */
+    pub entry_sqrt_price: u128,
+    /** Current sqrt price. */
+    pub sqrt_price: u128,
 }
 
 #[derive(Debug, Copy, Clone, PartialEq)]
 #[cfg_attr(feature = "wasm", wasm_expose)]
-pub struct RepayLpPositionDebtQuoteResult {
-    pub debt_a: u64,
-    pub debt_b: u64,
-    pub leverage: f64,
-    pub liquidation_lower_price: f64,
-    pub liquidation_upper_price: f64,
+pub struct LpPositionPnl {
+    /** Unrealized PnL valued in token A. */
+    pub pnl_a: i64,
+    /** Unrealized PnL valued in token B. */
+    pub pnl_b: i64,
+    /** Return on equity: the unrealized PnL relative to the position equity at entry, valued in token B. */
+    pub roe: f64,
+    /** Interest accrued on the debt in token A (debt_a - loan_funds_a). */
+    pub interest_a: u64,
+    /** Interest accrued on the debt in token B (debt_b - loan_funds_b). */
+    pub interest_b: u64,
+    /** The value of the entry amounts held outside of the pool (HODL), in token B. */
+    pub hodl_value: u64,
+    /** The difference between the current position value without compounded yield and the HODL value, in token B. */
+    pub impermanent_loss: i64,
+    /** The impermanent loss relative to the HODL value. */
+    pub impermanent_loss_percent: f64,
 }
 
+/// Returns the unrealized PnL of an lp position. The entry amounts are computed from the position liquidity
+/// at the entry price excluding the compounded yield, and the position equity at entry is compared with
+/// the current equity. The impermanent loss compares the current position value with holding the entry amounts.
+///
+/// # Parameters
+/// - `args`: LpPositionPnlArgs
+///
+/// # Returns
+/// - `LpPositionPnl`: unrealized PnL, ROE, interest paid and impermanent loss
 #[cfg_attr(feature = "wasm", wasm_expose)]
-pub fn get_repay_lp_position_debt_quote(args: RepayLpPositionDebtQuoteArgs) -> Result<RepayLpPositionDebtQuoteResult, CoreError> {
-    let mut debt_a = args.debt_a;
-    let mut debt_b = args.debt_b;
-    let repay_a = args.repay_a;
-    let repay_b = args.repay_b;
+pub fn get_lp_position_pnl(args: LpPositionPnlArgs) -> Result<LpPositionPnl, CoreError> {
+    let sqrt_price = args.sqrt_price;
+    let entry_sqrt_price = args.entry_sqrt_price;
 
-    if args.liquidity == 0 {
-        return Err("Position liquidity can't be zero.");
+    if args.tick_lower_index >= args.tick_upper_index {
+        return Err("Incorrect position tick index order: the lower tick must be less than the upper tick.");
     }
 
-    if debt_a < repay_a {
-        return Err("Position debt A is less than the repaid amount.");
+    if entry_sqrt_price == 0 || sqrt_price == 0 {
+        return Err(INVALID_ARGUMENTS);
     }
 
-    if debt_b < repay_b {
-        return Err("Position debt b is less than the repaid amount.");
-    }
+    let lower_sqrt_price = tick_index_to_sqrt_price(args.tick_lower_index);
+    let upper_sqrt_price = tick_index_to_sqrt_price(args.tick_upper_index);
 
-    debt_a -= repay_a;
-    debt_b -= repay_b;
+    let entry = get_amounts_from_liquidity(args.liquidity, entry_sqrt_price, lower_sqrt_price, upper_sqrt_price, false)?;
+    let entry_a = (entry.a + args.leftovers_a).saturating_sub(args.compounded_yield_a);
+    let entry_b = (entry.b + args.leftovers_b).saturating_sub(args.compounded_yield_b);
 
-    let liquidation_prices = get_lp_position_liquidation_prices(
-        args.tick_lower_index,
-        args.tick_upper_index,
-        args.liquidity,
-        args.leftovers_a,
-        args.leftovers_b,
-        debt_a,
-        debt_b,
-        args.liquidation_threshold,
-    )?;
+    let current = get_amounts_from_liquidity(args.liquidity, sqrt_price, lower_sqrt_price, upper_sqrt_price, false)?;
+    let current_a = current.a + args.leftovers_a;
+    let current_b = current.b + args.leftovers_b;
 
-    let lower_sqrt_price = tick_index_to_sqrt_price(args.tick_lower_index);
-    let upper_sqrt_price = tick_index_to_sqrt_price(args.tick_upper_index);
+    let value_b =
+        |a: u64, b: u64, sqrt_price: u128| -> Result<i128, CoreError> { Ok(mul_by_sqrt_price_squared(a, sqrt_price, false)? as i128 + b as i128) };
+    let value_a =
+        |a: u64, b: u64, sqrt_price: u128| -> Result<i128, CoreError> { Ok(a as i128 + div_by_sqrt_price_squared(b, sqrt_price, false)? as i128) };
 
-    let total = get_amounts_from_liquidity(args.liquidity, args.sqrt_price, lower_sqrt_price, upper_sqrt_price, false)?;
-    let leverage = compute_leverage(total.a + args.leftovers_a, total.b + args.leftovers_b, debt_a, debt_b, args.sqrt_price)?;
+    let entry_equity_b = value_b(entry_a, entry_b, entry_sqrt_price)? - value_b(args.loan_funds_a, args.loan_funds_b, entry_sqrt_price)?;
+    let equity_b = value_b(current_a, current_b, sqrt_price)? - value_b(args.debt_a, args.debt_b, sqrt_price)?;
+    let pnl_b = equity_b - entry_equity_b;
 
-    Ok(RepayLpPositionDebtQuoteResult {
-        debt_a,
-        debt_b,
-        leverage,
-        liquidation_lower_price: liquidation_prices.lower,
-        liquidation_upper_price: liquidation_prices.upper,
+    let entry_equity_a = value_a(entry_a, entry_b, entry_sqrt_price)? - value_a(args.loan_funds_a, args.loan_funds_b, entry_sqrt_price)?;
+    let equity_a = value_a(current_a, current_b, sqrt_price)? - value_a(args.debt_a, args.debt_b, sqrt_price)?;
+    let pnl_a = equity_a - entry_equity_a;
+
+    let roe = if entry_equity_b > 0 { pnl_b as f64 / entry_equity_b as f64 } else { 0.0 };
+
+    let hodl_value = value_b(entry_a, entry_b, sqrt_price)?;
+    let position_value = value_b(current_a.saturating_sub(args.compounded_yield_a), current_b.saturating_sub(args.compounded_yield_b), sqrt_price)?;
+    let impermanent_loss = position_value - hodl_value;
+    let impermanent_loss_percent = if hodl_value > 0 {
+        impermanent_loss as f64 / hodl_value as f64
+    } else {
+        0.0
+    };
+
+    Ok(LpPositionPnl {
+        pnl_a: pnl_a.try_into().map_err(|_| ARITHMETIC_OVERFLOW)?,
+        pnl_b: pnl_b.try_into().map_err(|_| ARITHMETIC_OVERFLOW)?,
+        roe,
+        interest_a: args.debt_a.saturating_sub(args.loan_funds_a),
+        interest_b: args.debt_b.saturating_sub(args.loan_funds_b),
+        hodl_value: hodl_value.try_into().map_err(|_| ARITHMETIC_OVERFLOW)?,
+        impermanent_loss: impermanent_loss.try_into().map_err(|_| ARITHMETIC_OVERFLOW)?,
+        impermanent_loss_percent,
     })
 }
 
@@ -558,13 +2156,25 @@ pub fn compute_leverage(total_a: u64, total_b: u64, debt_a: u64, debt_b: u64, sq
 #[cfg(all(test, not(feature = "wasm")))]
 mod tests {
     use crate::{
-        get_increase_lp_position_quote, get_lp_position_liquidation_prices, get_repay_lp_position_debt_quote, IncreaseLpPositionQuoteArgs,
-        IncreaseLpPositionQuoteResult, LiquidationPrices, RepayLpPositionDebtQuoteArgs, COMPUTED_AMOUNT, HUNDRED_PERCENT,
+        assert_approx_eq, compute_leverage, get_collect_and_compound_fees_quote, get_decrease_lp_position_quote,
+        get_increase_lp_position_args_for_leverage, get_increase_lp_position_orca_quote_with_price_impact, get_increase_lp_position_quote,
+        get_increase_lp_position_quote_with_price_impact, get_liquidate_lp_position_quote, get_lp_limit_order_execution_quote,
+        get_lp_position_future_liquidation_prices, get_lp_position_limit_order_swap_to_token, get_lp_position_liquidation_prices,
+        get_lp_position_pnl, get_lp_position_time_to_liquidation, get_max_lp_position, get_rebalance_lp_position_quote,
+        get_rebalanced_position_tick_range, get_repay_lp_position_debt_quote, CollectAndCompoundFeesQuoteArgs, DecreaseLpPositionQuoteArgs,
+        IncreaseLpPositionLeverageArgs, IncreaseLpPositionQuoteArgs, IncreaseLpPositionQuoteResult, LiquidateLpPositionQuoteArgs, LiquidationPrices,
+        LpLimitOrderExecutionQuoteArgs, LpPositionInterestAccrualArgs, LpPositionPnlArgs, LpPositionSizeConstraint, MaxLpPositionArgs,
+        RebalanceLpPositionQuoteArgs, RepayLpPositionDebtQuoteArgs, COMPUTED_AMOUNT, HUNDRED_PERCENT, TOKEN_A, TOKEN_B,
+        TUNA_POSITION_FLAGS_LOWER_LIMIT_ORDER_SWAP_TO_TOKEN_A, TUNA_POSITION_FLAGS_LOWER_LIMIT_ORDER_SWAP_TO_TOKEN_B,
+        TUNA_POSITION_FLAGS_UPPER_LIMIT_ORDER_SWAP_TO_TOKEN_A, TUNA_POSITION_FLAGS_UPPER_LIMIT_ORDER_SWAP_TO_TOKEN_B,
     };
     use fusionamm_core::{
-        get_liquidity_from_amount_b, price_to_sqrt_price, price_to_tick_index, tick_index_to_sqrt_price, MAX_TICK_INDEX, MIN_TICK_INDEX,
+        get_amounts_from_liquidity, get_liquidity_from_amount_b, get_tick_array_start_tick_index, price_to_sqrt_price, price_to_tick_index,
+        sqrt_price_to_tick_index, tick_index_to_sqrt_price, FusionPoolFacade, TickArrayFacade, TickFacade, ARITHMETIC_OVERFLOW, MAX_TICK_INDEX,
+        MIN_TICK_INDEX, TICK_ARRAY_SIZE,
     };
     use once_cell::sync::Lazy;
+    use orca_whirlpools_core::WhirlpoolFacade;
 
     pub static TICK_LOWER_INDEX: Lazy<i32> = Lazy::new(|| price_to_tick_index(180.736, 6, 6));
     pub static TICK_UPPER_INDEX: Lazy<i32> = Lazy::new(|| price_to_tick_index(225.66, 6, 6));
@@ -1016,6 +2626,131 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_lp_increase_quote_at_extreme_prices() {
+        let test_quote = |price: f64, collateral_a: u64, collateral_b: u64| {
+            get_increase_lp_position_quote(IncreaseLpPositionQuoteArgs {
+                collateral_a,
+                collateral_b,
+                borrow_a: 0,
+                borrow_b: 0,
+                tick_lower_index: price_to_tick_index(price / 2.0, 9, 6),
+                sqrt_price: price_to_sqrt_price(price, 9, 6),
+                tick_upper_index: price_to_tick_index(price * 2.0, 9, 6),
+                protocol_fee_rate: 0,
+                protocol_fee_rate_on_collateral: 0,
+                swap_fee_rate: 3000,
+                liquidation_threshold: HUNDRED_PERCENT * 83 / 100,
+            })
+            .unwrap()
+        };
+
+        let quote = test_quote(0.000001, 1_000_000_000_000_000, 1_000_000);
+        assert_eq!(quote.total_a, 999941538093948);
+        assert_eq!(quote.total_b, 1000057);
+        assert_eq!(quote.swap_input, 58461906052);
+        assert_eq!(quote.swap_output, 58);
+        assert_eq!(quote.liquidity, 107970958242);
+
+        let quote = test_quote(1_000_000.0, 1_000_000, 1_000_000_000_000);
+        assert_eq!(quote.total_a, 499720234);
+        assert_eq!(quote.total_b, 499781357415);
+        assert_eq!(quote.swap_input, 499469392679);
+        assert_eq!(quote.swap_output, 497970984);
+        assert_eq!(quote.liquidity, 53958624179);
+    }
+
+    #[test]
+    fn test_lp_increase_quote_with_price_impact() {
+        let sqrt_price = price_to_sqrt_price(2.0, 1, 1);
+        let tick_current_index = sqrt_price_to_tick_index(sqrt_price);
+        let tick_array_start_index = get_tick_array_start_tick_index(tick_current_index, 64);
+        let tick_array_offsets = [0, 1, 2, -1, -2];
+
+        let fusion_pool = FusionPoolFacade {
+            tick_current_index,
+            fee_rate: 3000,
+            liquidity: 1000000000,
+            sqrt_price,
+            tick_spacing: 64,
+            ..FusionPoolFacade::default()
+        };
+        let tick_arrays: [TickArrayFacade; 5] = tick_array_offsets.map(|offset| TickArrayFacade {
+            start_tick_index: tick_array_start_index + offset * TICK_ARRAY_SIZE as i32 * 64,
+            ticks: [TickFacade::default(); TICK_ARRAY_SIZE],
+        });
+
+        let whirlpool = WhirlpoolFacade {
+            fee_tier_index_seed: 64u16.to_le_bytes(),
+            tick_current_index,
+            fee_rate: 3000,
+            liquidity: 1000000000,
+            sqrt_price,
+            tick_spacing: 64,
+            ..WhirlpoolFacade::default()
+        };
+        let orca_tick_arrays: [orca_whirlpools_core::TickArrayFacade; 5] = tick_array_offsets.map(|offset| orca_whirlpools_core::TickArrayFacade {
+            start_tick_index: tick_array_start_index + offset * TICK_ARRAY_SIZE as i32 * 64,
+            ticks: [orca_whirlpools_core::TickFacade::default(); orca_whirlpools_core::TICK_ARRAY_SIZE],
+        });
+
+        let args = IncreaseLpPositionQuoteArgs {
+            collateral_a: 10000000,
+            collateral_b: 0,
+            borrow_a: 10000000,
+            borrow_b: 0,
+            tick_lower_index: price_to_tick_index(1.0, 1, 1),
+            sqrt_price: 0,
+            tick_upper_index: price_to_tick_index(4.0, 1, 1),
+            protocol_fee_rate: 0,
+            protocol_fee_rate_on_collateral: 0,
+            swap_fee_rate: 0,
+            liquidation_threshold: HUNDRED_PERCENT * 83 / 100,
+        };
+
+        let spot_quote = get_increase_lp_position_quote(IncreaseLpPositionQuoteArgs {
+            sqrt_price,
+            swap_fee_rate: 3000,
+            ..args
+        })
+        .unwrap();
+
+        let quote = get_increase_lp_position_quote_with_price_impact(args, HUNDRED_PERCENT / 100, fusion_pool, tick_arrays.into()).unwrap();
+        assert_eq!(quote.swap_input, spot_quote.swap_input);
+        assert!(quote.swap_a_to_b);
+        assert!(quote.swap_output < spot_quote.swap_output);
+        assert!(quote.next_sqrt_price < sqrt_price);
+        assert!(quote.price_impact > 0.003);
+        assert!(quote.liquidity < spot_quote.liquidity);
+        // The price moves down after the swap, so the position requires less token B than received.
+        assert_eq!(quote.leftovers_a, 0);
+        assert!(quote.leftovers_b > 0);
+        assert!(quote.leftovers_exceeded);
+        assert_eq!(quote.total_a, 20000000 - quote.swap_input);
+        assert_eq!(quote.total_b + quote.leftovers_b, quote.swap_output);
+
+        let orca_quote =
+            get_increase_lp_position_orca_quote_with_price_impact(args, HUNDRED_PERCENT / 100, whirlpool, None, orca_tick_arrays.into(), 0).unwrap();
+        assert_eq!(orca_quote.swap_output, quote.swap_output);
+        assert_approx_eq!(orca_quote.next_sqrt_price as f64, quote.next_sqrt_price as f64, quote.next_sqrt_price as f64 * 1e-8);
+        assert_approx_eq!(orca_quote.liquidity as f64, quote.liquidity as f64, quote.liquidity as f64 * 1e-6);
+        assert_approx_eq!(orca_quote.leftovers_percentage as f64, quote.leftovers_percentage as f64, 10.0);
+
+        // A small position in the same pool barely moves the price.
+        let quote = get_increase_lp_position_quote_with_price_impact(
+            IncreaseLpPositionQuoteArgs {
+                collateral_a: 1000,
+                borrow_a: 1000,
+                ..args
+            },
+            HUNDRED_PERCENT / 100,
+            fusion_pool,
+            tick_arrays.into(),
+        )
+        .unwrap();
+        assert!(!quote.leftovers_exceeded);
+    }
+
     #[test]
     fn test_lp_increase_quote_verify_liquidation_prices() {
         let quote = get_increase_lp_position_quote(IncreaseLpPositionQuoteArgs {
@@ -1059,4 +2794,641 @@ mod tests {
         assert_eq!(quote.liquidation_lower_price, 13.459576327110664);
         assert_eq!(quote.liquidation_upper_price, 692.0710879340029);
     }
+
+    fn test_decrease_quote_args(decrease_percent: u32, swap_to_token: Option<u8>) -> DecreaseLpPositionQuoteArgs {
+        DecreaseLpPositionQuoteArgs {
+            liquidity: 1109671058,
+            tick_lower_index: price_to_tick_index(180.736, 6, 6),
+            tick_upper_index: price_to_tick_index(225.66, 6, 6),
+            leftovers_a: 2,
+            leftovers_b: 15,
+            debt_a: 3_000_000,
+            debt_b: 100_000_000,
+            decrease_percent,
+            swap_to_token,
+            swap_fee_rate: 3000,
+            slippage_tolerance_bps: 100,
+            sqrt_price: price_to_sqrt_price(213.41, 6, 6),
+            liquidation_threshold: HUNDRED_PERCENT * 83 / 100,
+        }
+    }
+
+    #[test]
+    fn test_decrease_quote_without_swap_to_token() {
+        let mut args = test_decrease_quote_args(HUNDRED_PERCENT / 4, None);
+        args.debt_a = 0;
+        args.debt_b = 1_000_000_000;
+
+        let quote = get_decrease_lp_position_quote(args).unwrap();
+
+        assert_eq!(quote.removed_liquidity, 277417764);
+        assert_eq!(quote.removed_a, 522628);
+        assert_eq!(quote.removed_b, 323303719);
+        assert_eq!(quote.min_removed_amount_a, 517401);
+        assert_eq!(quote.min_removed_amount_b, 320070681);
+        assert_eq!(quote.swap_input, 0);
+        assert_eq!(quote.swap_output, 0);
+        assert_eq!(quote.repaid_debt_a, 0);
+        assert_eq!(quote.repaid_debt_b, 250_000_000);
+        assert_eq!(quote.withdrawn_a, 522628);
+        assert_eq!(quote.withdrawn_b, 73303722);
+        assert_eq!(quote.liquidity, 832253294);
+        assert_eq!(quote.debt_b, 750_000_000);
+        assert_eq!(quote.leverage, 2.352535890098892);
+        assert_eq!(quote.liquidation_lower_price, 138.87426744170136);
+        assert_eq!(quote.liquidation_upper_price, 0.0);
+    }
+
+    #[test]
+    fn test_decrease_quote_swaps_missing_debt() {
+        let quote = get_decrease_lp_position_quote(test_decrease_quote_args(HUNDRED_PERCENT / 2, None)).unwrap();
+
+        assert_eq!(quote.removed_a, 1045256);
+        assert_eq!(quote.removed_b, 646607440);
+        assert_eq!(quote.repaid_debt_a, 1_500_000);
+        assert_eq!(quote.repaid_debt_b, 50_000_000);
+        assert_eq!(quote.swap_input, 97338721);
+        assert_eq!(quote.swap_output, 454743);
+        assert!(!quote.swap_a_to_b);
+        assert_eq!(quote.withdrawn_a, 0);
+        assert_eq!(quote.withdrawn_b, 499268726);
+        assert_eq!(quote.liquidity, 554835529);
+        assert_eq!(quote.debt_a, 1_500_000);
+        assert_eq!(quote.debt_b, 50_000_000);
+        assert_eq!(quote.liquidation_lower_price, 23.80523400512427);
+        assert_eq!(quote.liquidation_upper_price, 451.38059238392776);
+    }
+
+    #[test]
+    fn test_decrease_quote_close_position_and_swap_to_token() {
+        let quote = get_decrease_lp_position_quote(test_decrease_quote_args(HUNDRED_PERCENT, Some(TOKEN_A))).unwrap();
+        assert_eq!(quote.swap_input, 1193214895);
+        assert_eq!(quote.swap_output, 5574411);
+        assert!(!quote.swap_a_to_b);
+        assert_eq!(quote.withdrawn_a, 4664926);
+        assert_eq!(quote.withdrawn_b, 0);
+        assert_eq!(quote.liquidity, 0);
+        assert_eq!(quote.debt_a, 0);
+        assert_eq!(quote.debt_b, 0);
+        assert_eq!(quote.leverage, 1.0);
+
+        let quote = get_decrease_lp_position_quote(test_decrease_quote_args(HUNDRED_PERCENT, Some(TOKEN_B))).unwrap();
+        assert_eq!(quote.swap_input, 194677226);
+        assert_eq!(quote.swap_output, 909485);
+        assert_eq!(quote.withdrawn_a, 0);
+        assert_eq!(quote.withdrawn_b, 998537669);
+        assert_eq!(quote.liquidation_lower_price, 0.0);
+        assert_eq!(quote.liquidation_upper_price, 0.0);
+    }
+
+    fn test_rebalance_quote_args(tick_current_index: i32) -> RebalanceLpPositionQuoteArgs {
+        RebalanceLpPositionQuoteArgs {
+            liquidity: 1109671058,
+            tick_lower_index: price_to_tick_index(180.736, 6, 6) / 8 * 8,
+            tick_upper_index: price_to_tick_index(225.66, 6, 6) / 8 * 8,
+            leftovers_a: 2,
+            leftovers_b: 15,
+            debt_a: 0,
+            debt_b: 100_000_000,
+            rebalance_threshold_ticks: 100,
+            rebalance_protocol_fee: HUNDRED_PERCENT / 1000,
+            swap_fee_rate: 3000,
+            tick_spacing: 8,
+            tick_current_index,
+            sqrt_price: tick_index_to_sqrt_price(tick_current_index),
+            liquidation_threshold: HUNDRED_PERCENT * 83 / 100,
+        }
+    }
+
+    fn test_liquidate_quote_args(decrease_percent: u32, debt_b: u64) -> LiquidateLpPositionQuoteArgs {
+        LiquidateLpPositionQuoteArgs {
+            liquidity: 1109671058,
+            tick_lower_index: price_to_tick_index(180.736, 6, 6),
+            tick_upper_index: price_to_tick_index(225.66, 6, 6),
+            leftovers_a: 2,
+            leftovers_b: 15,
+            debt_a: 3_000_000,
+            debt_b,
+            decrease_percent,
+            liquidation_fee: HUNDRED_PERCENT / 100,
+            swap_fee_rate: 3000,
+            sqrt_price: price_to_sqrt_price(213.41, 6, 6),
+        }
+    }
+
+    #[test]
+    fn test_liquidate_quote_repays_debt() {
+        let quote = get_liquidate_lp_position_quote(test_liquidate_quote_args(HUNDRED_PERCENT, 100_000_000)).unwrap();
+
+        assert_eq!(quote.removed_liquidity, 1109671058);
+        assert_eq!(quote.repaid_debt_a, 3_000_000);
+        assert_eq!(quote.repaid_debt_b, 100_000_000);
+        assert_eq!(quote.bad_debt_a, 0);
+        assert_eq!(quote.bad_debt_b, 0);
+        assert!(!quote.swap_a_to_b);
+        assert_eq!(quote.protocol_fee_a, 0);
+        assert_eq!(quote.protocol_fee_b, quote.removed_b / 100);
+        assert_eq!(quote.leftovers_a, 0);
+        assert_eq!(quote.leftovers_b + quote.protocol_fee_b + quote.swap_input + quote.repaid_debt_b, quote.removed_b);
+        assert_eq!(quote.removed_a + quote.swap_output, quote.repaid_debt_a);
+        assert_eq!(quote.liquidity, 0);
+        assert_eq!(quote.debt_a, 0);
+        assert_eq!(quote.debt_b, 0);
+    }
+
+    #[test]
+    fn test_liquidate_quote_keeps_remaining_funds_in_position() {
+        // Mirrors the "Liquidates a position due to an unhealthy state" TS test: the position is entirely in token B after the price
+        // moved, the token A debt is swapped from token B, 1% of the removed amount goes to the fee recipient and the rest stays
+        // in the position.
+        let args = LiquidateLpPositionQuoteArgs {
+            liquidity: 0,
+            tick_lower_index: -100,
+            tick_upper_index: 100,
+            leftovers_a: 0,
+            leftovers_b: 1_005_229_750,
+            debt_a: 4_000_000_000,
+            debt_b: 0,
+            decrease_percent: HUNDRED_PERCENT,
+            liquidation_fee: HUNDRED_PERCENT / 100,
+            swap_fee_rate: 0,
+            sqrt_price: price_to_sqrt_price(987_084_947.0 / 4_000_000_000.0, 1, 1),
+        };
+        let quote = get_liquidate_lp_position_quote(args).unwrap();
+
+        assert_eq!(quote.repaid_debt_a, 4_000_000_000);
+        assert_eq!(quote.protocol_fee_a, 0);
+        assert_eq!(quote.protocol_fee_b, 10_052_297);
+        assert_eq!(quote.liquidator_fee_a + quote.liquidator_fee_b, 0);
+        assert_eq!(quote.leftovers_a, 0);
+        assert_approx_eq!(quote.leftovers_b as f64, 8_092_506.0, 1.0);
+        assert_eq!(quote.removed_b - quote.swap_input, quote.protocol_fee_b + quote.leftovers_b);
+    }
+
+    #[test]
+    fn test_liquidate_quote_partial() {
+        let quote = get_liquidate_lp_position_quote(test_liquidate_quote_args(HUNDRED_PERCENT / 2, 100_000_000)).unwrap();
+
+        assert_eq!(quote.removed_liquidity, 554835529);
+        assert_eq!(quote.repaid_debt_a, 1_500_000);
+        assert_eq!(quote.repaid_debt_b, 50_000_000);
+        assert_eq!(quote.bad_debt_a + quote.bad_debt_b, 0);
+        assert_eq!(quote.liquidity, 554835529);
+        assert_eq!(quote.debt_a, 1_500_000);
+        assert_eq!(quote.debt_b, 50_000_000);
+    }
+
+    #[test]
+    fn test_liquidate_quote_with_bad_debt() {
+        let mut args = test_liquidate_quote_args(HUNDRED_PERCENT, 2_000_000_000);
+        args.debt_a = 0;
+        let quote = get_liquidate_lp_position_quote(args).unwrap();
+
+        assert_eq!(quote.repaid_debt_a, 0);
+        assert!(quote.swap_a_to_b);
+        assert_eq!(quote.swap_input, quote.removed_a);
+        assert_eq!(quote.repaid_debt_b, quote.removed_b + quote.swap_output);
+        assert_eq!(quote.bad_debt_a, 0);
+        assert_eq!(quote.bad_debt_b, 2_000_000_000 - quote.repaid_debt_b);
+        assert_eq!(quote.protocol_fee_a + quote.protocol_fee_b, 0);
+        assert_eq!(quote.leftovers_a + quote.leftovers_b, 0);
+        assert_eq!(quote.debt_b, 0);
+    }
+
+    fn test_limit_order_execution_quote_args(flags: u32, upper_limit_order: bool, price: f64) -> LpLimitOrderExecutionQuoteArgs {
+        LpLimitOrderExecutionQuoteArgs {
+            liquidity: 1109671058,
+            tick_lower_index: price_to_tick_index(180.736, 6, 6),
+            tick_upper_index: price_to_tick_index(225.66, 6, 6),
+            leftovers_a: 2,
+            leftovers_b: 15,
+            debt_a: 3_000_000,
+            debt_b: 100_000_000,
+            flags,
+            upper_limit_order,
+            trigger_sqrt_price: price_to_sqrt_price(price, 6, 6),
+            swap_fee_rate: 3000,
+        }
+    }
+
+    #[test]
+    fn test_limit_order_swap_to_token() {
+        let flags = TUNA_POSITION_FLAGS_LOWER_LIMIT_ORDER_SWAP_TO_TOKEN_B | TUNA_POSITION_FLAGS_UPPER_LIMIT_ORDER_SWAP_TO_TOKEN_A;
+        assert_eq!(get_lp_position_limit_order_swap_to_token(flags, false), Ok(Some(TOKEN_B)));
+        assert_eq!(get_lp_position_limit_order_swap_to_token(flags, true), Ok(Some(TOKEN_A)));
+        assert_eq!(get_lp_position_limit_order_swap_to_token(0, false), Ok(None));
+        assert_eq!(get_lp_position_limit_order_swap_to_token(0, true), Ok(None));
+
+        let flags = TUNA_POSITION_FLAGS_LOWER_LIMIT_ORDER_SWAP_TO_TOKEN_A | TUNA_POSITION_FLAGS_LOWER_LIMIT_ORDER_SWAP_TO_TOKEN_B;
+        assert!(get_lp_position_limit_order_swap_to_token(flags, false).is_err());
+        assert_eq!(get_lp_position_limit_order_swap_to_token(flags, true), Ok(None));
+    }
+
+    #[test]
+    fn test_limit_order_execution_quote_stop_loss() {
+        let args = test_limit_order_execution_quote_args(TUNA_POSITION_FLAGS_LOWER_LIMIT_ORDER_SWAP_TO_TOKEN_B, false, 180.0);
+        let quote = get_lp_limit_order_execution_quote(args).unwrap();
+
+        // The position is below the range and only holds token A.
+        assert_eq!(quote.removed_b, 0);
+        assert_eq!(quote.repaid_debt_a, 3_000_000);
+        assert_eq!(quote.repaid_debt_b, 100_000_000);
+        assert!(quote.swap_a_to_b);
+        assert_eq!(quote.amount_a, 0);
+        assert!(quote.amount_b > 0);
+        assert_eq!(quote.removed_a + args.leftovers_a, quote.repaid_debt_a + quote.swap_input);
+    }
+
+    #[test]
+    fn test_limit_order_execution_quote_take_profit() {
+        let args = test_limit_order_execution_quote_args(TUNA_POSITION_FLAGS_UPPER_LIMIT_ORDER_SWAP_TO_TOKEN_A, true, 230.0);
+        let quote = get_lp_limit_order_execution_quote(args).unwrap();
+
+        // The position is above the range and only holds token B.
+        assert_eq!(quote.removed_a, 0);
+        assert!(!quote.swap_a_to_b);
+        assert!(quote.amount_a > 0);
+        assert_eq!(quote.amount_b, 0);
+        assert_eq!(quote.removed_b + args.leftovers_b, quote.repaid_debt_b + quote.swap_input);
+    }
+
+    #[test]
+    fn test_limit_order_execution_quote_without_swap() {
+        let mut args = test_limit_order_execution_quote_args(TUNA_POSITION_FLAGS_LOWER_LIMIT_ORDER_SWAP_TO_TOKEN_B, true, 213.41);
+        args.debt_a = 100_000;
+        args.debt_b = 10_000_000;
+        let quote = get_lp_limit_order_execution_quote(args).unwrap();
+
+        assert_eq!(quote.swap_input, 0);
+        assert_eq!(quote.amount_a, quote.removed_a + args.leftovers_a - quote.repaid_debt_a);
+        assert_eq!(quote.amount_b, quote.removed_b + args.leftovers_b - quote.repaid_debt_b);
+    }
+
+    #[test]
+    fn test_limit_order_execution_quote_with_conflicting_flags() {
+        let flags = TUNA_POSITION_FLAGS_UPPER_LIMIT_ORDER_SWAP_TO_TOKEN_A | TUNA_POSITION_FLAGS_UPPER_LIMIT_ORDER_SWAP_TO_TOKEN_B;
+        assert!(get_lp_limit_order_execution_quote(test_limit_order_execution_quote_args(flags, true, 230.0)).is_err());
+        assert!(get_lp_limit_order_execution_quote(test_limit_order_execution_quote_args(flags, false, 180.0)).is_err());
+    }
+
+    #[test]
+    fn test_rebalanced_position_tick_range() {
+        assert_eq!(get_rebalanced_position_tick_range(1003, 8, -200, 200), (800, 1200));
+        assert_eq!(get_rebalanced_position_tick_range(-1003, 8, -200, 200), (-1208, -808));
+        assert_eq!(get_rebalanced_position_tick_range(MIN_TICK_INDEX, 8, -200, 200), (MIN_TICK_INDEX, MIN_TICK_INDEX + 400));
+        assert_eq!(get_rebalanced_position_tick_range(MAX_TICK_INDEX - 5, 8, -200, 200), (MAX_TICK_INDEX - 400, MAX_TICK_INDEX));
+    }
+
+    #[test]
+    fn test_rebalance_quote_below_range() {
+        let args = test_rebalance_quote_args(price_to_tick_index(170.0, 6, 6));
+        let quote = get_rebalance_lp_position_quote(args).unwrap();
+        assert!(quote.rebalance_conditions_met);
+        assert_eq!(quote.tick_upper_index - quote.tick_lower_index, args.tick_upper_index - args.tick_lower_index);
+        assert!(quote.tick_lower_index < args.tick_current_index && args.tick_current_index < quote.tick_upper_index);
+        assert_eq!(quote.tick_lower_index, 50248);
+        assert_eq!(quote.tick_upper_index, 52472);
+        assert_eq!(quote.protocol_fee_a, 8688);
+        assert_eq!(quote.protocol_fee_b, 0);
+        assert_eq!(quote.swap_input, 4346389);
+        assert_eq!(quote.swap_output, 736628652);
+        assert!(quote.swap_a_to_b);
+        assert_eq!(quote.liquidity, 1044720023);
+        assert_eq!(quote.leftovers_a, 1);
+        assert_eq!(quote.leftovers_b, 0);
+        assert_eq!(quote.leverage, 1.0728195555996123);
+    }
+
+    #[test]
+    fn test_rebalance_quote_above_range() {
+        let args = test_rebalance_quote_args(price_to_tick_index(240.0, 6, 6));
+        let quote = get_rebalance_lp_position_quote(args).unwrap();
+        assert!(quote.rebalance_conditions_met);
+        assert_eq!(quote.tick_lower_index, 53696);
+        assert_eq!(quote.tick_upper_index, 55920);
+        assert_eq!(quote.protocol_fee_a, 0);
+        assert_eq!(quote.protocol_fee_b, 1754126);
+        assert_eq!(quote.swap_input, 876736105);
+        assert_eq!(quote.swap_output, 3642155);
+        assert!(!quote.swap_a_to_b);
+        assert_eq!(quote.liquidity, 1044251759);
+        assert_eq!(quote.leftovers_a, 0);
+        assert_eq!(quote.leftovers_b, 14);
+        assert_eq!(quote.leverage, 1.0606155141649132);
+    }
+
+    #[test]
+    fn test_rebalance_quote_conditions_not_met() {
+        let mut args = test_rebalance_quote_args(0);
+        args.tick_current_index = args.tick_lower_index - 100;
+        args.sqrt_price = tick_index_to_sqrt_price(args.tick_current_index);
+        assert!(!get_rebalance_lp_position_quote(args).unwrap().rebalance_conditions_met);
+
+        args.tick_current_index = args.tick_lower_index - 101;
+        args.sqrt_price = tick_index_to_sqrt_price(args.tick_current_index);
+        assert!(get_rebalance_lp_position_quote(args).unwrap().rebalance_conditions_met);
+
+        args.tick_current_index = args.tick_upper_index + 99;
+        args.sqrt_price = tick_index_to_sqrt_price(args.tick_current_index);
+        assert!(!get_rebalance_lp_position_quote(args).unwrap().rebalance_conditions_met);
+    }
+
+    fn test_compound_fees_quote_args(with_leverage: bool) -> CollectAndCompoundFeesQuoteArgs {
+        CollectAndCompoundFeesQuoteArgs {
+            liquidity: 1109671058,
+            tick_lower_index: price_to_tick_index(180.736, 6, 6),
+            tick_upper_index: price_to_tick_index(225.66, 6, 6),
+            leftovers_a: 2,
+            leftovers_b: 15,
+            debt_a: 3_000_000,
+            debt_b: 100_000_000,
+            fees_a: 100_000,
+            fees_b: 10_000_000,
+            with_leverage,
+            protocol_fee_rate: (HUNDRED_PERCENT / 100) as u16,
+            protocol_fee_rate_on_collateral: (HUNDRED_PERCENT / 200) as u16,
+            swap_fee_rate: 3000,
+            sqrt_price: price_to_sqrt_price(213.41, 6, 6),
+            liquidation_threshold: HUNDRED_PERCENT * 83 / 100,
+        }
+    }
+
+    fn test_compound_fees_position_leverage(args: CollectAndCompoundFeesQuoteArgs) -> f64 {
+        let total = get_amounts_from_liquidity(
+            args.liquidity,
+            args.sqrt_price,
+            tick_index_to_sqrt_price(args.tick_lower_index),
+            tick_index_to_sqrt_price(args.tick_upper_index),
+            false,
+        )
+        .unwrap();
+        compute_leverage(total.a + args.leftovers_a, total.b + args.leftovers_b, args.debt_a, args.debt_b, args.sqrt_price).unwrap()
+    }
+
+    #[test]
+    fn test_compound_fees_quote_without_leverage() {
+        let args = test_compound_fees_quote_args(false);
+        let quote = get_collect_and_compound_fees_quote(args).unwrap();
+        assert_eq!(quote.borrow_a, 0);
+        assert_eq!(quote.borrow_b, 0);
+        assert_eq!(quote.protocol_fee_a, 500);
+        assert_eq!(quote.protocol_fee_b, 50000);
+        assert_eq!(quote.swap_input, 62069);
+        assert_eq!(quote.swap_output, 13206237);
+        assert!(quote.swap_a_to_b);
+        assert_eq!(quote.added_liquidity, 19869723);
+        assert_eq!(quote.liquidity, args.liquidity + quote.added_liquidity);
+        assert_eq!(quote.debt_a, args.debt_a);
+        assert_eq!(quote.debt_b, args.debt_b);
+        assert_eq!(quote.leverage, 1.7184842926102102);
+        assert!(quote.leverage < test_compound_fees_position_leverage(args));
+    }
+
+    #[test]
+    fn test_compound_fees_quote_with_leverage() {
+        let args = test_compound_fees_quote_args(true);
+        let quote = get_collect_and_compound_fees_quote(args).unwrap();
+        assert_eq!(quote.borrow_a, 94105);
+        assert_eq!(quote.borrow_b, 3136855);
+        assert_eq!(quote.protocol_fee_a, 1441);
+        assert_eq!(quote.protocol_fee_b, 81368);
+        assert_eq!(quote.swap_input, 127654);
+        assert_eq!(quote.swap_output, 27160904);
+        assert!(quote.swap_a_to_b);
+        assert_eq!(quote.added_liquidity, 34508558);
+        assert_eq!(quote.debt_a, 3094105);
+        assert_eq!(quote.debt_b, 103136855);
+        assert_eq!(quote.leverage, 1.741219106199101);
+        assert_eq!(quote.liquidation_lower_price, 23.81594321751317);
+        assert_eq!(quote.liquidation_upper_price, 451.25340633247004);
+        assert!((quote.leverage - test_compound_fees_position_leverage(args)).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_compound_fees_quote_overflow() {
+        let args = CollectAndCompoundFeesQuoteArgs {
+            debt_a: 0,
+            debt_b: 1,
+            fees_a: 0,
+            fees_b: u64::MAX - 100,
+            protocol_fee_rate: 0,
+            protocol_fee_rate_on_collateral: 0,
+            ..test_compound_fees_quote_args(true)
+        };
+        assert_eq!(get_collect_and_compound_fees_quote(args), Err(ARITHMETIC_OVERFLOW));
+    }
+
+    fn test_increase_leverage_args(leverage: f64) -> IncreaseLpPositionLeverageArgs {
+        IncreaseLpPositionLeverageArgs {
+            collateral_a: 1_000_000,
+            collateral_b: 100_000_000,
+            leverage,
+            max_leverage: 5 * HUNDRED_PERCENT,
+            borrow_limit_a: u64::MAX,
+            borrow_limit_b: u64::MAX,
+            protocol_fee_rate: (HUNDRED_PERCENT / 100) as u16,
+            protocol_fee_rate_on_collateral: (HUNDRED_PERCENT / 200) as u16,
+            swap_fee_rate: 3000,
+            sqrt_price: price_to_sqrt_price(200.0, 6, 6),
+            tick_lower_index: price_to_tick_index(180.736, 6, 6),
+            tick_upper_index: price_to_tick_index(225.66, 6, 6),
+            liquidation_threshold: HUNDRED_PERCENT * 83 / 100,
+        }
+    }
+
+    #[test]
+    fn test_increase_args_for_leverage() {
+        let args = get_increase_lp_position_args_for_leverage(test_increase_leverage_args(2.5)).unwrap();
+        assert_eq!(args.borrow_a, 1196221);
+        assert_eq!(args.borrow_b, 201888800);
+
+        let quote = get_increase_lp_position_quote(args).unwrap();
+        assert!((quote.leverage - 2.5).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_increase_args_for_leverage_one() {
+        let args = get_increase_lp_position_args_for_leverage(test_increase_leverage_args(1.0)).unwrap();
+        assert_eq!(args.borrow_a, 0);
+        assert_eq!(args.borrow_b, 0);
+    }
+
+    #[test]
+    fn test_increase_args_for_leverage_limits() {
+        assert!(get_increase_lp_position_args_for_leverage(test_increase_leverage_args(5.1)).is_err());
+        assert!(get_increase_lp_position_args_for_leverage(test_increase_leverage_args(0.9)).is_err());
+        assert!(get_increase_lp_position_args_for_leverage(test_increase_leverage_args(f64::NAN)).is_err());
+
+        let mut args = test_increase_leverage_args(2.5);
+        args.borrow_limit_b = 1_000_000;
+        assert_eq!(
+            get_increase_lp_position_args_for_leverage(args),
+            Err("The amount of token B to borrow exceeds the borrow limit of the market")
+        );
+    }
+
+    fn test_max_lp_position_args() -> MaxLpPositionArgs {
+        MaxLpPositionArgs {
+            collateral_a: 1_000_000,
+            collateral_b: 100_000_000,
+            max_leverage: 5 * HUNDRED_PERCENT,
+            vault_available_a: u64::MAX,
+            vault_available_b: u64::MAX,
+            borrow_limit_available_a: u64::MAX,
+            borrow_limit_available_b: u64::MAX,
+            protocol_fee_rate: (HUNDRED_PERCENT / 100) as u16,
+            protocol_fee_rate_on_collateral: (HUNDRED_PERCENT / 200) as u16,
+            swap_fee_rate: 3000,
+            sqrt_price: price_to_sqrt_price(200.0, 6, 6),
+            tick_lower_index: price_to_tick_index(180.736, 6, 6),
+            tick_upper_index: price_to_tick_index(225.66, 6, 6),
+            liquidation_threshold: HUNDRED_PERCENT * 83 / 100,
+        }
+    }
+
+    #[test]
+    fn test_max_lp_position_bound_by_max_leverage() {
+        let result = get_max_lp_position(test_max_lp_position_args()).unwrap();
+        assert_eq!(result.constraint, LpPositionSizeConstraint::MaxLeverage);
+        assert_eq!(result.leverage, 5.0);
+
+        let quote = get_increase_lp_position_quote(result.quote_args).unwrap();
+        assert!((quote.leverage - 5.0).abs() < 0.05);
+    }
+
+    #[test]
+    fn test_max_lp_position_bound_by_vault_liquidity() {
+        let mut args = test_max_lp_position_args();
+        args.vault_available_b = 200_000_000;
+        args.borrow_limit_available_a = 2_000_000;
+
+        let result = get_max_lp_position(args).unwrap();
+        assert_eq!(result.constraint, LpPositionSizeConstraint::VaultLiquidityB);
+        assert_eq!(result.quote_args.borrow_a, 1185029);
+        assert_eq!(result.quote_args.borrow_b, 200_000_000);
+        assert_eq!(result.leverage, 2.485757611171732);
+
+        let quote = get_increase_lp_position_quote(result.quote_args).unwrap();
+        assert!((quote.leverage - result.leverage).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_max_lp_position_bound_by_borrow_limit() {
+        let mut args = test_max_lp_position_args();
+        args.vault_available_b = 200_000_000;
+        args.borrow_limit_available_a = 500_000;
+
+        let result = get_max_lp_position(args).unwrap();
+        assert_eq!(result.constraint, LpPositionSizeConstraint::BorrowLimitA);
+        assert_eq!(result.quote_args.borrow_a, 500_000);
+        assert_eq!(result.quote_args.borrow_b, 84386102);
+        assert_eq!(result.leverage, 1.6215482431409618);
+    }
+
+    fn test_lp_pnl_args(sqrt_price: u128) -> LpPositionPnlArgs {
+        LpPositionPnlArgs {
+            liquidity: 1109671058,
+            tick_lower_index: price_to_tick_index(180.736, 6, 6),
+            tick_upper_index: price_to_tick_index(225.66, 6, 6),
+            leftovers_a: 0,
+            leftovers_b: 0,
+            loan_funds_a: 2_000_000,
+            loan_funds_b: 400_000_000,
+            debt_a: 2_000_000,
+            debt_b: 400_000_000,
+            compounded_yield_a: 0,
+            compounded_yield_b: 0,
+            entry_sqrt_price: price_to_sqrt_price(213.41, 6, 6),
+            sqrt_price,
+        }
+    }
+
+    #[test]
+    fn test_lp_pnl_at_entry_price() {
+        let pnl = get_lp_position_pnl(test_lp_pnl_args(price_to_sqrt_price(213.41, 6, 6))).unwrap();
+        assert_eq!(pnl.pnl_a, 0);
+        assert_eq!(pnl.pnl_b, 0);
+        assert_eq!(pnl.roe, 0.0);
+        assert_eq!(pnl.impermanent_loss, 0);
+    }
+
+    #[test]
+    fn test_lp_pnl_with_price_change_and_interest() {
+        let mut args = test_lp_pnl_args(price_to_sqrt_price(200.0, 6, 6));
+        args.debt_a = 2_001_000;
+        args.debt_b = 400_200_000;
+
+        let pnl = get_lp_position_pnl(args).unwrap();
+        assert_eq!(pnl.pnl_a, 196009);
+        assert_eq!(pnl.pnl_b, -18138851);
+        assert_eq!(pnl.roe, -0.019877511943949745);
+        assert_eq!(pnl.interest_a, 1_000);
+        assert_eq!(pnl.interest_b, 200_000);
+        assert_eq!(pnl.hodl_value, 1711317480);
+        assert_eq!(pnl.impermanent_loss, -16525072);
+        assert_eq!(pnl.impermanent_loss_percent, -0.009656345005019175);
+    }
+
+    #[test]
+    fn test_lp_pnl_excludes_compounded_yield_from_impermanent_loss() {
+        let mut args = test_lp_pnl_args(price_to_sqrt_price(213.41, 6, 6));
+        args.leftovers_b = 10_000_000;
+        args.compounded_yield_b = 10_000_000;
+
+        let pnl = get_lp_position_pnl(args).unwrap();
+        assert_eq!(pnl.impermanent_loss, 0);
+        assert_eq!(pnl.pnl_b, 10_000_000);
+    }
+
+    #[test]
+    fn test_lp_time_to_liquidation() {
+        let args = LpPositionInterestAccrualArgs {
+            liquidity: *LIQUIDITY,
+            tick_lower_index: *TICK_LOWER_INDEX,
+            tick_upper_index: *TICK_UPPER_INDEX,
+            leftovers_a: 0,
+            leftovers_b: 0,
+            debt_a: 0,
+            debt_b: 8_000_000_000,
+            liquidation_threshold: HUNDRED_PERCENT * 83 / 100,
+            // 10% annually at 90% utilization.
+            interest_rate_a: (1u64 << 60) / 31536000 / 10,
+            utilization_a: 0.9,
+            interest_rate_b: (1u64 << 60) / 31536000 / 10,
+            utilization_b: 0.9,
+        };
+
+        let time = get_lp_position_time_to_liquidation(args, *SQRT_PRICE).unwrap();
+        assert_eq!(time, 105076381);
+
+        let prices = get_lp_position_future_liquidation_prices(args, 0).unwrap();
+        assert_eq!(
+            prices,
+            get_lp_position_liquidation_prices(
+                args.tick_lower_index,
+                args.tick_upper_index,
+                args.liquidity,
+                0,
+                0,
+                0,
+                args.debt_b,
+                args.liquidation_threshold
+            )
+            .unwrap()
+        );
+
+        // The position is liquidated at the current price after the time to liquidation.
+        let prices = get_lp_position_future_liquidation_prices(args, time).unwrap();
+        assert_approx_eq!(prices.lower, 213.41, 1e-4);
+
+        // 40% annually at 100% utilization, the projected debt overflows u64 within 100 years.
+        let mut high_rate_args = args;
+        high_rate_args.utilization_b = 1.0;
+        let high_rate_time = get_lp_position_time_to_liquidation(high_rate_args, *SQRT_PRICE).unwrap();
+        assert_approx_eq!(high_rate_time as f64, time as f64 / 4.0, 100.0);
+
+        let mut args = args;
+        args.debt_b = 0;
+        assert_eq!(get_lp_position_time_to_liquidation(args, *SQRT_PRICE), Ok(u64::MAX));
+    }
 }
@@ -12,15 +12,17 @@ use wasm_bindgen::prelude::wasm_bindgen;
 //#[cfg(feature = "wasm")]
 //use wasm_bindgen::JsValue;
 
+use crate::quote::swap_pool::{SwapPool, SwapPoolFacade};
 use crate::{
-    apply_swap_fee, apply_tuna_protocol_fee, calculate_tuna_protocol_fee, reverse_apply_swap_fee, reverse_apply_tuna_protocol_fee, HUNDRED_PERCENT,
-    INVALID_ARGUMENTS, TOKEN_A, TOKEN_B,
+    apply_swap_fee, apply_tuna_protocol_fee, calculate_tuna_protocol_fee, find_time_to_liquidation, get_vault_projected_debt, reverse_apply_swap_fee,
+    reverse_apply_tuna_protocol_fee, HUNDRED_PERCENT, INVALID_ARGUMENTS, TOKEN_A, TOKEN_B,
 };
 use fusionamm_core::{
-    sqrt_price_to_price, swap_quote_by_input_token, swap_quote_by_output_token, try_get_max_amount_with_slippage_tolerance,
-    try_get_min_amount_with_slippage_tolerance, try_mul_div, CoreError, FusionPoolFacade, TickArrays, TokenPair,
+    div_by_sqrt_price_squared, mul_by_sqrt_price_squared, sqrt_price_to_price, try_get_max_amount_with_slippage_tolerance,
+    try_get_min_amount_with_slippage_tolerance, try_mul_div, CoreError, FusionPoolFacade, TickArrays, TokenPair, ARITHMETIC_OVERFLOW,
 };
 use libm::{ceil, round};
+use orca_whirlpools_core::{OracleFacade, WhirlpoolFacade};
 
 pub const DEFAULT_SLIPPAGE_TOLERANCE_BPS: u16 = 100;
 
@@ -71,12 +73,6 @@ pub struct IncreaseSpotPositionEstimationResult {
     pub protocol_fee_b: u64,
 }
 
-struct SwapPool {
-    pub fusion_pool: FusionPoolFacade,
-    pub tick_arrays: TickArrays,
-    pub slippage_bps: Option<u16>,
-}
-
 fn get_increase_spot_position_quote_internal(
     increase_amount: u64,
     collateral_token: u8,
@@ -105,7 +101,7 @@ fn get_increase_spot_position_quote_internal(
 
     let (fee_rate, slippage_bps) = match pool.as_ref() {
         None => (0, 0),
-        Some(pool) => (pool.fusion_pool.fee_rate, pool.slippage_bps.unwrap_or(DEFAULT_SLIPPAGE_TOLERANCE_BPS)),
+        Some(pool) => (pool.fee_rate()?, pool.slippage_bps.unwrap_or(DEFAULT_SLIPPAGE_TOLERANCE_BPS)),
     };
 
     let borrowed_token = if position_token == TOKEN_A { TOKEN_B } else { TOKEN_A };
@@ -150,9 +146,8 @@ fn get_increase_spot_position_quote_internal(
 
     if swap_input_amount > 0 {
         if let Some(pool) = pool {
-            let quote = swap_quote_by_input_token(swap_input_amount, swap_input_token_is_a, 0, pool.fusion_pool, pool.tick_arrays, None, None)?;
-            estimated_amount += quote.token_est_out;
-            swap_output_amount = quote.token_est_out;
+            swap_output_amount = pool.swap_exact_in(swap_input_amount, swap_input_token_is_a)?;
+            estimated_amount += swap_output_amount;
             min_swap_output_amount = try_get_min_amount_with_slippage_tolerance(swap_output_amount, slippage_bps)?;
             //let new_price = sqrt_price_to_price(quote.next_sqrt_price.into(), 1, 1);
             //price_impact = (new_price / price - 1.0).abs();
@@ -215,8 +210,61 @@ pub fn get_increase_spot_position_quote(
         protocol_fee_rate_on_collateral,
         price,
         Some(SwapPool {
-            fusion_pool,
-            tick_arrays,
+            facade: SwapPoolFacade::Fusion { fusion_pool, tick_arrays },
+            slippage_bps,
+        }),
+    )
+}
+
+/// Spot position increase quote for a market on Orca. Unlike the Fusion version, the swap is simulated
+/// using the Whirlpool math including the adaptive fee.
+///
+/// # Parameters
+/// - `increase_amount`: Position total size in the collateral_token.
+/// - `collateral_token`: Collateral token.
+/// - `position_token`: Token of the position.
+/// - `leverage`: Leverage (1.0 or higher).
+/// - `slippage_bps`: An optional slippage tolerance in basis points. Defaults to the global slippage tolerance if not provided.
+/// - `protocol_fee_rate`: Protocol fee rate from a market account represented as hundredths of a basis point (0.01% = 100).
+/// - `protocol_fee_rate_on_collateral`: Protocol fee rate from a market account represented as hundredths of a basis point (0.01% = 100).
+/// - `whirlpool`: Orca whirlpool.
+/// - `oracle`: Whirlpool oracle. Required for pools with the adaptive fee.
+/// - `tick_arrays`: Five tick arrays around the current pool price.
+/// - `timestamp`: Current blockchain timestamp. Used to compute the adaptive fee.
+///
+/// # Returns
+/// - `IncreaseSpotPositionQuoteResult`: quote result
+#[cfg_attr(feature = "wasm", wasm_expose)]
+pub fn get_increase_spot_position_orca_quote(
+    increase_amount: u64,
+    collateral_token: u8,
+    position_token: u8,
+    leverage: f64,
+    slippage_bps: Option<u16>,
+    protocol_fee_rate: u16,
+    protocol_fee_rate_on_collateral: u16,
+    whirlpool: WhirlpoolFacade,
+    oracle: Option<OracleFacade>,
+    tick_arrays: orca_whirlpools_core::TickArrays,
+    timestamp: u64,
+) -> Result<IncreaseSpotPositionQuoteResult, CoreError> {
+    let price = sqrt_price_to_price(whirlpool.sqrt_price.into(), 1, 1);
+
+    get_increase_spot_position_quote_internal(
+        increase_amount,
+        collateral_token,
+        position_token,
+        leverage,
+        protocol_fee_rate,
+        protocol_fee_rate_on_collateral,
+        price,
+        Some(SwapPool {
+            facade: SwapPoolFacade::Orca {
+                whirlpool,
+                oracle,
+                tick_arrays,
+                timestamp,
+            },
             slippage_bps,
         }),
     )
@@ -408,12 +456,10 @@ fn get_decrease_spot_position_quote_internal(
         if position_debt > 0 {
             swap_output_amount = estimated_payable_debt;
             if let Some(pool) = pool {
-                let swap =
-                    swap_quote_by_output_token(swap_output_amount, borrowed_token == TOKEN_A, 0, pool.fusion_pool, pool.tick_arrays, None, None)?;
-                swap_input_amount = swap.token_est_in;
+                swap_input_amount = pool.swap_exact_out(swap_output_amount, borrowed_token == TOKEN_A)?;
                 //next_sqrt_price = swap.next_sqrt_price;
-                required_swap_amount = try_get_max_amount_with_slippage_tolerance(swap.token_est_in, slippage_bps)?;
-                estimated_collateral_to_be_withdrawn = position_amount.saturating_sub(swap.token_est_in).saturating_sub(estimated_amount);
+                required_swap_amount = try_get_max_amount_with_slippage_tolerance(swap_input_amount, slippage_bps)?;
+                estimated_collateral_to_be_withdrawn = position_amount.saturating_sub(swap_input_amount).saturating_sub(estimated_amount);
             }
         } else {
             estimated_collateral_to_be_withdrawn = position_amount - estimated_amount;
@@ -422,11 +468,10 @@ fn get_decrease_spot_position_quote_internal(
         swap_exact_in = true;
         swap_input_amount = position_amount - estimated_amount;
         if let Some(pool) = pool {
-            let swap = swap_quote_by_input_token(swap_input_amount, position_token == TOKEN_A, 0, pool.fusion_pool, pool.tick_arrays, None, None)?;
+            swap_output_amount = pool.swap_exact_in(swap_input_amount, position_token == TOKEN_A)?;
             //next_sqrt_price = swap.next_sqrt_price;
-            swap_output_amount = swap.token_est_out;
-            required_swap_amount = try_get_min_amount_with_slippage_tolerance(swap.token_est_out, slippage_bps)?;
-            estimated_collateral_to_be_withdrawn = swap.token_est_out.saturating_sub(estimated_payable_debt);
+            required_swap_amount = try_get_min_amount_with_slippage_tolerance(swap_output_amount, slippage_bps)?;
+            estimated_collateral_to_be_withdrawn = swap_output_amount.saturating_sub(estimated_payable_debt);
         }
     }
 
@@ -490,8 +535,56 @@ pub fn get_decrease_spot_position_quote(
         position_debt,
         price,
         Some(SwapPool {
-            fusion_pool,
-            tick_arrays,
+            facade: SwapPoolFacade::Fusion { fusion_pool, tick_arrays },
+            slippage_bps,
+        }),
+    )
+}
+
+/// Spot position decrease quote for a market on Orca.
+///
+/// # Parameters
+/// - `decrease_amount`: Position total decrease size in the collateral_token.
+/// - `collateral_token`: Collateral token.
+/// - `slippage_bps`: An optional slippage tolerance in basis points. Defaults to the global slippage tolerance if not provided.
+/// - `position_token`: Token of the existing position.
+/// - `position_amount`: Existing position amount in the position_token.
+/// - `position_debt`: Existing position debt in the token opposite to the position_token.
+/// - `whirlpool`: Orca whirlpool.
+/// - `oracle`: Whirlpool oracle. Required for pools with the adaptive fee.
+/// - `tick_arrays`: Five tick arrays around the current pool price.
+/// - `timestamp`: Current blockchain timestamp. Used to compute the adaptive fee.
+///
+/// # Returns
+/// - `DecreaseSpotPositionQuoteResult`: quote result
+#[cfg_attr(feature = "wasm", wasm_expose)]
+pub fn get_decrease_spot_position_orca_quote(
+    decrease_amount: u64,
+    collateral_token: u8,
+    slippage_bps: Option<u16>,
+    position_token: u8,
+    position_amount: u64,
+    position_debt: u64,
+    whirlpool: WhirlpoolFacade,
+    oracle: Option<OracleFacade>,
+    tick_arrays: orca_whirlpools_core::TickArrays,
+    timestamp: u64,
+) -> Result<DecreaseSpotPositionQuoteResult, CoreError> {
+    let price = sqrt_price_to_price(whirlpool.sqrt_price.into(), 1, 1);
+    get_decrease_spot_position_quote_internal(
+        decrease_amount,
+        collateral_token,
+        position_token,
+        position_amount,
+        position_debt,
+        price,
+        Some(SwapPool {
+            facade: SwapPoolFacade::Orca {
+                whirlpool,
+                oracle,
+                tick_arrays,
+                timestamp,
+            },
             slippage_bps,
         }),
     )
@@ -588,6 +681,126 @@ pub async fn wasm_get_decrease_spot_position_quote(
 }
 */
 
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "wasm", wasm_expose)]
+pub struct LiquidateSpotPositionQuoteResult {
+    /** The amount of the position token removed from the position. */
+    pub removed_amount: u64,
+    /** Swap input amount in the position token. */
+    pub swap_input_amount: u64,
+    /** Swap output amount in the borrowed token. */
+    pub swap_output_amount: u64,
+    /** The amount of debt that is repaid to the vault. */
+    pub repaid_debt: u64,
+    /** The part of the liquidated debt that can't be repaid and becomes a bad debt of the vault. */
+    pub bad_debt: u64,
+    /** The part of the liquidation fee in the position token transferred to the protocol fee recipient. */
+    pub protocol_fee: u64,
+    /** The part of the liquidation fee in the position token paid to the liquidator. The program doesn't reward the liquidator, so it's zero. */
+    pub liquidator_fee: u64,
+    /** The amount of the collateral token returned to the position owner. */
+    pub returned_amount: u64,
+    /** The position size after the liquidation. */
+    pub amount: u64,
+    /** The position debt after the liquidation. */
+    pub debt: u64,
+}
+
+/// Spot position liquidation quote. The liquidation fee is charged in the position token on the removed amount, but it
+/// can't exceed the funds remaining after the debt repayment. The whole fee goes to the protocol fee recipient.
+/// The removed part of the position is swapped to the borrowed token to repay the liquidated part of the debt.
+/// If the collateral token is the borrowed token, everything except the fee is swapped. The rest of the funds is
+/// returned to the position owner. If the removed funds are not enough to repay the debt, the unpaid part becomes a bad debt.
+///
+/// # Parameters
+/// - `position_token`: Token of the position
+/// - `collateral_token`: The token used as collateral
+/// - `amount`: Position total size
+/// - `debt`: Position total debt including accrued interest
+/// - `decrease_percent`: Liquidation percentage (HUNDRED_PERCENT liquidates the whole position)
+/// - `liquidation_fee`: The liquidation fee of the market represented as hundredths of a basis point
+/// - `swap_fee_rate`: The swap fee rate of a pool denominated in 1e6
+/// - `sqrt_price`: Current sqrt price
+///
+/// # Returns
+/// - `LiquidateSpotPositionQuoteResult`: quote result
+#[cfg_attr(feature = "wasm", wasm_expose)]
+pub fn get_liquidate_spot_position_quote(
+    position_token: u8,
+    collateral_token: u8,
+    amount: u64,
+    debt: u64,
+    decrease_percent: u32,
+    liquidation_fee: u32,
+    swap_fee_rate: u16,
+    sqrt_price: u128,
+) -> Result<LiquidateSpotPositionQuoteResult, CoreError> {
+    if position_token > TOKEN_B || collateral_token > TOKEN_B || decrease_percent > HUNDRED_PERCENT || liquidation_fee > HUNDRED_PERCENT {
+        return Err(INVALID_ARGUMENTS);
+    }
+
+    let removed_amount = try_mul_div(amount, decrease_percent as u128, HUNDRED_PERCENT as u128, false)?;
+    let liquidated_debt = try_mul_div(debt, decrease_percent as u128, HUNDRED_PERCENT as u128, true)?;
+
+    let to_borrowed_token = |amount: u64, round_up: bool| {
+        if position_token == TOKEN_A {
+            mul_by_sqrt_price_squared(amount, sqrt_price, round_up)
+        } else {
+            div_by_sqrt_price_squared(amount, sqrt_price, round_up)
+        }
+    };
+
+    let to_position_token = |amount: u64, round_up: bool| {
+        if position_token == TOKEN_A {
+            div_by_sqrt_price_squared(amount, sqrt_price, round_up)
+        } else {
+            mul_by_sqrt_price_squared(amount, sqrt_price, round_up)
+        }
+    };
+
+    // The amount of the position token required to repay the liquidated debt.
+    let required_swap_input = reverse_apply_swap_fee(to_position_token(liquidated_debt, true)?, swap_fee_rate, true)?.min(removed_amount);
+    let protocol_fee =
+        try_mul_div(removed_amount, liquidation_fee as u128, HUNDRED_PERCENT as u128, false)?.min(removed_amount - required_swap_input);
+
+    let swap_input_amount = if collateral_token == position_token {
+        // Only the amount required to repay the debt is swapped.
+        required_swap_input
+    } else {
+        removed_amount - protocol_fee
+    };
+
+    let swap_output_amount = if swap_input_amount == 0 {
+        0
+    } else if swap_input_amount == required_swap_input && swap_input_amount < removed_amount {
+        liquidated_debt
+    } else {
+        to_borrowed_token(apply_swap_fee(swap_input_amount, swap_fee_rate, false)?, false)?
+    };
+
+    let repaid_debt = swap_output_amount.min(liquidated_debt);
+
+    // The funds remaining in the collateral token.
+    let returned_amount = if collateral_token == position_token {
+        removed_amount - swap_input_amount - protocol_fee
+    } else {
+        swap_output_amount - repaid_debt
+    };
+
+    Ok(LiquidateSpotPositionQuoteResult {
+        removed_amount,
+        swap_input_amount,
+        swap_output_amount,
+        repaid_debt,
+        bad_debt: liquidated_debt - repaid_debt,
+        protocol_fee,
+        liquidator_fee: 0,
+        returned_amount,
+        amount: amount - removed_amount,
+        debt: debt - liquidated_debt,
+    })
+}
+
 /// Returns the liquidation price
 ///
 /// # Parameters
@@ -617,6 +830,154 @@ pub fn get_spot_position_liquidation_price(position_token: u8, amount: u64, debt
     }
 }
 
+/// Returns the number of seconds until the position becomes unhealthy at the provided price due to accrued interest,
+/// assuming the vault utilization doesn't change.
+///
+/// # Parameters
+/// - `position_token`: Token of the position
+/// - `amount`: Position total size
+/// - `debt`: Position total debt including accrued interest
+/// - `liquidation_threshold`: Liquidation threshold of a market
+/// - `interest_rate`: The interest rate of the borrowed token vault per second (Fixed128 bits)
+/// - `utilization`: The utilization of the borrowed token vault (1.0 is equal to 100%)
+/// - `sqrt_price`: Current sqrt price
+///
+/// # Returns
+/// - `u64`: seconds until liquidation, zero if the position is already unhealthy, or u64::MAX if it doesn't happen within 100 years
+#[cfg_attr(feature = "wasm", wasm_expose)]
+pub fn get_spot_position_time_to_liquidation(
+    position_token: u8,
+    amount: u64,
+    debt: u64,
+    liquidation_threshold: u32,
+    interest_rate: u64,
+    utilization: f64,
+    sqrt_price: u128,
+) -> Result<u64, CoreError> {
+    if position_token > TOKEN_B || liquidation_threshold >= HUNDRED_PERCENT {
+        return Err(INVALID_ARGUMENTS);
+    }
+
+    if debt == 0 {
+        return Ok(u64::MAX);
+    }
+
+    // The position value and the debt valued in the position token.
+    let total = amount as u128 * liquidation_threshold as u128 / HUNDRED_PERCENT as u128;
+
+    find_time_to_liquidation(|duration| {
+        let debt = get_vault_projected_debt(debt, interest_rate, utilization, duration)?;
+        let debt = if position_token == TOKEN_A {
+            div_by_sqrt_price_squared(debt, sqrt_price, true)?
+        } else {
+            mul_by_sqrt_price_squared(debt, sqrt_price, true)?
+        };
+        Ok(debt as u128 > total)
+    })
+}
+
+/// Returns the liquidation price of the position after the given period of time, taking into account
+/// the interest accrued on the debt and assuming the vault utilization doesn't change.
+///
+/// # Parameters
+/// - `position_token`: Token of the position
+/// - `amount`: Position total size
+/// - `debt`: Position total debt including accrued interest
+/// - `liquidation_threshold`: Liquidation threshold of a market
+/// - `interest_rate`: The interest rate of the borrowed token vault per second (Fixed128 bits)
+/// - `utilization`: The utilization of the borrowed token vault (1.0 is equal to 100%)
+/// - `duration`: The period of time in seconds
+///
+/// # Returns
+/// - `f64`: Decimal liquidation price
+#[cfg_attr(feature = "wasm", wasm_expose)]
+pub fn get_spot_position_future_liquidation_price(
+    position_token: u8,
+    amount: u64,
+    debt: u64,
+    liquidation_threshold: u32,
+    interest_rate: u64,
+    utilization: f64,
+    duration: u64,
+) -> Result<f64, CoreError> {
+    let debt = get_vault_projected_debt(debt, interest_rate, utilization, duration)?;
+    get_spot_position_liquidation_price(position_token, amount, debt, liquidation_threshold)
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "wasm", wasm_expose)]
+pub struct SpotPositionPnl {
+    /** Unrealized PnL valued in token A. */
+    pub pnl_a: i64,
+    /** Unrealized PnL valued in token B. */
+    pub pnl_b: i64,
+    /** Return on equity: the unrealized PnL relative to the position equity at entry, both valued in the collateral token. */
+    pub roe: f64,
+    /** Interest accrued on the debt since the position was opened (debt - loan_funds), in the borrowed token. */
+    pub interest: u64,
+}
+
+/// Returns the unrealized PnL of a spot position. The position equity at the entry price is compared
+/// with the current equity, and the difference is valued in both tokens.
+///
+/// # Parameters
+/// - `position_token`: Token of the position
+/// - `collateral_token`: The token used as collateral
+/// - `amount`: Position total size
+/// - `loan_funds`: The amount of borrowed funds without accrued interest
+/// - `debt`: Position total debt including accrued interest
+/// - `entry_sqrt_price`: Position entry sqrt price
+/// - `sqrt_price`: Current sqrt price
+///
+/// # Returns
+/// - `SpotPositionPnl`: unrealized PnL, ROE and interest paid
+#[cfg_attr(feature = "wasm", wasm_expose)]
+pub fn get_spot_position_pnl(
+    position_token: u8,
+    collateral_token: u8,
+    amount: u64,
+    loan_funds: u64,
+    debt: u64,
+    entry_sqrt_price: u128,
+    sqrt_price: u128,
+) -> Result<SpotPositionPnl, CoreError> {
+    if position_token > TOKEN_B || collateral_token > TOKEN_B || entry_sqrt_price == 0 || sqrt_price == 0 {
+        return Err(INVALID_ARGUMENTS);
+    }
+
+    // Position equity in the given token: the position amount minus the debt in the opposite token.
+    let equity = |token: u8, debt: u64, sqrt_price: u128| -> Result<i128, CoreError> {
+        let (amount, debt) = if token == TOKEN_B {
+            if position_token == TOKEN_A {
+                (mul_by_sqrt_price_squared(amount, sqrt_price, false)?, debt)
+            } else {
+                (amount, mul_by_sqrt_price_squared(debt, sqrt_price, true)?)
+            }
+        } else {
+            if position_token == TOKEN_A {
+                (amount, div_by_sqrt_price_squared(debt, sqrt_price, true)?)
+            } else {
+                (div_by_sqrt_price_squared(amount, sqrt_price, false)?, debt)
+            }
+        };
+        Ok(amount as i128 - debt as i128)
+    };
+
+    let pnl_a = equity(TOKEN_A, debt, sqrt_price)? - equity(TOKEN_A, loan_funds, entry_sqrt_price)?;
+    let pnl_b = equity(TOKEN_B, debt, sqrt_price)? - equity(TOKEN_B, loan_funds, entry_sqrt_price)?;
+
+    let entry_equity = equity(collateral_token, loan_funds, entry_sqrt_price)?;
+    let pnl = if collateral_token == TOKEN_A { pnl_a } else { pnl_b };
+    let roe = if entry_equity > 0 { pnl as f64 / entry_equity as f64 } else { 0.0 };
+
+    Ok(SpotPositionPnl {
+        pnl_a: pnl_a.try_into().map_err(|_| ARITHMETIC_OVERFLOW)?,
+        pnl_b: pnl_b.try_into().map_err(|_| ARITHMETIC_OVERFLOW)?,
+        roe,
+        interest: debt.saturating_sub(loan_funds),
+    })
+}
+
 /// Calculates the maximum tradable amount in the collateral token.
 ///
 /// # Parameters
@@ -817,6 +1178,43 @@ mod tests {
         .into()
     }
 
+    fn test_whirlpool(sqrt_price: u128) -> WhirlpoolFacade {
+        let tick_current_index = sqrt_price_to_tick_index(sqrt_price);
+        WhirlpoolFacade {
+            fee_tier_index_seed: 2u16.to_le_bytes(),
+            tick_current_index,
+            fee_rate: 3000,
+            liquidity: 10000000000000,
+            sqrt_price,
+            tick_spacing: 2,
+            ..WhirlpoolFacade::default()
+        }
+    }
+
+    fn test_orca_tick_array(start_tick_index: i32) -> orca_whirlpools_core::TickArrayFacade {
+        orca_whirlpools_core::TickArrayFacade {
+            start_tick_index,
+            ticks: [orca_whirlpools_core::TickFacade {
+                initialized: true,
+                ..orca_whirlpools_core::TickFacade::default()
+            }; orca_whirlpools_core::TICK_ARRAY_SIZE],
+        }
+    }
+
+    fn test_orca_tick_arrays(whirlpool: WhirlpoolFacade) -> orca_whirlpools_core::TickArrays {
+        let tick_spacing = whirlpool.tick_spacing;
+        let tick_array_start_index = get_tick_array_start_tick_index(whirlpool.tick_current_index, tick_spacing);
+
+        [
+            test_orca_tick_array(tick_array_start_index),
+            test_orca_tick_array(tick_array_start_index + TICK_ARRAY_SIZE as i32 * tick_spacing as i32),
+            test_orca_tick_array(tick_array_start_index + TICK_ARRAY_SIZE as i32 * tick_spacing as i32 * 2),
+            test_orca_tick_array(tick_array_start_index - TICK_ARRAY_SIZE as i32 * tick_spacing as i32),
+            test_orca_tick_array(tick_array_start_index - TICK_ARRAY_SIZE as i32 * tick_spacing as i32 * 2),
+        ]
+        .into()
+    }
+
     #[test]
     fn test_get_liquidation_price() {
         assert_eq!(get_spot_position_liquidation_price(TOKEN_A, 5, 0, HUNDRED_PERCENT * 85 / 100), Ok(0.0));
@@ -825,6 +1223,112 @@ mod tests {
         assert_eq!(get_spot_position_liquidation_price(TOKEN_B, 1000, 4, HUNDRED_PERCENT * 85 / 100), Ok(212.5));
     }
 
+    #[test]
+    fn test_liquidate_long_position() {
+        let sqrt_price = price_to_sqrt_price(200.0, 1, 1);
+
+        // Collateral in the position token: only the debt is swapped.
+        let quote =
+            get_liquidate_spot_position_quote(TOKEN_A, TOKEN_A, 5_000_000, 900_000_000, HUNDRED_PERCENT, HUNDRED_PERCENT / 100, 0, sqrt_price)
+                .unwrap();
+        assert_eq!(quote.removed_amount, 5_000_000);
+        assert_eq!(quote.swap_input_amount, 4_500_000);
+        assert_eq!(quote.swap_output_amount, 900_000_000);
+        assert_eq!(quote.repaid_debt, 900_000_000);
+        assert_eq!(quote.bad_debt, 0);
+        assert_eq!(quote.protocol_fee, 50_000);
+        assert_eq!(quote.returned_amount, 450_000);
+        assert_eq!(quote.amount, 0);
+        assert_eq!(quote.debt, 0);
+
+        // Collateral in the borrowed token: the whole position is swapped.
+        let quote =
+            get_liquidate_spot_position_quote(TOKEN_A, TOKEN_B, 5_000_000, 900_000_000, HUNDRED_PERCENT / 2, HUNDRED_PERCENT / 100, 0, sqrt_price)
+                .unwrap();
+        assert_eq!(quote.removed_amount, 2_500_000);
+        assert_eq!(quote.swap_input_amount, 2_475_000);
+        assert_eq!(quote.swap_output_amount, 495_000_000);
+        assert_eq!(quote.repaid_debt, 450_000_000);
+        assert_eq!(quote.protocol_fee, 25_000);
+        assert_eq!(quote.returned_amount, 45_000_000);
+        assert_eq!(quote.amount, 2_500_000);
+        assert_eq!(quote.debt, 450_000_000);
+    }
+
+    #[test]
+    fn test_liquidate_position_with_bad_debt() {
+        let quote = get_liquidate_spot_position_quote(
+            TOKEN_B,
+            TOKEN_B,
+            1_000_000_000,
+            6_000_000,
+            HUNDRED_PERCENT,
+            HUNDRED_PERCENT / 100,
+            0,
+            price_to_sqrt_price(200.0, 1, 1),
+        )
+        .unwrap();
+        assert_eq!(quote.swap_input_amount, 1_000_000_000);
+        assert_eq!(quote.swap_output_amount, 4_999_999);
+        assert_eq!(quote.repaid_debt, 4_999_999);
+        assert_eq!(quote.bad_debt, 1_000_001);
+        assert_eq!(quote.protocol_fee, 0);
+        assert_eq!(quote.returned_amount, 0);
+    }
+
+    // The amounts mirror the spot position liquidation TS tests (1% liquidation fee, the price after the swap in the pool).
+    #[test]
+    fn test_liquidate_position_matches_program() {
+        // LONG position with the collateral in token B.
+        let quote = get_liquidate_spot_position_quote(
+            TOKEN_A,
+            TOKEN_B,
+            9_447_566_700,
+            1_700_000_000,
+            HUNDRED_PERCENT,
+            HUNDRED_PERCENT / 100,
+            0,
+            price_to_sqrt_price(1_820_529_141.0 / 9_353_091_033.0, 1, 1),
+        )
+        .unwrap();
+        assert_eq!(quote.protocol_fee, 94_475_667);
+        assert_eq!(quote.liquidator_fee, 0);
+        assert_eq!(quote.repaid_debt, 1_700_000_000);
+        assert_approx_eq!(quote.returned_amount as f64, 120_529_141.0, 1.0);
+
+        // SHORT position with the collateral in token B.
+        let quote = get_liquidate_spot_position_quote(
+            TOKEN_B,
+            TOKEN_B,
+            1_395_037_300,
+            6_000_000_000,
+            HUNDRED_PERCENT,
+            HUNDRED_PERCENT / 100,
+            0,
+            price_to_sqrt_price(1_363_812_486.0 / 6_000_000_000.0, 1, 1),
+        )
+        .unwrap();
+        assert_eq!(quote.protocol_fee, 13_950_373);
+        assert_eq!(quote.repaid_debt, 6_000_000_000);
+        assert_approx_eq!(quote.returned_amount as f64, 17_274_441.0, 1.0);
+
+        // LONG position with a bad debt: no fee is charged.
+        let quote = get_liquidate_spot_position_quote(
+            TOKEN_A,
+            TOKEN_B,
+            9_447_566_700,
+            1_700_000_000,
+            HUNDRED_PERCENT,
+            HUNDRED_PERCENT / 100,
+            0,
+            price_to_sqrt_price(904_701_214.0 / 9_447_566_700.0, 1, 1),
+        )
+        .unwrap();
+        assert_eq!(quote.protocol_fee, 0);
+        assert_eq!(quote.returned_amount, 0);
+        assert_approx_eq!(quote.bad_debt as f64, 795_298_786.0, 1.0);
+    }
+
     #[tokio::test]
     async fn increase_long_position_providing_token_a() {
         let sqrt_price = price_to_sqrt_price(200.0, 9, 6);
@@ -1328,4 +1832,238 @@ mod tests {
             assert_eq!(quote.estimated_amount, 0);
         }
     }
+
+    #[tokio::test]
+    async fn increase_position_orca_quote_matches_fusion_quote() {
+        let sqrt_price = price_to_sqrt_price(200.0, 9, 6);
+        let fusion_pool = test_fusion_pool(sqrt_price);
+        let whirlpool = test_whirlpool(sqrt_price);
+
+        for (collateral_token, position_token) in [(TOKEN_A, TOKEN_A), (TOKEN_B, TOKEN_A), (TOKEN_A, TOKEN_B), (TOKEN_B, TOKEN_B)] {
+            let fusion_quote = get_increase_spot_position_quote(
+                5_000_000_000,
+                collateral_token,
+                position_token,
+                5.0,
+                Some(0),
+                (HUNDRED_PERCENT / 100) as u16,
+                (HUNDRED_PERCENT / 200) as u16,
+                fusion_pool,
+                test_tick_arrays(fusion_pool),
+            )
+            .unwrap();
+
+            let orca_quote = get_increase_spot_position_orca_quote(
+                5_000_000_000,
+                collateral_token,
+                position_token,
+                5.0,
+                Some(0),
+                (HUNDRED_PERCENT / 100) as u16,
+                (HUNDRED_PERCENT / 200) as u16,
+                whirlpool,
+                None,
+                test_orca_tick_arrays(whirlpool),
+                0,
+            )
+            .unwrap();
+
+            assert_eq!(orca_quote.collateral, fusion_quote.collateral);
+            assert_eq!(orca_quote.borrow, fusion_quote.borrow);
+            assert_eq!(orca_quote.swap_input_amount, fusion_quote.swap_input_amount);
+            assert_eq!(orca_quote.swap_output_amount, fusion_quote.swap_output_amount);
+            assert_eq!(orca_quote.estimated_amount, fusion_quote.estimated_amount);
+        }
+    }
+
+    #[tokio::test]
+    async fn increase_position_orca_quote_uses_adaptive_fee() {
+        let sqrt_price = price_to_sqrt_price(200.0, 9, 6);
+        let whirlpool = WhirlpoolFacade {
+            fee_tier_index_seed: 1026u16.to_le_bytes(),
+            ..test_whirlpool(sqrt_price)
+        };
+        let timestamp = 1_000_000;
+        let oracle = OracleFacade {
+            adaptive_fee_constants: orca_whirlpools_core::AdaptiveFeeConstantsFacade {
+                filter_period: 30,
+                decay_period: 600,
+                reduction_factor: 5000,
+                adaptive_fee_control_factor: 50000,
+                max_volatility_accumulator: 350000,
+                tick_group_size: 2,
+                major_swap_threshold_ticks: 2,
+            },
+            adaptive_fee_variables: orca_whirlpools_core::AdaptiveFeeVariablesFacade {
+                last_reference_update_timestamp: timestamp,
+                last_major_swap_timestamp: timestamp,
+                volatility_reference: 300000,
+                tick_group_index_reference: whirlpool.tick_current_index.div_euclid(2),
+                volatility_accumulator: 300000,
+            },
+            ..OracleFacade::default()
+        };
+
+        let pool = SwapPool {
+            facade: SwapPoolFacade::Orca {
+                whirlpool,
+                oracle: Some(oracle),
+                tick_arrays: test_orca_tick_arrays(whirlpool),
+                timestamp,
+            },
+            slippage_bps: None,
+        };
+        // Static 0.3% + adaptive 0.18%.
+        assert_eq!(pool.fee_rate(), Ok(4800));
+
+        let fusion_pool = FusionPoolFacade {
+            fee_rate: 4800,
+            ..test_fusion_pool(sqrt_price)
+        };
+        let fusion_quote = get_increase_spot_position_quote(
+            5_000_000_000,
+            TOKEN_B,
+            TOKEN_A,
+            5.0,
+            Some(0),
+            (HUNDRED_PERCENT / 100) as u16,
+            (HUNDRED_PERCENT / 200) as u16,
+            fusion_pool,
+            test_tick_arrays(fusion_pool),
+        )
+        .unwrap();
+        let orca_quote = get_increase_spot_position_orca_quote(
+            5_000_000_000,
+            TOKEN_B,
+            TOKEN_A,
+            5.0,
+            Some(0),
+            (HUNDRED_PERCENT / 100) as u16,
+            (HUNDRED_PERCENT / 200) as u16,
+            whirlpool,
+            Some(oracle),
+            test_orca_tick_arrays(whirlpool),
+            timestamp,
+        )
+        .unwrap();
+
+        assert_eq!(orca_quote.collateral, fusion_quote.collateral);
+        assert_eq!(orca_quote.borrow, fusion_quote.borrow);
+    }
+
+    #[tokio::test]
+    async fn decrease_long_position_orca_quote() {
+        let sqrt_price = price_to_sqrt_price(200.0, 9, 6);
+        let whirlpool = test_whirlpool(sqrt_price);
+
+        let quote = get_decrease_spot_position_orca_quote(
+            1_000_000_000,
+            TOKEN_A,
+            Some(0),
+            TOKEN_A,
+            5_000_000_000, // A
+            800_000_000,   // B
+            whirlpool,
+            None,
+            test_orca_tick_arrays(whirlpool),
+            0,
+        )
+        .unwrap();
+
+        assert_eq!(quote.decrease_percent, 200000);
+        assert_eq!(quote.estimated_amount, 4_000_000_000);
+        assert_eq!(quote.estimated_payable_debt, 160_000_000);
+        assert!(!quote.swap_exact_in);
+        assert_eq!(quote.swap_input_amount, 802_435_931);
+        assert_eq!(quote.swap_output_amount, 160_000_000);
+        assert_eq!(quote.estimated_collateral_to_be_withdrawn, 197_564_069);
+
+        let quote = get_decrease_spot_position_orca_quote(
+            200_000_000,
+            TOKEN_B,
+            Some(0),
+            TOKEN_A,
+            5_000_000_000, // A
+            800_000_000,   // B
+            whirlpool,
+            None,
+            test_orca_tick_arrays(whirlpool),
+            0,
+        )
+        .unwrap();
+
+        assert!(quote.swap_exact_in);
+        assert_eq!(quote.swap_input_amount, 1_000_000_000);
+        assert_eq!(quote.swap_output_amount, 199_391_108);
+        assert_eq!(quote.estimated_collateral_to_be_withdrawn, 39_391_108);
+    }
+
+    #[test]
+    fn pnl_of_long_position() {
+        let pnl = get_spot_position_pnl(
+            TOKEN_A,
+            TOKEN_B,
+            1_000_000_000,   // amount of A
+            100_000_000_000, // loan_funds in B
+            100_100_000_000, // debt in B
+            price_to_sqrt_price(200.0, 1, 1),
+            price_to_sqrt_price(220.0, 1, 1),
+        )
+        .unwrap();
+
+        assert_approx_eq!(pnl.pnl_b as f64, 19_900_000_000.0, 1_000.0);
+        assert_approx_eq!(pnl.pnl_a as f64, 45_000_000.0, 1_000.0);
+        assert_approx_eq!(pnl.roe, 0.199, 1e-6);
+        assert_eq!(pnl.interest, 100_000_000);
+    }
+
+    #[test]
+    fn pnl_of_short_position() {
+        let pnl = get_spot_position_pnl(
+            TOKEN_B,
+            TOKEN_B,
+            400_000_000_000, // amount of B
+            1_000_000_000,   // loan_funds in A
+            1_000_000_000,   // debt in A
+            price_to_sqrt_price(200.0, 1, 1),
+            price_to_sqrt_price(250.0, 1, 1),
+        )
+        .unwrap();
+
+        assert_approx_eq!(pnl.pnl_b as f64, -50_000_000_000.0, 1_000.0);
+        assert_approx_eq!(pnl.pnl_a as f64, 600_000_000.0 - 1_000_000_000.0, 1_000.0);
+        assert_approx_eq!(pnl.roe, -0.25, 1e-6);
+        assert_eq!(pnl.interest, 0);
+    }
+
+    #[test]
+    fn time_to_liquidation_of_long_position() {
+        // 10% annually at 90% utilization.
+        let interest_rate = (1u64 << 60) / 31536000 / 10;
+        let sqrt_price = price_to_sqrt_price(200.0, 1, 1);
+
+        // The position value is 200e9 (B), the liquidation happens when the debt reaches 166e9.
+        let time = get_spot_position_time_to_liquidation(TOKEN_A, 1_000_000_000, 150_000_000_000, 830_000, interest_rate, 0.9, sqrt_price).unwrap();
+        assert_approx_eq!(time as f64 / 31536000.0, (166.0f64 / 150.0).ln() / 0.1, 1e-3);
+
+        let price = get_spot_position_future_liquidation_price(TOKEN_A, 1_000_000_000, 150_000_000_000, 830_000, interest_rate, 0.9, time).unwrap();
+        assert_approx_eq!(price, 200.0, 1e-3);
+
+        assert_eq!(get_spot_position_time_to_liquidation(TOKEN_A, 1_000_000_000, 0, 830_000, interest_rate, 0.9, sqrt_price), Ok(u64::MAX));
+        assert_eq!(
+            get_spot_position_time_to_liquidation(TOKEN_A, 1_000_000_000, 170_000_000_000, 830_000, interest_rate, 0.9, sqrt_price),
+            Ok(0)
+        );
+    }
+
+    #[test]
+    fn time_to_liquidation_at_high_borrow_rate() {
+        // 40% annually at 100% utilization, the projected debt overflows u64 within 100 years.
+        let interest_rate = (1u64 << 60) / 31536000 / 10;
+        let sqrt_price = price_to_sqrt_price(200.0, 1, 1);
+        assert!(get_vault_projected_debt(150_000_000_000, interest_rate, 1.0, 100 * 31536000).is_err());
+
+        let time = get_spot_position_time_to_liquidation(TOKEN_A, 1_000_000_000, 150_000_000_000, 830_000, interest_rate, 1.0, sqrt_price).unwrap();
+        assert_approx_eq!(time as f64 / 31536000.0, (166.0f64 / 150.0).ln() / 0.4, 1e-3);
+    }
 }
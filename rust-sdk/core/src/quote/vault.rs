@@ -0,0 +1,209 @@
+use crate::borrow_curve::{BorrowCurve, KinkedBorrowCurve};
+use crate::{Fixed128, SECONDS_PER_YEAR};
+use fusionamm_core::{CoreError, ARITHMETIC_OVERFLOW};
+use libm::exp;
+
+#[cfg(feature = "wasm")]
+use fusionamm_macros::wasm_expose;
+
+/// Returns the annual borrow rate (APR) of a vault.
+///
+/// # Parameters
+/// - `interest_rate`: The interest rate of a vault per second (Fixed128 bits, (1<<60) / 31536000 = 100% annually)
+/// - `utilization`: Vault utilization (1.0 is equal to 100%)
+///
+/// # Returns
+/// - `f64`: the annual borrow rate (1.0 is equal to 100%)
+#[cfg_attr(feature = "wasm", wasm_expose)]
+pub fn get_vault_borrow_rate(interest_rate: u64, utilization: f64) -> f64 {
+    get_vault_borrow_rate_with_curve(&KinkedBorrowCurve::default(), interest_rate, utilization)
+}
+
+/// Returns the annual borrow rate (APR) of a vault using the provided borrow curve.
+pub fn get_vault_borrow_rate_with_curve(curve: &dyn BorrowCurve, interest_rate: u64, utilization: f64) -> f64 {
+    Fixed128::from_bits(interest_rate as u128).to_num::<f64>() * curve.sample(utilization) * SECONDS_PER_YEAR as f64
+}
+
+/// Returns the annual supply rate (APR) of a vault. All accrued interest is distributed to the suppliers,
+/// so the supply rate is the borrow rate scaled by the utilization.
+///
+/// # Parameters
+/// - `interest_rate`: The interest rate of a vault per second (Fixed128 bits)
+/// - `utilization`: Vault utilization (1.0 is equal to 100%)
+///
+/// # Returns
+/// - `f64`: the annual supply rate (1.0 is equal to 100%)
+#[cfg_attr(feature = "wasm", wasm_expose)]
+pub fn get_vault_supply_rate(interest_rate: u64, utilization: f64) -> f64 {
+    get_vault_supply_rate_with_curve(&KinkedBorrowCurve::default(), interest_rate, utilization)
+}
+
+/// Returns the annual supply rate (APR) of a vault using the provided borrow curve.
+pub fn get_vault_supply_rate_with_curve(curve: &dyn BorrowCurve, interest_rate: u64, utilization: f64) -> f64 {
+    get_vault_borrow_rate_with_curve(curve, interest_rate, utilization) * utilization.clamp(0.0, 1.0)
+}
+
+/// Converts an annual rate to a yield with continuous compounding.
+///
+/// The vault compounds the interest on each accrual using a three-term Taylor expansion of e^r - 1,
+/// so continuous compounding is an approximation of the on-chain accrual.
+///
+/// # Parameters
+/// - `rate`: The annual rate (1.0 is equal to 100%)
+///
+/// # Returns
+/// - `f64`: the annual yield (APY)
+#[cfg_attr(feature = "wasm", wasm_expose)]
+pub fn rate_to_apy(rate: f64) -> f64 {
+    exp(rate) - 1.0
+}
+
+/// Returns the projected debt after the given period of time, assuming the vault utilization doesn't change.
+///
+/// # Parameters
+/// - `debt`: The current debt including accrued interest
+/// - `interest_rate`: The interest rate of a vault per second (Fixed128 bits)
+/// - `utilization`: Vault utilization (1.0 is equal to 100%)
+/// - `duration`: The projection period in seconds
+///
+/// # Returns
+/// - `u64`: the projected debt
+#[cfg_attr(feature = "wasm", wasm_expose)]
+pub fn get_vault_projected_debt(debt: u64, interest_rate: u64, utilization: f64, duration: u64) -> Result<u64, CoreError> {
+    get_vault_projected_debt_with_curve(&KinkedBorrowCurve::default(), debt, interest_rate, utilization, duration)
+}
+
+/// Returns the projected debt after the given period of time using the provided borrow curve.
+pub fn get_vault_projected_debt_with_curve(
+    curve: &dyn BorrowCurve,
+    debt: u64,
+    interest_rate: u64,
+    utilization: f64,
+    duration: u64,
+) -> Result<u64, CoreError> {
+    let rate = get_vault_borrow_rate_with_curve(curve, interest_rate, utilization) * duration as f64 / SECONDS_PER_YEAR as f64;
+    let projected_debt = debt as f64 * exp(rate);
+    if projected_debt >= u64::MAX as f64 {
+        return Err(ARITHMETIC_OVERFLOW);
+    }
+    Ok(projected_debt as u64)
+}
+
+/// Returns the borrow rate curve of a vault sampled at evenly spaced utilization points from 0% to 100%.
+///
+/// # Parameters
+/// - `interest_rate`: The interest rate of a vault per second (Fixed128 bits)
+/// - `points`: The number of points (at least 2)
+///
+/// # Returns
+/// - `Vec<f64>`: annual borrow rates, where the i-th rate corresponds to the utilization i / (points - 1)
+#[cfg_attr(feature = "wasm", wasm_expose)]
+pub fn get_vault_borrow_rate_curve(interest_rate: u64, points: u32) -> Vec<f64> {
+    get_vault_borrow_rate_curve_with_curve(&KinkedBorrowCurve::default(), interest_rate, points)
+}
+
+/// Returns the borrow rate curve of a vault using the provided borrow curve.
+pub fn get_vault_borrow_rate_curve_with_curve(curve: &dyn BorrowCurve, interest_rate: u64, points: u32) -> Vec<f64> {
+    let points = points.max(2);
+    (0..points)
+        .map(|i| get_vault_borrow_rate_with_curve(curve, interest_rate, i as f64 / (points - 1) as f64))
+        .collect()
+}
+
+/// The maximum period used to search for the time to liquidation (100 years).
+const MAX_TIME_TO_LIQUIDATION: u64 = 100 * SECONDS_PER_YEAR;
+
+/// Returns the number of seconds until `is_unhealthy` becomes true, assuming it's monotonic in time.
+/// Returns u64::MAX if it doesn't happen within MAX_TIME_TO_LIQUIDATION.
+pub(crate) fn find_time_to_liquidation<F>(is_unhealthy: F) -> Result<u64, CoreError>
+where
+    F: Fn(u64) -> Result<bool, CoreError>,
+{
+    if is_unhealthy(0)? {
+        return Ok(0);
+    }
+
+    // At high borrow rates the projected debt overflows u64 long before the end of the search period.
+    // Such a debt can't be covered by any position, so the overflow is treated as unhealthy.
+    let is_unhealthy_after = |duration: u64| match is_unhealthy(duration) {
+        Err(err) if err == ARITHMETIC_OVERFLOW => Ok(true),
+        result => result,
+    };
+
+    if !is_unhealthy_after(MAX_TIME_TO_LIQUIDATION)? {
+        return Ok(u64::MAX);
+    }
+
+    let mut healthy = 0;
+    let mut unhealthy = MAX_TIME_TO_LIQUIDATION;
+    while unhealthy - healthy > 1 {
+        let mid = healthy + (unhealthy - healthy) / 2;
+        if is_unhealthy_after(mid)? {
+            unhealthy = mid;
+        } else {
+            healthy = mid;
+        }
+    }
+
+    Ok(unhealthy)
+}
+
+#[cfg(all(test, not(feature = "wasm")))]
+mod tests {
+    use super::*;
+    use crate::assert_approx_eq;
+
+    // 10% annually.
+    const INTEREST_RATE: u64 = (1u64 << 60) / SECONDS_PER_YEAR / 10;
+
+    #[test]
+    fn test_borrow_and_supply_rate() {
+        assert_approx_eq!(get_vault_borrow_rate(INTEREST_RATE, 0.9), 0.1, 1e-6);
+        assert_approx_eq!(get_vault_borrow_rate(INTEREST_RATE, 1.0), 0.4, 1e-6);
+        assert_approx_eq!(get_vault_borrow_rate(INTEREST_RATE, 0.0), 0.025, 1e-6);
+        assert_approx_eq!(get_vault_supply_rate(INTEREST_RATE, 0.9), 0.09, 1e-6);
+        assert_eq!(get_vault_supply_rate(INTEREST_RATE, 0.0), 0.0);
+    }
+
+    #[test]
+    fn test_custom_borrow_curve() {
+        struct FlatCurve;
+        impl BorrowCurve for FlatCurve {
+            fn sample(&self, _utilization: f64) -> f64 {
+                2.0
+            }
+        }
+
+        assert_approx_eq!(get_vault_borrow_rate_with_curve(&FlatCurve, INTEREST_RATE, 0.0), 0.2, 1e-6);
+        assert_approx_eq!(get_vault_supply_rate_with_curve(&FlatCurve, INTEREST_RATE, 0.5), 0.1, 1e-6);
+
+        let curve = KinkedBorrowCurve {
+            target_utilization: 0.8,
+            k: 2.0,
+        };
+        assert_approx_eq!(get_vault_borrow_rate_with_curve(&curve, INTEREST_RATE, 0.8), 0.1, 1e-6);
+        assert_approx_eq!(get_vault_borrow_rate_with_curve(&curve, INTEREST_RATE, 0.9), 0.15, 1e-6);
+        assert_approx_eq!(get_vault_borrow_rate_with_curve(&curve, INTEREST_RATE, 0.0), 0.05, 1e-6);
+    }
+
+    #[test]
+    fn test_rate_to_apy() {
+        assert_eq!(rate_to_apy(0.0), 0.0);
+        assert_approx_eq!(rate_to_apy(0.1), 0.105170918, 1e-6);
+    }
+
+    #[test]
+    fn test_projected_debt() {
+        assert_eq!(get_vault_projected_debt(1_000_000_000, INTEREST_RATE, 0.9, 0).unwrap(), 1_000_000_000);
+        assert_approx_eq!(get_vault_projected_debt(1_000_000_000, INTEREST_RATE, 0.9, SECONDS_PER_YEAR).unwrap() as f64, 1_105_170_918.0, 100.0);
+    }
+
+    #[test]
+    fn test_borrow_rate_curve() {
+        let curve = get_vault_borrow_rate_curve(INTEREST_RATE, 11);
+        assert_eq!(curve.len(), 11);
+        assert_approx_eq!(curve[0], 0.025, 1e-6);
+        assert_approx_eq!(curve[9], 0.1, 1e-6);
+        assert_approx_eq!(curve[10], 0.4, 1e-6);
+    }
+}
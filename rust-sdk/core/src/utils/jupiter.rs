@@ -1,3 +1,5 @@
+use crate::{INCORRECT_JUPITER_SWAP_INPUT_AMOUNT, INVALID_JUPITER_ROUTE_INSTRUCTION_DATA, INVALID_JUPITER_ROUTE_USER_ACCOUNT};
+use fusionamm_core::CoreError;
 use solana_instruction::Instruction;
 use solana_pubkey::Pubkey;
 
@@ -61,3 +63,513 @@ pub fn get_user_destination_token_account_address(ix: &Instruction) -> Option<Pu
         None
     }
 }
+
+/// Jupiter route instruction variants supported by the tuna program.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JupiterRouteKind {
+    Route,
+    RouteV2,
+    SharedAccountsRoute,
+    SharedAccountsRouteV2,
+    ExactOutRoute,
+    ExactOutRouteV2,
+    SharedAccountsExactOutRoute,
+    SharedAccountsExactOutRouteV2,
+}
+
+impl JupiterRouteKind {
+    pub fn from_discriminator(discriminator: &[u8]) -> Option<Self> {
+        if discriminator == JUPITER_ROUTE_DISCRIMINATOR {
+            Some(Self::Route)
+        } else if discriminator == JUPITER_ROUTE_V2_DISCRIMINATOR {
+            Some(Self::RouteV2)
+        } else if discriminator == JUPITER_SHARED_ACCOUNTS_ROUTE_DISCRIMINATOR {
+            Some(Self::SharedAccountsRoute)
+        } else if discriminator == JUPITER_SHARED_ACCOUNTS_ROUTE_V2_DISCRIMINATOR {
+            Some(Self::SharedAccountsRouteV2)
+        } else if discriminator == JUPITER_EXACT_OUT_ROUTE_DISCRIMINATOR {
+            Some(Self::ExactOutRoute)
+        } else if discriminator == JUPITER_EXACT_OUT_ROUTE_V2_DISCRIMINATOR {
+            Some(Self::ExactOutRouteV2)
+        } else if discriminator == JUPITER_SHARED_ACCOUNTS_EXACT_OUT_ROUTE_DISCRIMINATOR {
+            Some(Self::SharedAccountsExactOutRoute)
+        } else if discriminator == JUPITER_SHARED_ACCOUNTS_EXACT_OUT_ROUTE_V2_DISCRIMINATOR {
+            Some(Self::SharedAccountsExactOutRouteV2)
+        } else {
+            None
+        }
+    }
+
+    pub fn is_v2(&self) -> bool {
+        matches!(self, Self::RouteV2 | Self::SharedAccountsRouteV2 | Self::ExactOutRouteV2 | Self::SharedAccountsExactOutRouteV2)
+    }
+
+    pub fn is_shared_accounts(&self) -> bool {
+        matches!(
+            self,
+            Self::SharedAccountsRoute | Self::SharedAccountsRouteV2 | Self::SharedAccountsExactOutRoute | Self::SharedAccountsExactOutRouteV2
+        )
+    }
+
+    pub fn is_exact_out(&self) -> bool {
+        matches!(
+            self,
+            Self::ExactOutRoute | Self::ExactOutRouteV2 | Self::SharedAccountsExactOutRoute | Self::SharedAccountsExactOutRouteV2
+        )
+    }
+
+    /// Returns the indexes of the user transfer authority, source and destination token accounts in the instruction accounts.
+    pub fn user_account_indexes(&self) -> (usize, usize, usize) {
+        match self {
+            Self::RouteV2 | Self::ExactOutRouteV2 => (0, 1, 2),
+            Self::Route | Self::ExactOutRoute => (1, 2, 3),
+            Self::SharedAccountsRouteV2 | Self::SharedAccountsExactOutRouteV2 => (1, 2, 5),
+            Self::SharedAccountsRoute | Self::SharedAccountsExactOutRoute => (2, 3, 6),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JupiterRoutePlanStep {
+    /// Index of the `Swap` enum variant in the Jupiter program IDL.
+    pub swap: u8,
+    /// Borsh-encoded fields of the swap variant.
+    pub swap_data: Vec<u8>,
+    /// Share of the step input in basis points. The percent value of v1 routes is converted to basis points.
+    pub bps: u16,
+    pub input_index: u8,
+    pub output_index: u8,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JupiterRouteInstructionData {
+    pub kind: JupiterRouteKind,
+    /// Shared accounts program authority id. Always zero for non-shared routes.
+    pub id: u8,
+    /// The route plan, or None if a step uses a swap variant unknown to this decoder. The amounts, slippage and fees
+    /// are decoded either way.
+    pub route_plan: Option<Vec<JupiterRoutePlanStep>>,
+    /// Exact input amount, or the quoted input amount for exact-out routes.
+    pub in_amount: u64,
+    /// Quoted output amount, or the exact output amount for exact-out routes.
+    pub out_amount: u64,
+    pub slippage_bps: u16,
+    pub platform_fee_bps: u16,
+    /// Always zero for v1 routes.
+    pub positive_slippage_bps: u16,
+}
+
+impl JupiterRouteInstructionData {
+    /// Returns the maximum amount of input tokens the route can consume.
+    pub fn max_in_amount(&self) -> u64 {
+        if self.kind.is_exact_out() {
+            let max_in_amount = self.in_amount as u128 * (10000 + self.slippage_bps as u128) / 10000;
+            max_in_amount.min(u64::MAX as u128) as u64
+        } else {
+            self.in_amount
+        }
+    }
+}
+
+/// The size of the v1 route args following the route plan: in amount, quoted out amount, slippage bps and platform fee bps.
+const JUPITER_ROUTE_V1_ARGS_LEN: usize = 8 + 8 + 2 + 1;
+
+struct JupiterDataReader<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> JupiterDataReader<'a> {
+    fn read(&mut self, len: usize) -> Result<&'a [u8], CoreError> {
+        if self.data.len() < len {
+            return Err(INVALID_JUPITER_ROUTE_INSTRUCTION_DATA);
+        }
+        let (bytes, rest) = self.data.split_at(len);
+        self.data = rest;
+        Ok(bytes)
+    }
+
+    fn read_u8(&mut self) -> Result<u8, CoreError> {
+        Ok(self.read(1)?[0])
+    }
+
+    fn read_u16(&mut self) -> Result<u16, CoreError> {
+        Ok(u16::from_le_bytes(self.read(2)?.try_into().unwrap()))
+    }
+
+    fn read_u32(&mut self) -> Result<u32, CoreError> {
+        Ok(u32::from_le_bytes(self.read(4)?.try_into().unwrap()))
+    }
+
+    fn read_u64(&mut self) -> Result<u64, CoreError> {
+        Ok(u64::from_le_bytes(self.read(8)?.try_into().unwrap()))
+    }
+
+    /// Returns the borsh-encoded fields of the swap variant, or None if the variant is unknown.
+    ///
+    /// The field lengths follow the `Swap` enum of the Jupiter v6 program IDL (JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNyVTaV4),
+    /// as generated in the `jupiter-solana-client` 1.0.0 crate, variants 0 to 108. Variants added to the IDL later are unknown.
+    fn read_swap_data(&mut self, swap: u8) -> Result<Option<Vec<u8>>, CoreError> {
+        let data = self.data;
+
+        let len = match swap {
+            // Variants without fields.
+            0..=7
+            | 9..=11
+            | 13
+            | 14
+            | 19
+            | 20
+            | 22
+            | 25
+            | 26
+            | 30..=32
+            | 34..=38
+            | 40
+            | 46
+            | 48..=57
+            | 59
+            | 62
+            | 63
+            | 65..=70
+            | 72..=74
+            | 76..=80
+            | 83
+            | 84
+            | 88
+            | 90..=93
+            | 96..=102
+            | 105
+            | 108 => 0,
+            // Variants with a single bool or Side field.
+            8 | 12 | 15..=18 | 21 | 23 | 24 | 27 | 28 | 39 | 58 | 60 | 61 | 64 | 85 | 89 | 94 | 95 | 104 | 106 | 107 => 1,
+            // Symmetry
+            29 => 16,
+            // StakeDexSwapViaStake, StakeDexPrefundWithdrawStakeAndDepositStake
+            33 | 41 => 4,
+            // Clone
+            42 => 3,
+            // SanctumS
+            43 => 10,
+            // SanctumSAddLiquidity, SanctumSRemoveLiquidity
+            44 | 45 => 5,
+            // Perena, GoonFi
+            71 | 86 => 2,
+            // RaydiumLaunchlabBuy, RaydiumLaunchlabSell
+            81 | 82 => 8,
+            // HumidiFi
+            87 => 9,
+            // WhirlpoolSwapV2, DefiTuna: a_to_b and optional remaining accounts info.
+            47 | 103 => {
+                self.read_u8()?;
+                if self.read_u8()? != 0 {
+                    let slices = self.read_u32()? as usize;
+                    self.read(slices.checked_mul(2).ok_or(INVALID_JUPITER_ROUTE_INSTRUCTION_DATA)?)?;
+                }
+                0
+            }
+            // MeteoraDlmmSwapV2: remaining accounts info.
+            75 => {
+                let slices = self.read_u32()? as usize;
+                self.read(slices.checked_mul(2).ok_or(INVALID_JUPITER_ROUTE_INSTRUCTION_DATA)?)?;
+                0
+            }
+            _ => return Ok(None),
+        };
+        self.read(len)?;
+
+        Ok(Some(data[..data.len() - self.data.len()].to_vec()))
+    }
+
+    /// Returns the route plan, or None if a step uses an unknown swap variant. The length of an unknown variant can't be
+    /// known, so the rest of the data is left unread in that case.
+    fn read_route_plan(&mut self, v2: bool) -> Result<Option<Vec<JupiterRoutePlanStep>>, CoreError> {
+        let steps = self.read_u32()? as usize;
+        // Each step takes at least four bytes, so the length can't exceed the remaining data.
+        if steps > self.data.len() {
+            return Err(INVALID_JUPITER_ROUTE_INSTRUCTION_DATA);
+        }
+
+        let mut route_plan = Vec::with_capacity(steps);
+        for _ in 0..steps {
+            let swap = self.read_u8()?;
+            let Some(swap_data) = self.read_swap_data(swap)? else {
+                return Ok(None);
+            };
+            let bps = if v2 { self.read_u16()? } else { self.read_u8()? as u16 * 100 };
+            route_plan.push(JupiterRoutePlanStep {
+                swap,
+                swap_data,
+                bps,
+                input_index: self.read_u8()?,
+                output_index: self.read_u8()?,
+            });
+        }
+
+        Ok(Some(route_plan))
+    }
+}
+
+/// Decodes the data of a Jupiter route, shared accounts route or exact-out route instruction (v1 or v2).
+///
+/// The amounts, slippage and fees are located without walking the route plan: v2 routes encode them before the plan,
+/// v1 routes at the end of the data. So a route with a swap variant unknown to the decoder is still decoded, with no route plan.
+pub fn decode_jupiter_route_instruction_data(data: &[u8]) -> Result<JupiterRouteInstructionData, CoreError> {
+    if data.len() < 8 {
+        return Err(INVALID_JUPITER_ROUTE_INSTRUCTION_DATA);
+    }
+
+    let kind = JupiterRouteKind::from_discriminator(&data[0..8]).ok_or(INVALID_JUPITER_ROUTE_INSTRUCTION_DATA)?;
+    let mut reader = JupiterDataReader { data: &data[8..] };
+
+    let id = if kind.is_shared_accounts() { reader.read_u8()? } else { 0 };
+
+    let (amount, quoted_amount, slippage_bps, platform_fee_bps, positive_slippage_bps) = if kind.is_v2() {
+        (reader.read_u64()?, reader.read_u64()?, reader.read_u16()?, reader.read_u16()?, reader.read_u16()?)
+    } else {
+        let route_plan_len = reader
+            .data
+            .len()
+            .checked_sub(JUPITER_ROUTE_V1_ARGS_LEN)
+            .ok_or(INVALID_JUPITER_ROUTE_INSTRUCTION_DATA)?;
+        let mut args_reader = JupiterDataReader {
+            data: &reader.data[route_plan_len..],
+        };
+        reader.data = &reader.data[..route_plan_len];
+        (args_reader.read_u64()?, args_reader.read_u64()?, args_reader.read_u16()?, args_reader.read_u8()? as u16, 0)
+    };
+
+    let route_plan = reader.read_route_plan(kind.is_v2())?;
+
+    // The data of a decoded route plan must be consumed entirely.
+    if route_plan.is_some() && !reader.data.is_empty() {
+        return Err(INVALID_JUPITER_ROUTE_INSTRUCTION_DATA);
+    }
+
+    let (in_amount, out_amount) = if kind.is_exact_out() {
+        (quoted_amount, amount)
+    } else {
+        (amount, quoted_amount)
+    };
+
+    Ok(JupiterRouteInstructionData {
+        kind,
+        id,
+        route_plan,
+        in_amount,
+        out_amount,
+        slippage_bps,
+        platform_fee_bps,
+        positive_slippage_bps,
+    })
+}
+
+/// Checks the Jupiter route instruction the same way the tuna program does and returns the decoded route data.
+///
+/// # Parameters
+/// - `ix`: The Jupiter route instruction.
+/// - `user_transfer_authority`: The tuna position address.
+/// - `user_source_token_account`: The tuna position token account of the input token.
+/// - `user_destination_token_account`: The tuna position token account of the output token.
+/// - `input_amount`: The amount of input tokens the program is going to swap.
+pub fn validate_jupiter_route_instruction(
+    ix: &Instruction,
+    user_transfer_authority: &Pubkey,
+    user_source_token_account: &Pubkey,
+    user_destination_token_account: &Pubkey,
+    input_amount: u64,
+) -> Result<JupiterRouteInstructionData, CoreError> {
+    let route = decode_jupiter_route_instruction_data(&ix.data)?;
+
+    // A route plan with unknown swap variants can't be checked here and is left to the program.
+    if route.route_plan.as_ref().is_some_and(|route_plan| route_plan.is_empty()) {
+        return Err(INVALID_JUPITER_ROUTE_INSTRUCTION_DATA);
+    }
+
+    let (authority_index, source_index, destination_index) = route.kind.user_account_indexes();
+    if ix.accounts.len() <= authority_index.max(source_index).max(destination_index) {
+        return Err(INVALID_JUPITER_ROUTE_INSTRUCTION_DATA);
+    }
+
+    if ix.accounts[authority_index].pubkey != *user_transfer_authority
+        || ix.accounts[source_index].pubkey != *user_source_token_account
+        || ix.accounts[destination_index].pubkey != *user_destination_token_account
+    {
+        return Err(INVALID_JUPITER_ROUTE_USER_ACCOUNT);
+    }
+
+    if route.kind.is_exact_out() {
+        if route.max_in_amount() > input_amount {
+            return Err(INCORRECT_JUPITER_SWAP_INPUT_AMOUNT);
+        }
+    } else if route.in_amount != input_amount {
+        return Err(INCORRECT_JUPITER_SWAP_INPUT_AMOUNT);
+    }
+
+    Ok(route)
+}
+
+#[cfg(all(test, not(feature = "wasm")))]
+mod tests {
+    use super::*;
+    use solana_instruction::AccountMeta;
+
+    fn route_v2_data(in_amount: u64, slippage_bps: u16) -> Vec<u8> {
+        let mut data = JUPITER_ROUTE_V2_DISCRIMINATOR.to_vec();
+        data.extend_from_slice(&in_amount.to_le_bytes());
+        data.extend_from_slice(&990u64.to_le_bytes());
+        data.extend_from_slice(&slippage_bps.to_le_bytes());
+        data.extend_from_slice(&20u16.to_le_bytes());
+        data.extend_from_slice(&0u16.to_le_bytes());
+        // Two steps: WhirlpoolSwapV2 with remaining accounts info and RaydiumCP.
+        data.extend_from_slice(&2u32.to_le_bytes());
+        data.extend_from_slice(&[47, 1, 1, 1, 0, 0, 0, 5, 2]);
+        data.extend_from_slice(&6000u16.to_le_bytes());
+        data.extend_from_slice(&[0, 1]);
+        data.push(46);
+        data.extend_from_slice(&4000u16.to_le_bytes());
+        data.extend_from_slice(&[0, 1]);
+        data
+    }
+
+    fn shared_accounts_exact_out_route_data(out_amount: u64, quoted_in_amount: u64, slippage_bps: u16) -> Vec<u8> {
+        let mut data = JUPITER_SHARED_ACCOUNTS_EXACT_OUT_ROUTE_DISCRIMINATOR.to_vec();
+        data.push(3);
+        // One step: Whirlpool { a_to_b: true }, 100%.
+        data.extend_from_slice(&1u32.to_le_bytes());
+        data.extend_from_slice(&[17, 1, 100, 0, 1]);
+        data.extend_from_slice(&out_amount.to_le_bytes());
+        data.extend_from_slice(&quoted_in_amount.to_le_bytes());
+        data.extend_from_slice(&slippage_bps.to_le_bytes());
+        data.push(10);
+        data
+    }
+
+    fn route_instruction(data: Vec<u8>, accounts: &[Pubkey]) -> Instruction {
+        Instruction {
+            program_id: Pubkey::new_unique(),
+            accounts: accounts.iter().map(|pubkey| AccountMeta::new(*pubkey, false)).collect(),
+            data,
+        }
+    }
+
+    #[test]
+    fn test_decode_route_v2() {
+        let route = decode_jupiter_route_instruction_data(&route_v2_data(1000, 50)).unwrap();
+        assert_eq!(route.kind, JupiterRouteKind::RouteV2);
+        assert_eq!(route.in_amount, 1000);
+        assert_eq!(route.out_amount, 990);
+        assert_eq!(route.slippage_bps, 50);
+        assert_eq!(route.platform_fee_bps, 20);
+        let route_plan = route.route_plan.unwrap();
+        assert_eq!(route_plan.len(), 2);
+        assert_eq!(route_plan[0].swap, 47);
+        assert_eq!(route_plan[0].swap_data, vec![1, 1, 1, 0, 0, 0, 5, 2]);
+        assert_eq!(route_plan[0].bps, 6000);
+        assert_eq!(route_plan[1].swap, 46);
+        assert!(route_plan[1].swap_data.is_empty());
+        assert_eq!(route_plan[1].bps, 4000);
+    }
+
+    #[test]
+    fn test_decode_shared_accounts_exact_out_route() {
+        let route = decode_jupiter_route_instruction_data(&shared_accounts_exact_out_route_data(500, 1000, 100)).unwrap();
+        assert_eq!(route.kind, JupiterRouteKind::SharedAccountsExactOutRoute);
+        assert_eq!(route.id, 3);
+        assert_eq!(route.in_amount, 1000);
+        assert_eq!(route.out_amount, 500);
+        assert_eq!(route.platform_fee_bps, 10);
+        assert_eq!(route.route_plan.as_ref().unwrap()[0].bps, 10000);
+        assert_eq!(route.max_in_amount(), 1010);
+    }
+
+    #[test]
+    fn test_decode_route_with_unknown_swap_variant() {
+        // The second step of the v2 route is replaced by a variant unknown to the decoder.
+        let mut data = route_v2_data(1000, 50);
+        let second_step = data.len() - 5;
+        data[second_step] = 200;
+        let route = decode_jupiter_route_instruction_data(&data).unwrap();
+        assert_eq!(route.route_plan, None);
+        assert_eq!(route.in_amount, 1000);
+        assert_eq!(route.out_amount, 990);
+        assert_eq!(route.slippage_bps, 50);
+        assert_eq!(route.platform_fee_bps, 20);
+
+        // The args of v1 routes follow the route plan, so they are read from the end of the data.
+        let mut data = shared_accounts_exact_out_route_data(500, 1000, 100);
+        data[13] = 200;
+        let route = decode_jupiter_route_instruction_data(&data).unwrap();
+        assert_eq!(route.route_plan, None);
+        assert_eq!(route.in_amount, 1000);
+        assert_eq!(route.out_amount, 500);
+        assert_eq!(route.slippage_bps, 100);
+        assert_eq!(route.platform_fee_bps, 10);
+
+        // The user accounts and amounts are still validated.
+        let authority = Pubkey::new_unique();
+        let source = Pubkey::new_unique();
+        let destination = Pubkey::new_unique();
+        let mut data = route_v2_data(1000, 50);
+        data[second_step] = 200;
+        let ix = route_instruction(data, &[authority, source, destination]);
+        assert!(validate_jupiter_route_instruction(&ix, &authority, &source, &destination, 1000).is_ok());
+        assert_eq!(
+            validate_jupiter_route_instruction(&ix, &authority, &source, &destination, 999),
+            Err(INCORRECT_JUPITER_SWAP_INPUT_AMOUNT)
+        );
+    }
+
+    #[test]
+    fn test_decode_invalid_data() {
+        let mut data = route_v2_data(1000, 50);
+        data.push(0);
+        assert_eq!(decode_jupiter_route_instruction_data(&data), Err(INVALID_JUPITER_ROUTE_INSTRUCTION_DATA));
+
+        let data = route_v2_data(1000, 50);
+        assert_eq!(decode_jupiter_route_instruction_data(&data[..data.len() - 1]), Err(INVALID_JUPITER_ROUTE_INSTRUCTION_DATA));
+
+        let mut data = route_v2_data(1000, 50);
+        data[0] = 0;
+        assert_eq!(decode_jupiter_route_instruction_data(&data), Err(INVALID_JUPITER_ROUTE_INSTRUCTION_DATA));
+    }
+
+    #[test]
+    fn test_validate_route_instruction() {
+        let authority = Pubkey::new_unique();
+        let source = Pubkey::new_unique();
+        let destination = Pubkey::new_unique();
+
+        let ix = route_instruction(route_v2_data(1000, 50), &[authority, source, destination]);
+        assert!(validate_jupiter_route_instruction(&ix, &authority, &source, &destination, 1000).is_ok());
+        assert_eq!(
+            validate_jupiter_route_instruction(&ix, &authority, &source, &destination, 999),
+            Err(INCORRECT_JUPITER_SWAP_INPUT_AMOUNT)
+        );
+        assert_eq!(
+            validate_jupiter_route_instruction(&ix, &authority, &destination, &source, 1000),
+            Err(INVALID_JUPITER_ROUTE_USER_ACCOUNT)
+        );
+
+        let accounts = [
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            authority,
+            source,
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            destination,
+        ];
+        let ix = route_instruction(shared_accounts_exact_out_route_data(500, 1000, 100), &accounts);
+        assert!(validate_jupiter_route_instruction(&ix, &authority, &source, &destination, 1010).is_ok());
+        assert_eq!(
+            validate_jupiter_route_instruction(&ix, &authority, &source, &destination, 1009),
+            Err(INCORRECT_JUPITER_SWAP_INPUT_AMOUNT)
+        );
+
+        let ix = route_instruction(shared_accounts_exact_out_route_data(500, 1000, 100), &accounts[..6]);
+        assert_eq!(
+            validate_jupiter_route_instruction(&ix, &authority, &source, &destination, 1010),
+            Err(INVALID_JUPITER_ROUTE_INSTRUCTION_DATA)
+        );
+    }
+}